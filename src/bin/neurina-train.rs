@@ -57,6 +57,14 @@ enum NetworkVersion
     V3,
 }
 
+#[derive(ValueEnum, Copy, Clone, Debug)]
+#[clap(rename_all = "lower")]
+enum Backend
+{
+    OpenCl,
+    Cuda,
+}
+
 #[derive(Parser, Debug)]
 #[command(version)]
 struct Args
@@ -94,6 +102,18 @@ struct Args
     /// Network version
     #[arg(short = 'v', long, value_name = "VERSION", value_enum, default_value_t = NetworkVersion::V3)]
     network_version: NetworkVersion,
+    /// Backend kind
+    #[arg(long, value_enum)]
+    backend: Option<Backend>,
+}
+
+fn backend_kind(backend: Option<Backend>) -> Option<BackendKind>
+{
+    match backend {
+        Some(Backend::OpenCl) => Some(BackendKind::OpenCl),
+        Some(Backend::Cuda) => Some(BackendKind::Cuda),
+        None => None,
+    }
 }
 
 fn initialize_sampler(args: &Args) -> Arc<dyn Sample + Send + Sync>
@@ -400,7 +420,11 @@ fn main()
         },
         None => (),
     }
-    match initialize_backend(&config) {
+    if selected_backend_kind(&config, backend_kind(args.backend)) == Some(BackendKind::Cpu) {
+        eprintln!("training isn't supported by cpu backend");
+        exit(1);
+    }
+    match initialize_backend_with_kind(&config, backend_kind(args.backend)) {
         Ok(()) => (),
         Err(err) => {
             eprintln!("{}", err);
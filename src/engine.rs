@@ -11,46 +11,68 @@ use crate::matrix;
 
 pub use fathom_syzygy as fathom;
 
+pub(crate) mod adjudicator;
+pub(crate) mod book;
+pub(crate) mod draw;
 pub(crate) mod engine;
 pub(crate) mod engine_id;
+pub(crate) mod engine_option;
 pub(crate) mod eval;
 pub(crate) mod intr_checker;
 pub(crate) mod io;
+pub(crate) mod mcts_searcher;
 pub(crate) mod middle_searcher;
+pub(crate) mod move_orderer;
 pub(crate) mod neural_search;
 pub(crate) mod neural_searcher;
 pub(crate) mod one_neural_searcher;
 pub(crate) mod one_searcher;
 pub(crate) mod print;
 pub(crate) mod protocol;
+pub(crate) mod quiescence_searcher;
 pub(crate) mod random_eval_fun;
 pub(crate) mod search;
 pub(crate) mod simple_eval_fun;
+pub(crate) mod strength_limit;
 pub(crate) mod syzygy;
+#[cfg(test)]
+pub(crate) mod test_neural_searcher;
 pub(crate) mod thinker;
+pub(crate) mod transposition_table;
 pub(crate) mod uci;
 pub(crate) mod utils;
+pub(crate) mod wdl_model;
 pub(crate) mod xboard;
 
+pub use adjudicator::*;
+pub use book::*;
+pub use draw::*;
 pub use engine::*;
 pub use engine_id::*;
+pub use engine_option::*;
 pub use eval::*;
 pub use intr_checker::*;
 pub use io::*;
+pub use mcts_searcher::*;
 pub use middle_searcher::*;
+pub use move_orderer::*;
 pub use neural_search::*;
 pub use neural_searcher::*;
 pub use one_neural_searcher::*;
 pub use one_searcher::*;
 pub use print::*;
 pub use protocol::*;
+pub use quiescence_searcher::*;
 pub use random_eval_fun::*;
 pub use search::*;
 pub use simple_eval_fun::*;
+pub use strength_limit::*;
 pub use syzygy::*;
 pub use thinker::*;
+pub use transposition_table::*;
 pub use uci::*;
 pub use utils::*;
+pub use wdl_model::*;
 pub use xboard::*;
 
 /// An enumeration of loop error.
@@ -67,6 +89,8 @@ pub enum LoopError
     Fathom(fathom::Error),
     /// Can't initialize a loop context.
     UninitializedLoopContext,
+    /// The engine doesn't have a searcher loader.
+    NoSearcherLoader,
     /// Can't recognize a protocol.
     UnrecognizedProtocol,
 }
@@ -84,6 +108,7 @@ impl fmt::Display for LoopError
             LoopError::Matrix(err) => write!(f, "{}", err),
             LoopError::Fathom(err) => write!(f, "{}", err),
             LoopError::UninitializedLoopContext => write!(f, "uninitialized loop context"),
+            LoopError::NoSearcherLoader => write!(f, "no searcher loader"),
             LoopError::UnrecognizedProtocol => write!(f, "unrecognized protocol"),
         }
     }
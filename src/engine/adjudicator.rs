@@ -0,0 +1,138 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+/// A structure of adjudicator.
+///
+/// The adjudicator decides whether the engine resigns, offers a draw, or accepts a draw from
+/// search values of the moves of engine. The engine resigns if the search values are less than or
+/// equal to the negated resign value over the number of consecutive moves for resignation. The
+/// engine offers a draw if absolute search values are less than or equal to the draw value over
+/// the number of consecutive moves for a draw offer, but not before the minimal move number for a
+/// draw offer. The number of consecutive moves that is equal to zero disables resignation or draw
+/// offers.
+#[derive(Clone, Debug)]
+pub struct Adjudicator
+{
+    /// A resign value.
+    pub resign_value: i32,
+    /// A number of consecutive moves for resignation.
+    pub resign_move_count: usize,
+    /// A draw value.
+    pub draw_value: i32,
+    /// A number of consecutive moves for a draw offer.
+    pub draw_move_count: usize,
+    /// A minimal move number from which a draw can be offered.
+    pub min_draw_offer_move_number: usize,
+    values: Vec<i32>,
+}
+
+impl Adjudicator
+{
+    /// A default resign value.
+    pub const DEFAULT_RESIGN_VALUE: i32 = 1000;
+
+    /// A minimal resign value.
+    pub const MIN_RESIGN_VALUE: i32 = 100;
+
+    /// A maximal resign value.
+    pub const MAX_RESIGN_VALUE: i32 = 32000;
+
+    /// A default number of consecutive moves for resignation.
+    pub const DEFAULT_RESIGN_MOVE_COUNT: usize = 5;
+
+    /// A default draw value.
+    pub const DEFAULT_DRAW_VALUE: i32 = 10;
+
+    /// A minimal draw value.
+    pub const MIN_DRAW_VALUE: i32 = 0;
+
+    /// A maximal draw value.
+    pub const MAX_DRAW_VALUE: i32 = 1000;
+
+    /// A default number of consecutive moves for a draw offer.
+    pub const DEFAULT_DRAW_MOVE_COUNT: usize = 10;
+
+    /// A maximal number of consecutive moves for resignation or a draw offer.
+    pub const MAX_MOVE_COUNT: usize = 100;
+
+    /// A default minimal move number from which a draw can be offered.
+    pub const DEFAULT_MIN_DRAW_OFFER_MOVE_NUMBER: usize = 40;
+
+    /// A maximal minimal move number from which a draw can be offered.
+    pub const MAX_MIN_DRAW_OFFER_MOVE_NUMBER: usize = 1000;
+
+    /// Creates an adjudicator with the default thresholds.
+    pub fn new() -> Self
+    {
+        Adjudicator {
+            resign_value: Self::DEFAULT_RESIGN_VALUE,
+            resign_move_count: Self::DEFAULT_RESIGN_MOVE_COUNT,
+            draw_value: Self::DEFAULT_DRAW_VALUE,
+            draw_move_count: Self::DEFAULT_DRAW_MOVE_COUNT,
+            min_draw_offer_move_number: Self::DEFAULT_MIN_DRAW_OFFER_MOVE_NUMBER,
+            values: Vec::new(),
+        }
+    }
+
+    /// Adds the search value of move of engine.
+    pub fn push_value(&mut self, value: i32)
+    {
+        self.values.push(value);
+        let max_len = self.resign_move_count.max(self.draw_move_count).max(1);
+        if self.values.len() > max_len {
+            self.values.drain(0..(self.values.len() - max_len));
+        }
+    }
+
+    /// Removes all search values.
+    ///
+    /// This method should be called for a new game or a move of engine without a search value.
+    pub fn clear_values(&mut self)
+    { self.values.clear(); }
+
+    /// Returns `true` if the engine should resign, otherwise `false`.
+    pub fn can_resign(&self) -> bool
+    {
+        if self.resign_move_count == 0 || self.values.len() < self.resign_move_count {
+            return false;
+        }
+        self.values[(self.values.len() - self.resign_move_count)..].iter().all(|value| *value <= -self.resign_value)
+    }
+
+    /// Returns `true` if the engine should offer a draw at the move number, otherwise `false`.
+    pub fn can_offer_draw(&self, move_number: usize) -> bool
+    {
+        if self.draw_move_count == 0 || self.values.len() < self.draw_move_count || move_number < self.min_draw_offer_move_number {
+            return false;
+        }
+        self.values[(self.values.len() - self.draw_move_count)..].iter().all(|value| value.abs() <= self.draw_value)
+    }
+
+    /// Returns `true` if the engine should accept a draw offer from the opponent, otherwise
+    /// `false`.
+    ///
+    /// The draw offer is accepted if the last search value is less than or equal to the draw
+    /// value. This condition isn't symmetric, so the draw offer is also accepted if the engine is
+    /// far behind, because a draw is then better for the engine than an expected loss.
+    pub fn can_accept_draw(&self) -> bool
+    {
+        match self.values.last() {
+            Some(value) => *value <= self.draw_value,
+            None => false,
+        }
+    }
+}
+
+impl Default for Adjudicator
+{
+    fn default() -> Self
+    { Self::new() }
+}
+
+#[cfg(test)]
+mod tests;
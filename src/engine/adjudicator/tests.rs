@@ -0,0 +1,79 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use super::*;
+
+#[test]
+fn test_adjudicator_can_resign_returns_true_for_consecutive_losing_values()
+{
+    let mut adjudicator = Adjudicator::new();
+    adjudicator.resign_value = 500;
+    adjudicator.resign_move_count = 3;
+    adjudicator.push_value(-600);
+    adjudicator.push_value(-700);
+    assert!(!adjudicator.can_resign());
+    adjudicator.push_value(-800);
+    assert!(adjudicator.can_resign());
+    adjudicator.push_value(-100);
+    assert!(!adjudicator.can_resign());
+    adjudicator.resign_move_count = 0;
+    adjudicator.clear_values();
+    adjudicator.push_value(-900);
+    assert!(!adjudicator.can_resign());
+}
+
+#[test]
+fn test_adjudicator_can_offer_draw_returns_true_for_consecutive_draw_values()
+{
+    let mut adjudicator = Adjudicator::new();
+    adjudicator.draw_value = 20;
+    adjudicator.draw_move_count = 2;
+    adjudicator.push_value(15);
+    adjudicator.push_value(-10);
+    assert!(adjudicator.can_offer_draw(Adjudicator::DEFAULT_MIN_DRAW_OFFER_MOVE_NUMBER));
+    assert!(!adjudicator.can_offer_draw(Adjudicator::DEFAULT_MIN_DRAW_OFFER_MOVE_NUMBER - 1));
+    adjudicator.push_value(30);
+    assert!(!adjudicator.can_offer_draw(Adjudicator::DEFAULT_MIN_DRAW_OFFER_MOVE_NUMBER));
+}
+
+#[test]
+fn test_adjudicator_can_offer_draw_returns_true_from_minimal_move_number_for_draw_offer()
+{
+    let mut adjudicator = Adjudicator::new();
+    adjudicator.draw_value = 20;
+    adjudicator.draw_move_count = 2;
+    adjudicator.min_draw_offer_move_number = 10;
+    adjudicator.push_value(5);
+    adjudicator.push_value(-5);
+    assert!(adjudicator.can_offer_draw(10));
+    assert!(!adjudicator.can_offer_draw(9));
+    adjudicator.min_draw_offer_move_number = 0;
+    assert!(adjudicator.can_offer_draw(1));
+}
+
+#[test]
+fn test_adjudicator_can_accept_draw_returns_true_for_last_value_not_greater_than_draw_value()
+{
+    let mut adjudicator = Adjudicator::new();
+    adjudicator.draw_value = 20;
+    assert!(!adjudicator.can_accept_draw());
+    adjudicator.push_value(-300);
+    assert!(adjudicator.can_accept_draw());
+    adjudicator.push_value(50);
+    assert!(!adjudicator.can_accept_draw());
+}
+
+#[test]
+fn test_adjudicator_can_accept_draw_returns_true_for_far_behind_last_value()
+{
+    let mut adjudicator = Adjudicator::new();
+    adjudicator.draw_value = 20;
+    adjudicator.push_value(-Adjudicator::MAX_RESIGN_VALUE);
+    assert!(adjudicator.can_accept_draw());
+    adjudicator.push_value(21);
+    assert!(!adjudicator.can_accept_draw());
+}
@@ -0,0 +1,119 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::io::Cursor;
+use super::*;
+
+fn polyglot_move(src: usize, dst: usize) -> u16
+{ ((src << 6) | dst) as u16 }
+
+fn book_data(entries: &[(u64, u16, u16)]) -> Vec<u8>
+{
+    let mut data: Vec<u8> = Vec::new();
+    for (key, mv, weight) in entries {
+        data.extend_from_slice(&key.to_be_bytes());
+        data.extend_from_slice(&mv.to_be_bytes());
+        data.extend_from_slice(&weight.to_be_bytes());
+        data.extend_from_slice(&0u32.to_be_bytes());
+    }
+    data
+}
+
+#[test]
+fn test_polyglot_key_calculates_polyglot_keys()
+{
+    let fens_and_keys = [
+        ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", 0x463b96181691fc9cu64),
+        ("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1", 0x823c9b50fd114196),
+        ("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2", 0x0756b94461c50fb0),
+        ("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 2", 0x662fafb965db29d4),
+        ("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3", 0x22a48b5a8e47ff78),
+        ("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR b kq - 0 3", 0x652a607ca3f242c1),
+        ("rnbq1bnr/ppp1pkpp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR w - - 0 4", 0x00fdd303c946bdd9),
+        ("rnbqkbnr/p1pppppp/8/8/PpP4P/8/1P1PPPP1/RNBQKBNR b KQkq c3 0 3", 0x3c8123ea7b067637),
+        ("rnbqkbnr/p1pppppp/8/8/P6P/R1p5/1P1PPPP1/1NBQKBNR b Kkq - 0 4", 0x5c3f9b829b279560)
+    ];
+    for (fen, key) in &fens_and_keys {
+        let board = Board::from_fen(fen).unwrap();
+        assert_eq!(*key, polyglot_key(&board));
+    }
+}
+
+#[test]
+fn test_book_read_reads_book_and_returns_moves()
+{
+    let board = Board::initial();
+    let key = polyglot_key(&board);
+    let data = book_data(&[
+            (key + 1, polyglot_move(11, 27), 10),
+            (key, polyglot_move(12, 28), 20),
+            (key - 1, polyglot_move(6, 21), 10),
+            (key, polyglot_move(11, 27), 30),
+            (key, polyglot_move(12, 36), 40)
+    ]);
+    let mut cursor = Cursor::new(data);
+    match Book::read(&mut cursor) {
+        Ok(book) => {
+            assert_eq!(5, book.entry_count());
+            assert_eq!(3, book.entries(key).len());
+            let moves = book.moves(&board);
+            assert_eq!(2, moves.len());
+            assert_eq!((Move::from_uci("e2e4", &board).unwrap(), 20), moves[0]);
+            assert_eq!((Move::from_uci("d2d4", &board).unwrap(), 30), moves[1]);
+            assert_eq!(Some(Move::from_uci("d2d4", &board).unwrap()), book.best_move(&board));
+            match book.random_move(&board) {
+                Some(mv) => assert!(moves.iter().any(|(mv2, _)| *mv2 == mv)),
+                None => assert!(false),
+            }
+        },
+        Err(_) => assert!(false),
+    }
+}
+
+#[test]
+fn test_book_moves_converts_castlings()
+{
+    let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let key = polyglot_key(&board);
+    let data = book_data(&[(key, polyglot_move(4, 7), 1), (key, polyglot_move(4, 0), 1)]);
+    let mut cursor = Cursor::new(data);
+    match Book::read(&mut cursor) {
+        Ok(book) => {
+            let moves = book.moves(&board);
+            assert_eq!(2, moves.len());
+            assert_eq!(Move::from_uci("e1g1", &board).unwrap(), moves[0].0);
+            assert_eq!(Move::from_uci("e1c1", &board).unwrap(), moves[1].0);
+        },
+        Err(_) => assert!(false),
+    }
+}
+
+#[test]
+fn test_book_best_move_and_random_move_skip_moves_with_zero_weights()
+{
+    let board = Board::initial();
+    let key = polyglot_key(&board);
+    let data = book_data(&[(key, polyglot_move(12, 28), 0)]);
+    let mut cursor = Cursor::new(data);
+    match Book::read(&mut cursor) {
+        Ok(book) => {
+            assert_eq!(None, book.best_move(&board));
+            assert_eq!(None, book.random_move(&board));
+        },
+        Err(_) => assert!(false),
+    }
+}
+
+#[test]
+fn test_book_read_complains_on_invalid_book_size()
+{
+    let mut cursor = Cursor::new(vec![0u8; 17]);
+    match Book::read(&mut cursor) {
+        Err(err) => assert_eq!(ErrorKind::InvalidData, err.kind()),
+        Ok(_) => assert!(false),
+    }
+}
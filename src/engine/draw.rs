@@ -0,0 +1,58 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::chess::Board;
+use crate::chess::MoveChain;
+
+/// A number of half moves for the fifty-move rule.
+pub const FIFTY_MOVE_HALF_MOVE_COUNT: usize = 100;
+
+/// Returns the history of the move chain.
+///
+/// The history contains Zobrist hashes of all boards of the move chain. The last hash is a Zobrist
+/// hash of the last board.
+pub fn move_chain_history(move_chain: &MoveChain) -> Vec<u64>
+{
+    let mut history: Vec<u64> = Vec::with_capacity(move_chain.len() + 1);
+    let mut walker = move_chain.walk();
+    while let Some((board, _)) = walker.next() {
+        history.push(board.zobrist_hash());
+    }
+    history.push(move_chain.last().zobrist_hash());
+    history
+}
+
+/// Returns `true` if the board repeats a board from the history, otherwise `false`.
+///
+/// The history contains Zobrist hashes of boards that precede the board. Only boards after the last
+/// irreversible move with the same side are compared.
+pub fn is_repetition(board: &Board, history: &[u64]) -> bool
+{
+    let zobrist_hash = board.zobrist_hash();
+    let reversible_count = (board.raw().move_counter as usize).min(history.len());
+    let mut i = 2usize;
+    while i <= reversible_count {
+        if history[history.len() - i] == zobrist_hash {
+            return true;
+        }
+        i += 2;
+    }
+    false
+}
+
+/// Returns `true` if the board is a draw by the fifty-move rule, otherwise `false`.
+///
+/// A checkmate at the last half move isn't a draw.
+pub fn is_fifty_move_draw(board: &Board) -> bool
+{ (board.raw().move_counter as usize) >= FIFTY_MOVE_HALF_MOVE_COUNT && (!board.is_check() || board.has_legal_moves()) }
+
+/// Returns `true` if the board is a draw by repetition or the fifty-move rule, otherwise `false`.
+pub fn is_draw_by_history(board: &Board, history: &[u64]) -> bool
+{ is_fifty_move_draw(board) || is_repetition(board, history) }
+
+#[cfg(test)]
+mod tests;
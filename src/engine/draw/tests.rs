@@ -0,0 +1,50 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::chess::Move;
+use super::*;
+
+#[test]
+fn test_move_chain_history_returns_history()
+{
+    let move_chain = MoveChain::from_uci_list(Board::initial(), "g1f3 g8f6").unwrap();
+    let history = move_chain_history(&move_chain);
+    assert_eq!(3, history.len());
+    assert_eq!(Board::initial().zobrist_hash(), history[0]);
+    assert_eq!(move_chain.last().zobrist_hash(), history[2]);
+}
+
+#[test]
+fn test_is_repetition_returns_true_for_repeated_board()
+{
+    let move_chain = MoveChain::from_uci_list(Board::initial(), "g1f3 g8f6 f3g1").unwrap();
+    let mut history = move_chain_history(&move_chain);
+    let board = move_chain.last().make_move(Move::from_uci("f6g8", move_chain.last()).unwrap()).unwrap();
+    assert!(is_repetition(&board, history.as_slice()));
+    assert!(!is_draw_by_history(move_chain.last(), &history[0..3]));
+    history.clear();
+    assert!(!is_repetition(&board, history.as_slice()));
+}
+
+#[test]
+fn test_is_repetition_returns_false_after_irreversible_move()
+{
+    let move_chain = MoveChain::from_uci_list(Board::initial(), "g1f3 g8f6 f3g1 f6g8 e2e4").unwrap();
+    let history = move_chain_history(&move_chain);
+    assert!(!is_repetition(move_chain.last(), &history[0..(history.len() - 1)]));
+}
+
+#[test]
+fn test_is_fifty_move_draw_returns_true_for_hundred_half_moves()
+{
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 100 80").unwrap();
+    assert!(is_fifty_move_draw(&board));
+    let board2 = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 99 80").unwrap();
+    assert!(!is_fifty_move_draw(&board2));
+    let board3 = Board::from_fen("R3k3/8/4K3/8/8/8/8/8 b - - 100 80").unwrap();
+    assert!(!is_fifty_move_draw(&board3));
+}
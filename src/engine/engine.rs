@@ -5,6 +5,9 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 //
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::mpsc::channel;
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
@@ -17,7 +20,11 @@ use crate::chess::types::OutcomeFilter;
 use crate::chess::Move;
 use crate::chess::MoveChain;
 use crate::engine::print::*;
+use crate::engine::search::*;
 use crate::engine::thinker::*;
+use crate::engine::transposition_table::*;
+use crate::engine::LoopError;
+use crate::engine::LoopResult;
 
 /// An enumeration of time control.
 #[derive(Copy, Clone, Debug)]
@@ -30,18 +37,85 @@ pub enum TimeControl
     Fixed(Duration),
 }
 
-#[derive(Clone, Debug)]
-struct ThinkingParams
+/// An enumeration of network version.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum NetworkVersionKind
+{
+    /// A first version of neural network.
+    V1,
+    /// A second version of neural network.
+    V2,
+    /// A third version of neural network.
+    V3,
+}
+
+impl NetworkVersionKind
+{
+    /// Parses a network version from a name.
+    ///
+    /// The names of network versions are `v1`, `v2`, and `v3`.
+    pub fn parse(s: &str) -> Option<Self>
+    {
+        match s {
+            "v1" => Some(NetworkVersionKind::V1),
+            "v2" => Some(NetworkVersionKind::V2),
+            "v3" => Some(NetworkVersionKind::V3),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of network version.
+    pub fn name(&self) -> &'static str
+    {
+        match self {
+            NetworkVersionKind::V1 => "v1",
+            NetworkVersionKind::V2 => "v2",
+            NetworkVersionKind::V3 => "v3",
+        }
+    }
+}
+
+/// A type of searcher loader.
+///
+/// The searcher loader loads a neural network of the network version from the network file and
+/// creates a searcher that uses this neural network. The created searcher has the specified size
+/// of transposition table or tree of Monte-Carlo tree search in megabytes.
+pub type SearcherLoader = dyn Fn(&str, NetworkVersionKind, usize) -> LoopResult<Arc<dyn Search + Send + Sync>>;
+
+/// A number of moves to go that is assumed for a time control on all game.
+const SUDDEN_DEATH_MOVE_COUNT_TO_GO: usize = 40;
+
+/// A maximal ratio of the hard timeout to the soft timeout.
+const MAX_HARD_TIMEOUT_RATIO: u32 = 4;
+
+fn calculate_timeouts(time_control: TimeControl, remaining_time: Duration, move_count_to_go: usize, move_overhead: Duration, move_chain_len: usize) -> (Option<Duration>, Duration)
 {
-    search_moves: Option<Vec<Move>>,
-    depth: Option<usize>,
-    node_count: Option<u64>,
-    move_count_to_checkmate: Option<usize>,
-    now: Instant,
-    timeout: Option<Duration>,
-    can_make_best_move: bool,
-    can_print_pv: bool,
-    can_print_best_move_and_outcome: bool,
+    match time_control {
+        TimeControl::Level(mps, inc) => {
+            let move_count_to_go = if move_count_to_go > 0 {
+                move_count_to_go
+            } else {
+                if mps > 0 {
+                    mps - (move_chain_len / 2) % mps
+                } else {
+                    SUDDEN_DEATH_MOVE_COUNT_TO_GO
+                }
+            };
+            // The increments of next moves are added to the remaining time and the move overheads
+            // of all moves are subtracted from the remaining time.
+            let total_time = (remaining_time + inc * ((move_count_to_go - 1) as u32)).saturating_sub(move_overhead * (move_count_to_go as u32));
+            let available_time = remaining_time.saturating_sub(move_overhead);
+            let max_timeout = if move_count_to_go > 1 {
+                available_time * 3 / 4
+            } else {
+                available_time
+            };
+            let soft_timeout = total_time / (move_count_to_go as u32);
+            let timeout = (soft_timeout * MAX_HARD_TIMEOUT_RATIO).min(max_timeout);
+            (Some(soft_timeout.min(timeout)), timeout)
+        },
+        TimeControl::Fixed(time) => (None, time.saturating_sub(move_overhead)),
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -54,20 +128,39 @@ enum ThreadCommand
 /// An engine structure.
 ///
 /// The engine controls a game, a time, and iterative searches. The iterative searches are
-/// performed in other thread.
+/// performed in other thread. The time for a move is divided into a soft timeout after which a
+/// new iteration of search isn't started and a hard timeout after which the search is aborted.
+/// The engine can change a neural network between games by a searcher loader.
 pub struct Engine
 {
     thread: JoinHandle<()>,
     sender: Sender<ThreadCommand>,
     thinker: Arc<Thinker>,
     move_chain: Arc<Mutex<MoveChain>>,
+    is_ponder_timeout: AtomicBool,
+    ponder_move_chain_len: AtomicUsize,
     time_control: TimeControl,
     remaining_time: Duration,
     move_count_to_go: usize,
+    move_overhead: Duration,
+    hash_size: usize,
+    searcher_loader: Option<Box<SearcherLoader>>,
+    network_file: String,
+    network_version: NetworkVersionKind,
+    has_network_change: bool,
 }
 
 impl Engine
 {
+    /// A default move overhead.
+    pub const DEFAULT_MOVE_OVERHEAD: Duration = Duration::from_millis(100);
+
+    /// A minimal move overhead.
+    pub const MIN_MOVE_OVERHEAD: Duration = Duration::ZERO;
+
+    /// A maximal move overhead.
+    pub const MAX_MOVE_OVERHEAD: Duration = Duration::from_millis(5000);
+
     /// Creates an engine.
     pub fn new(thinker: Arc<Thinker>) -> Self
     {
@@ -79,7 +172,7 @@ impl Engine
                 loop {
                     match receiver.recv().unwrap() {
                         ThreadCommand::Think(params) => {
-                            match thread_thinker.think(&thread_move_chain, &params.search_moves, params.depth, params.node_count, params.move_count_to_checkmate, params.now, params.timeout, params.can_make_best_move, params.can_print_pv, params.can_print_best_move_and_outcome) {
+                            match thread_thinker.think(&thread_move_chain, params) {
                                 Ok(()) => (),
                                 Err(err) => {
                                     thread_thinker.stop();
@@ -96,9 +189,17 @@ impl Engine
             sender,
             thinker,
             move_chain,
+            is_ponder_timeout: AtomicBool::new(false),
+            ponder_move_chain_len: AtomicUsize::new(0),
             time_control: TimeControl::Level(0, Duration::ZERO),
             remaining_time: Duration::from_secs(5 * 60),
             move_count_to_go: 0,
+            move_overhead: Self::DEFAULT_MOVE_OVERHEAD,
+            hash_size: TranspositionTable::DEFAULT_SIZE,
+            searcher_loader: None,
+            network_file: String::new(),
+            network_version: NetworkVersionKind::V3,
+            has_network_change: false,
         }
     }
     
@@ -134,6 +235,100 @@ impl Engine
     pub fn set_move_count_to_go(&mut self, move_count_to_go: usize)
     { self.move_count_to_go = move_count_to_go; }
 
+    /// Returns the move overhead.
+    ///
+    /// The move overhead is a time that is reserved for each move for a communication with a GUI
+    /// and a delay of search abort.
+    pub fn move_overhead(&self) -> Duration
+    { self.move_overhead }
+
+    /// Sets the move overhead.
+    pub fn set_move_overhead(&mut self, move_overhead: Duration)
+    { self.move_overhead = move_overhead.clamp(Self::MIN_MOVE_OVERHEAD, Self::MAX_MOVE_OVERHEAD); }
+
+    /// Returns the size of transposition table or tree of Monte-Carlo tree search in megabytes.
+    pub fn hash_size(&self) -> usize
+    { self.hash_size }
+
+    /// Sets the size of transposition table or tree of Monte-Carlo tree search in megabytes.
+    ///
+    /// The transposition table or the tree of the current searcher is resized.
+    pub fn set_hash_size(&mut self, size: usize)
+    {
+        self.hash_size = size;
+        self.thinker.searcher().resize(size);
+    }
+
+    /// Sets the searcher loader.
+    ///
+    /// The network file and the network version describe the neural network of the current
+    /// searcher. The size is the size of transposition table or tree of the current searcher in
+    /// megabytes.
+    pub fn set_searcher_loader(&mut self, searcher_loader: Box<SearcherLoader>, network_file: String, network_version: NetworkVersionKind, size: usize)
+    {
+        self.searcher_loader = Some(searcher_loader);
+        self.network_file = network_file;
+        self.network_version = network_version;
+        self.hash_size = size;
+        self.has_network_change = false;
+    }
+
+    /// Returns the network file.
+    pub fn network_file(&self) -> &str
+    { self.network_file.as_str() }
+
+    /// Sets the network file.
+    ///
+    /// The neural network is loaded from the new network file by the
+    /// [`load_network`](Self::load_network) method.
+    pub fn set_network_file(&mut self, network_file: String)
+    {
+        self.has_network_change = self.has_network_change || network_file != self.network_file;
+        self.network_file = network_file;
+    }
+
+    /// Returns the network version.
+    pub fn network_version(&self) -> NetworkVersionKind
+    { self.network_version }
+
+    /// Sets the network version.
+    ///
+    /// The neural network of the new network version is loaded by the
+    /// [`load_network`](Self::load_network) method.
+    pub fn set_network_version(&mut self, network_version: NetworkVersionKind)
+    {
+        self.has_network_change = self.has_network_change || network_version != self.network_version;
+        self.network_version = network_version;
+    }
+
+    /// Loads the neural network if the network file or the network version are changed.
+    ///
+    /// This method stops an iterative search, waits for the thinker, and replaces the searcher of
+    /// the thinker with a searcher that is created by the searcher loader. The created searcher has
+    /// the current size of transposition table or tree. The transposition table is cleared after
+    /// the replacement of searcher. The searcher isn't replaced if the searcher
+    /// loader returns an error.
+    pub fn load_network(&mut self) -> LoopResult<()>
+    {
+        if !self.has_network_change {
+            return Ok(());
+        }
+        self.has_network_change = false;
+        match &self.searcher_loader {
+            Some(searcher_loader) => {
+                self.stop();
+                self.thinker.wait();
+                let searcher = searcher_loader(self.network_file.as_str(), self.network_version, self.hash_size)?;
+                self.thinker.set_searcher(searcher);
+                if let Some(transposition_table) = self.thinker.transposition_table() {
+                    transposition_table.clear();
+                }
+                Ok(())
+            },
+            None => Err(LoopError::NoSearcherLoader),
+        }
+    }
+
     /// Returns the printer.
     pub fn printer(&self) -> &Arc<dyn Print + Send + Sync>
     { self.thinker.printer() }
@@ -150,46 +345,50 @@ impl Engine
     }
     
     /// Stops an iterative search.
+    ///
+    /// This method also stops pondering and an infinite search.
     pub fn stop(&self)
-    { self.thinker.intr_checker().stop(); }
+    {
+        self.thinker.stop_pondering();
+        self.thinker.stop_infinite();
+        self.thinker.intr_checker().stop();
+    }
+    
+    /// Switches pondering to a normal iterative search.
+    ///
+    /// The timeouts are calculated and counted from the call of this method if the timeout flag
+    /// was set for pondering.
+    pub fn ponderhit(&self)
+    {
+        let (soft_timeout, timeout) = if self.is_ponder_timeout.load(Ordering::SeqCst) {
+            // The thinker made a best move and a pondered move if it has the pondered move.
+            let move_chain_len = match self.thinker.pondered_move() {
+                Some(_) => self.ponder_move_chain_len.load(Ordering::SeqCst) + 2,
+                None => self.ponder_move_chain_len.load(Ordering::SeqCst),
+            };
+            let (soft_timeout, timeout) = self.calculate_timeouts_for_move_chain_len(move_chain_len);
+            (soft_timeout, Some(timeout))
+        } else {
+            (None, None)
+        };
+        self.thinker.ponderhit(Instant::now(), soft_timeout, timeout);
+    }
     
     /// Returns `true` if an iterative search is stopped, otherwise `false`.
     pub fn is_stopped(&self) -> bool
     { self.thinker.is_stopped() } 
     
-    fn calculate_timeout(&self) -> Duration
+    fn calculate_timeouts(&self) -> (Option<Duration>, Duration)
     {
-        match self.time_control {
-            TimeControl::Level(mps, inc) => {
-                let move_count_to_go = if self.move_count_to_go > 0 {
-                    self.move_count_to_go
-                } else {
-                    if mps > 0 {
-                        let move_chain_g = self.move_chain.lock().unwrap();
-                        mps - (move_chain_g.len() / 2) % mps
-                    } else {
-                        30
-                    }
-                };
-                let mut timeout = self.remaining_time / (move_count_to_go as u32) + inc / 2;
-                if timeout >= self.remaining_time {
-                    if self.remaining_time > Duration::from_millis(500) {
-                        timeout = self.remaining_time - Duration::from_millis(500);
-                    } else {
-                        timeout = Duration::ZERO;
-                    }
-                }
-                timeout
-            },
-            TimeControl::Fixed(time) => {
-                if time > Duration::from_millis(500) {
-                    time - Duration::from_millis(500)
-                } else {
-                    Duration::ZERO
-                }
-            },
-        }
+        let move_chain_len = {
+            let move_chain_g = self.move_chain.lock().unwrap();
+            move_chain_g.len()
+        };
+        self.calculate_timeouts_for_move_chain_len(move_chain_len)
     }
+
+    fn calculate_timeouts_for_move_chain_len(&self, move_chain_len: usize) -> (Option<Duration>, Duration)
+    { calculate_timeouts(self.time_control, self.remaining_time, self.move_count_to_go, self.move_overhead, move_chain_len) }
     
     /// Iteratively searchs a game tree.
     ///
@@ -200,6 +399,32 @@ impl Engine
     /// print a principal variation, and print the best move and an outcome. This method stops an
     /// iterative search and waits for the thinker before the iterative search.
     pub fn go(&self, search_moves: Option<Vec<Move>>, depth: Option<usize>, node_count: Option<u64>, move_count_to_checkmate: Option<usize>, is_timeout: bool, can_make_best_move: bool, can_print_pv: bool, can_print_best_move_and_outcome: bool)
+    {
+        let params = ThinkingParams {
+            search_moves,
+            depth,
+            node_count,
+            move_count_to_checkmate,
+            can_make_best_move,
+            can_print_pv,
+            can_print_best_move_and_outcome,
+            ..ThinkingParams::new()
+        };
+        self.go_with_ponder(params, is_timeout, false);
+    }
+    
+    /// Iteratively searchs a game tree with pondering.
+    ///
+    /// This method is similar to the [`go`](Self::go) method but this method takes the thinking
+    /// parameters with the ponder flag and the flag of pondering after a best move. If the timeout
+    /// flag is set, the timeouts of the thinking parameters are replaced by calculated timeouts.
+    /// The start time of the thinking parameters is always set to the current time. If the ponder
+    /// flag is set, the iterative search is pondering that ignores the timeout until the
+    /// [`ponderhit`](Self::ponderhit) method is called. If the infinite flag is set, the best move
+    /// isn't printed until the [`stop`](Self::stop) method is called. If the flag of pondering
+    /// after the best move is set, the thinker makes the best move and an expected move of
+    /// opponent, and then ponders.
+    pub fn go_with_ponder(&self, mut params: ThinkingParams, is_timeout: bool, is_infinite: bool)
     {
         self.stop();
         self.thinker.wait();
@@ -210,22 +435,24 @@ impl Engine
             outcome.is_some()
         };
         if !is_outcome {
+            if is_timeout {
+                let (soft_timeout, timeout) = self.calculate_timeouts();
+                params.soft_timeout = soft_timeout;
+                params.timeout = Some(timeout);
+            }
+            self.is_ponder_timeout.store(is_timeout, Ordering::SeqCst);
+            {
+                let move_chain_g = self.move_chain.lock().unwrap();
+                self.ponder_move_chain_len.store(move_chain_g.len(), Ordering::SeqCst);
+            }
             self.thinker.start();
-            let timeout = if is_timeout {
-                Some(self.calculate_timeout())
-            } else {
-                None
-            };
-            let params = ThinkingParams {
-                search_moves,
-                depth, node_count, 
-                move_count_to_checkmate,
-                now: Instant::now(),
-                timeout,
-                can_make_best_move,
-                can_print_pv,
-                can_print_best_move_and_outcome,
-            };
+            if params.is_ponder {
+                self.thinker.start_pondering();
+            }
+            if is_infinite {
+                self.thinker.start_infinite();
+            }
+            params.now = Instant::now();
             self.sender.send(ThreadCommand::Think(params)).unwrap();
         }
     }
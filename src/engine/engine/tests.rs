@@ -7,18 +7,41 @@
 //
 use std::io::Cursor;
 use crate::matrix::Matrix;
+use crate::engine::mcts_searcher::*;
 use crate::engine::middle_searcher::*;
 use crate::engine::neural_searcher::*;
 use crate::engine::one_searcher::*;
 use crate::engine::simple_eval_fun::*;
 use crate::engine::thinker::*;
 use crate::shared::converter::*;
+use crate::shared::cpu_matrix::*;
+use crate::shared::cpu_network::*;
+use crate::shared::cpu_network_v3::*;
 use crate::shared::index_converter::*;
 use crate::shared::intr_check::*;
 use crate::shared::network::*;
 use crate::shared::xavier_init::*;
 use super::*;
 
+fn cpu_layer(input_count: usize, output_count: usize) -> CpuLayer
+{
+    let mut w_elems = vec![0.0f32; output_count * input_count];
+    xavier_init(w_elems.as_mut_slice(), input_count, output_count);
+    let b_elems = vec![0.0f32; output_count];
+    CpuLayer::new(CpuMatrix::new_with_elems(output_count, input_count, w_elems.as_slice()), CpuMatrix::new_with_elems(output_count, 1, b_elems.as_slice()))
+}
+
+fn mcts_searcher_with_size(size: usize) -> Arc<dyn Search + Send + Sync>
+{
+    let converter = Converter::new(IndexConverter::new());
+    let input_layer = cpu_layer(Converter::BOARD_ROW_COUNT, 4);
+    let search_layer = cpu_layer(4, 4);
+    let pv_layer = cpu_layer(4, 4);
+    let output_layer = cpu_layer(4, converter.move_row_count());
+    let network = CpuNetworkV3::new(input_layer, search_layer, pv_layer, output_layer);
+    Arc::new(MctsSearcher::new_with_size(Arc::new(EmptyIntrChecker::new()), converter, network, Arc::new(SimpleEvalFun::new()), size))
+}
+
 #[test]
 fn test_engine_go_thinks_without_panic()
 {
@@ -66,3 +89,62 @@ fn test_engine_go_thinks_without_panic()
     engine.quit();
     engine.join_thread();
 }
+
+#[test]
+fn test_calculate_timeouts_calculates_timeouts_for_level()
+{
+    let (soft_timeout, timeout) = calculate_timeouts(TimeControl::Level(40, Duration::ZERO), Duration::from_secs(40), 0, Duration::ZERO, 0);
+    assert_eq!(Some(Duration::from_secs(1)), soft_timeout);
+    assert_eq!(Duration::from_secs(4), timeout);
+    let (soft_timeout, timeout) = calculate_timeouts(TimeControl::Level(40, Duration::ZERO), Duration::from_secs(40), 1, Duration::from_secs(1), 0);
+    assert_eq!(Some(Duration::from_secs(39)), soft_timeout);
+    assert_eq!(Duration::from_secs(39), timeout);
+    let (soft_timeout, timeout) = calculate_timeouts(TimeControl::Level(0, Duration::from_secs(2)), Duration::from_secs(1), 0, Duration::from_millis(200), 0);
+    assert_eq!(Some(Duration::from_millis(600)), soft_timeout);
+    assert_eq!(Duration::from_millis(600), timeout);
+}
+
+#[test]
+fn test_calculate_timeouts_calculates_timeouts_for_fixed_time()
+{
+    let (soft_timeout, timeout) = calculate_timeouts(TimeControl::Fixed(Duration::from_secs(5)), Duration::from_secs(40), 0, Duration::from_millis(100), 0);
+    assert_eq!(None, soft_timeout);
+    assert_eq!(Duration::from_millis(4900), timeout);
+    let (soft_timeout, timeout) = calculate_timeouts(TimeControl::Fixed(Duration::from_millis(50)), Duration::from_secs(40), 0, Duration::from_millis(100), 0);
+    assert_eq!(None, soft_timeout);
+    assert_eq!(Duration::ZERO, timeout);
+}
+
+#[test]
+fn test_network_version_kind_parse_parses_network_versions()
+{
+    assert_eq!(Some(NetworkVersionKind::V1), NetworkVersionKind::parse("v1"));
+    assert_eq!(Some(NetworkVersionKind::V2), NetworkVersionKind::parse("v2"));
+    assert_eq!(Some(NetworkVersionKind::V3), NetworkVersionKind::parse("v3"));
+    assert_eq!(None, NetworkVersionKind::parse("v4"));
+    assert_eq!("v2", NetworkVersionKind::V2.name());
+}
+
+#[test]
+fn test_engine_load_network_loads_searcher_with_current_hash_size()
+{
+    let cursor = Arc::new(Mutex::new(Cursor::new(Vec::<u8>::new())));
+    let printer = Arc::new(EmptyPrinter::new());
+    let thinker = Arc::new(Thinker::new(mcts_searcher_with_size(1), cursor, printer, Arc::new(Mutex::new(None))));
+    let mut engine = Engine::new(thinker);
+    let sizes = Arc::new(Mutex::new(Vec::<usize>::new()));
+    let loader_sizes = sizes.clone();
+    let searcher_loader = move |_: &str, _: NetworkVersionKind, size: usize| {
+        loader_sizes.lock().unwrap().push(size);
+        Ok(mcts_searcher_with_size(size))
+    };
+    engine.set_searcher_loader(Box::new(searcher_loader), String::from("neurina.nnet"), NetworkVersionKind::V3, 1);
+    assert_eq!(1, engine.hash_size());
+    engine.set_hash_size(2);
+    assert_eq!(2, engine.hash_size());
+    engine.set_network_file(String::from("neurina2.nnet"));
+    engine.load_network().unwrap();
+    assert_eq!(vec![2], *sizes.lock().unwrap());
+    engine.quit();
+    engine.join_thread();
+}
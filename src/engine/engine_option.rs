@@ -0,0 +1,451 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::convert::TryFrom;
+use std::error;
+use std::fmt;
+use std::io;
+use std::mem::swap;
+use std::time::Duration;
+use crate::engine::adjudicator::*;
+use crate::engine::book::*;
+use crate::engine::engine::*;
+use crate::engine::fathom;
+use crate::engine::middle_searcher::*;
+use crate::engine::neural_search::*;
+use crate::engine::one_searcher::*;
+use crate::engine::random_eval_fun::*;
+use crate::engine::search::*;
+use crate::engine::strength_limit::*;
+use crate::engine::syzygy::*;
+use crate::engine::thinker::*;
+use crate::engine::transposition_table::*;
+
+/// Names of network versions for the option of network version.
+const NETWORK_VERSION_NAMES: [&str; 3] = ["v1", "v2", "v3"];
+
+/// An enumeration of option type.
+///
+/// The option type contains a default value of option and a range of option values.
+#[derive(Clone, Debug)]
+pub enum OptionType
+{
+    /// A check option with a default value.
+    Check(bool),
+    /// A spin option with a default value, a minimal value, and a maximal value.
+    Spin(i64, i64, i64),
+    /// A string option with a default value.
+    String(String),
+    /// A file option with a default value.
+    File(String),
+    /// A path option with a default value.
+    Path(String),
+    /// A combo option with a default value and values.
+    Combo(&'static str, &'static [&'static str]),
+}
+
+/// An enumeration of option value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OptionValue
+{
+    /// A value of check option.
+    Check(bool),
+    /// A value of spin option.
+    Spin(i64),
+    /// A value of string option, file option, path option, or combo option.
+    String(String),
+}
+
+/// An enumeration of option error.
+#[derive(Debug)]
+pub enum OptionError
+{
+    /// An option is unknown.
+    UnknownOption,
+    /// An option value is invalid.
+    InvalidValue,
+    /// An option isn't supported by the searcher.
+    UnsupportedOption,
+    /// An input/output error.
+    Io(io::Error),
+    /// A fathom error.
+    Fathom(fathom::Error),
+}
+
+impl error::Error for OptionError
+{}
+
+impl fmt::Display for OptionError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            OptionError::UnknownOption => write!(f, "unknown option"),
+            OptionError::InvalidValue => write!(f, "invalid option value"),
+            OptionError::UnsupportedOption => write!(f, "unsupported option"),
+            OptionError::Io(err) => write!(f, "{}", err),
+            OptionError::Fathom(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+/// A type of option result.
+pub type OptionResult<T> = Result<T, OptionError>;
+
+/// A structure of engine option.
+///
+/// The engine option is an entry of option registry that is shared by the protocols. The option
+/// name for the UCI protocol or the option name for the Xboard protocol is absent if the option
+/// isn't available for this protocol.
+#[derive(Clone)]
+pub struct EngineOption
+{
+    /// An option name for the UCI protocol.
+    pub uci_name: Option<&'static str>,
+    /// An option name for the Xboard protocol.
+    pub xboard_name: Option<&'static str>,
+    /// An option type.
+    pub option_type: OptionType,
+    /// A flag of restart of analysis after a change of option.
+    pub can_restart_analysis: bool,
+    /// A function that sets the option for the engine.
+    pub set: fn(&mut Engine, &OptionValue) -> OptionResult<()>,
+}
+
+impl EngineOption
+{
+    /// Parses an option value from the string.
+    ///
+    /// The values of check option are `true` and `false` for the UCI protocol, and are `1` and `0`
+    /// for the Xboard protocol.
+    pub fn parse_value(&self, s: &str, is_xboard: bool) -> OptionResult<OptionValue>
+    {
+        match &self.option_type {
+            OptionType::Check(_) => {
+                match (s, is_xboard) {
+                    ("true", false) | ("1", true) => Ok(OptionValue::Check(true)),
+                    ("false", false) | ("0", true) => Ok(OptionValue::Check(false)),
+                    (_, _) => Err(OptionError::InvalidValue),
+                }
+            },
+            OptionType::Spin(_, _, _) => {
+                match s.parse::<i64>() {
+                    Ok(n) => Ok(OptionValue::Spin(n)),
+                    Err(_) => Err(OptionError::InvalidValue),
+                }
+            },
+            OptionType::Combo(_, values) => {
+                if values.contains(&s) {
+                    Ok(OptionValue::String(String::from(s)))
+                } else {
+                    Err(OptionError::InvalidValue)
+                }
+            },
+            _ => Ok(OptionValue::String(String::from(s))),
+        }
+    }
+}
+
+fn spin_value<T: TryFrom<i64>>(value: &OptionValue) -> OptionResult<T>
+{
+    match value {
+        OptionValue::Spin(n) => T::try_from(*n).map_err(|_| OptionError::InvalidValue),
+        _ => Err(OptionError::InvalidValue),
+    }
+}
+
+fn check_value(value: &OptionValue) -> OptionResult<bool>
+{
+    match value {
+        OptionValue::Check(b) => Ok(*b),
+        _ => Err(OptionError::InvalidValue),
+    }
+}
+
+fn string_value(value: &OptionValue) -> OptionResult<&str>
+{
+    match value {
+        OptionValue::String(s) => Ok(s.as_str()),
+        _ => Err(OptionError::InvalidValue),
+    }
+}
+
+fn set_hash(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    let size = spin_value::<usize>(value)?;
+    if !(TranspositionTable::MIN_SIZE..=TranspositionTable::MAX_SIZE).contains(&size) {
+        return Err(OptionError::InvalidValue);
+    }
+    engine.set_hash_size(size);
+    Ok(())
+}
+
+/// Loads the Syzygy endgame tablebases for the thinker.
+///
+/// The Syzygy endgame tablebases are unloaded if the path is empty.
+pub fn load_syzygy(thinker: &Thinker, path: &str) -> OptionResult<()>
+{
+    let mut syzygy_g = thinker.syzygy().lock().unwrap();
+    if !path.is_empty() {
+        let mut syzygy: Option<Syzygy> = None;
+        swap(&mut *syzygy_g, &mut syzygy);
+        let res = match syzygy {
+            Some(syzygy) => syzygy.reload(path),
+            None => Syzygy::new(path),
+        };
+        match res {
+            Ok(tmp_syzygy) => {
+                *syzygy_g = Some(tmp_syzygy);
+                Ok(())
+            },
+            Err(err) => Err(OptionError::Fathom(err)),
+        }
+    } else {
+        *syzygy_g = None;
+        Ok(())
+    }
+}
+
+fn set_syzygy_path(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{ load_syzygy(engine.thinker(), string_value(value)?) }
+
+fn set_search_config<F>(engine: &mut Engine, f: F) -> OptionResult<()>
+    where F: FnOnce(&dyn SearchConfig)
+{
+    match engine.thinker().searcher().config() {
+        Some(config) => {
+            f(config);
+            Ok(())
+        },
+        None => Err(OptionError::UnsupportedOption),
+    }
+}
+
+fn set_syzygy_probe_limit(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    let probe_limit = spin_value::<usize>(value)?;
+    set_search_config(engine, |config| config.set_syzygy_probe_limit(probe_limit))
+}
+
+fn set_syzygy_probe_depth(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    let probe_depth = spin_value::<usize>(value)?;
+    set_search_config(engine, |config| config.set_syzygy_probe_depth(probe_depth))
+}
+
+fn set_book_file(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    let path = string_value(value)?;
+    let mut book_g = engine.thinker().book().lock().unwrap();
+    if !path.is_empty() {
+        match Book::load(path) {
+            Ok(book) => *book_g = Some(book),
+            Err(err) => return Err(OptionError::Io(err)),
+        }
+    } else {
+        *book_g = None;
+    }
+    Ok(())
+}
+
+fn set_book_max_depth(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    engine.thinker().set_book_max_depth(spin_value::<usize>(value)?);
+    Ok(())
+}
+
+fn set_book_best_move(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    engine.thinker().set_book_best_move(check_value(value)?);
+    Ok(())
+}
+
+fn set_multi_pv(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    engine.thinker().set_multi_pv(spin_value::<usize>(value)?);
+    Ok(())
+}
+
+fn set_ponder(_engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    check_value(value)?;
+    Ok(())
+}
+
+fn set_wdl(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    engine.thinker().set_wdl(check_value(value)?);
+    Ok(())
+}
+
+fn check_neural_searcher(engine: &Engine) -> OptionResult<()>
+{
+    match engine.thinker().searcher().neural_searcher() {
+        Some(_) => Ok(()),
+        None => Err(OptionError::UnsupportedOption),
+    }
+}
+
+fn set_strength_limit(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    let has_strength_limit = check_value(value)?;
+    // The move temperature of strength limit is only applied to the neural searcher.
+    check_neural_searcher(engine)?;
+    engine.thinker().set_strength_limit(has_strength_limit);
+    Ok(())
+}
+
+fn set_elo(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    let elo = spin_value::<u32>(value)?;
+    check_neural_searcher(engine)?;
+    engine.thinker().set_elo(elo);
+    Ok(())
+}
+
+fn set_resign_value(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    engine.thinker().set_resign_value(spin_value::<i32>(value)?);
+    Ok(())
+}
+
+fn set_resign_move_count(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    engine.thinker().set_resign_move_count(spin_value::<usize>(value)?);
+    Ok(())
+}
+
+fn set_draw_value(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    engine.thinker().set_draw_value(spin_value::<i32>(value)?);
+    Ok(())
+}
+
+fn set_draw_move_count(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    engine.thinker().set_draw_move_count(spin_value::<usize>(value)?);
+    Ok(())
+}
+
+fn set_min_draw_offer_move_number(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    engine.thinker().set_min_draw_offer_move_number(spin_value::<usize>(value)?);
+    Ok(())
+}
+
+fn set_move_overhead(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    engine.set_move_overhead(Duration::from_millis(spin_value::<u64>(value)?));
+    Ok(())
+}
+
+fn set_threads(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    let thread_count = spin_value::<usize>(value)?;
+    set_search_config(engine, |config| config.set_thread_count(thread_count))
+}
+
+fn set_middle_depth(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    let middle_depth = spin_value::<usize>(value)?;
+    set_search_config(engine, |config| config.set_middle_depth(middle_depth))
+}
+
+fn set_eval_random_range(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    let range = spin_value::<i32>(value)?;
+    match engine.thinker().searcher().eval_fun() {
+        Some(eval_fun) => {
+            eval_fun.set_random_range(range);
+            Ok(())
+        },
+        None => Err(OptionError::UnsupportedOption),
+    }
+}
+
+fn set_move_eps(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    match string_value(value)?.parse::<f32>() {
+        Ok(move_eps) if (MIN_MOVE_EPS..=MAX_MOVE_EPS).contains(&move_eps) => {
+            match engine.thinker().searcher().neural_searcher() {
+                Some(neural_searcher) => {
+                    neural_searcher.set_move_eps(move_eps);
+                    Ok(())
+                },
+                None => Err(OptionError::UnsupportedOption),
+            }
+        },
+        _ => Err(OptionError::InvalidValue),
+    }
+}
+
+fn set_network_file(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    let path = string_value(value)?;
+    if !path.is_empty() {
+        engine.set_network_file(String::from(path));
+    }
+    Ok(())
+}
+
+fn set_network_version(engine: &mut Engine, value: &OptionValue) -> OptionResult<()>
+{
+    match NetworkVersionKind::parse(string_value(value)?) {
+        Some(network_version) => {
+            engine.set_network_version(network_version);
+            Ok(())
+        },
+        None => Err(OptionError::InvalidValue),
+    }
+}
+
+/// Returns the engine options for the engine.
+///
+/// The engine options form an option registry that is used by the UCI protocol and the Xboard
+/// protocol. The default values of options are current values of the engine, so the advertised
+/// default values reflect the configuration and the command-line arguments.
+pub fn engine_options(engine: &Engine) -> Vec<EngineOption>
+{
+    let thinker = engine.thinker();
+    let searcher = thinker.searcher();
+    let config = searcher.config();
+    let thread_count = config.map(|config| config.thread_count()).unwrap_or(1);
+    let middle_depth = config.map(|config| config.middle_depth()).unwrap_or(OneSearcher::DEFAULT_MIDDLE_DEPTH);
+    let syzygy_probe_limit = config.map(|config| config.syzygy_probe_limit()).unwrap_or(MiddleSearcher::DEFAULT_SYZYGY_PROBE_LIMIT);
+    let syzygy_probe_depth = config.map(|config| config.syzygy_probe_depth()).unwrap_or(MiddleSearcher::DEFAULT_SYZYGY_PROBE_DEPTH);
+    let eval_random_range = searcher.eval_fun().map(|eval_fun| eval_fun.random_range()).unwrap_or(RandomEvalFun::DEFAULT_RANGE);
+    let move_eps = searcher.neural_searcher().map(|neural_searcher| neural_searcher.move_eps()).unwrap_or(DEFAULT_MOVE_EPS);
+    vec![
+        EngineOption { uci_name: Some("Hash"), xboard_name: Some("Hash"), option_type: OptionType::Spin(engine.hash_size() as i64, TranspositionTable::MIN_SIZE as i64, TranspositionTable::MAX_SIZE as i64), can_restart_analysis: false, set: set_hash, },
+        EngineOption { uci_name: Some("SyzygyPath"), xboard_name: Some("SyzygyPath"), option_type: OptionType::Path(String::new()), can_restart_analysis: true, set: set_syzygy_path, },
+        EngineOption { uci_name: Some("SyzygyProbeLimit"), xboard_name: Some("SyzygyProbeLimit"), option_type: OptionType::Spin(syzygy_probe_limit as i64, MiddleSearcher::MIN_SYZYGY_PROBE_LIMIT as i64, MiddleSearcher::MAX_SYZYGY_PROBE_LIMIT as i64), can_restart_analysis: true, set: set_syzygy_probe_limit, },
+        EngineOption { uci_name: Some("SyzygyProbeDepth"), xboard_name: Some("SyzygyProbeDepth"), option_type: OptionType::Spin(syzygy_probe_depth as i64, MiddleSearcher::MIN_SYZYGY_PROBE_DEPTH as i64, MiddleSearcher::MAX_SYZYGY_PROBE_DEPTH as i64), can_restart_analysis: true, set: set_syzygy_probe_depth, },
+        EngineOption { uci_name: Some("BookFile"), xboard_name: Some("BookFile"), option_type: OptionType::File(String::new()), can_restart_analysis: false, set: set_book_file, },
+        EngineOption { uci_name: Some("BookMaxDepth"), xboard_name: Some("BookMaxDepth"), option_type: OptionType::Spin(thinker.book_max_depth() as i64, Book::MIN_MAX_DEPTH as i64, Book::MAX_MAX_DEPTH as i64), can_restart_analysis: false, set: set_book_max_depth, },
+        EngineOption { uci_name: Some("BookBestMove"), xboard_name: Some("BookBestMove"), option_type: OptionType::Check(thinker.has_book_best_move()), can_restart_analysis: false, set: set_book_best_move, },
+        EngineOption { uci_name: Some("MultiPV"), xboard_name: Some("MultiPV"), option_type: OptionType::Spin(thinker.multi_pv() as i64, Thinker::MIN_MULTI_PV as i64, Thinker::MAX_MULTI_PV as i64), can_restart_analysis: true, set: set_multi_pv, },
+        EngineOption { uci_name: Some("Ponder"), xboard_name: None, option_type: OptionType::Check(false), can_restart_analysis: false, set: set_ponder, },
+        EngineOption { uci_name: Some("UCI_ShowWDL"), xboard_name: None, option_type: OptionType::Check(thinker.has_wdl()), can_restart_analysis: false, set: set_wdl, },
+        EngineOption { uci_name: Some("UCI_LimitStrength"), xboard_name: Some("LimitStrength"), option_type: OptionType::Check(thinker.has_strength_limit()), can_restart_analysis: false, set: set_strength_limit, },
+        EngineOption { uci_name: Some("UCI_Elo"), xboard_name: Some("Elo"), option_type: OptionType::Spin(thinker.elo() as i64, StrengthLimit::MIN_ELO as i64, StrengthLimit::MAX_ELO as i64), can_restart_analysis: false, set: set_elo, },
+        EngineOption { uci_name: None, xboard_name: Some("ResignValue"), option_type: OptionType::Spin(thinker.resign_value() as i64, Adjudicator::MIN_RESIGN_VALUE as i64, Adjudicator::MAX_RESIGN_VALUE as i64), can_restart_analysis: false, set: set_resign_value, },
+        EngineOption { uci_name: None, xboard_name: Some("ResignMoveCount"), option_type: OptionType::Spin(thinker.resign_move_count() as i64, 0, Adjudicator::MAX_MOVE_COUNT as i64), can_restart_analysis: false, set: set_resign_move_count, },
+        EngineOption { uci_name: None, xboard_name: Some("DrawValue"), option_type: OptionType::Spin(thinker.draw_value() as i64, Adjudicator::MIN_DRAW_VALUE as i64, Adjudicator::MAX_DRAW_VALUE as i64), can_restart_analysis: false, set: set_draw_value, },
+        EngineOption { uci_name: None, xboard_name: Some("DrawMoveCount"), option_type: OptionType::Spin(thinker.draw_move_count() as i64, 0, Adjudicator::MAX_MOVE_COUNT as i64), can_restart_analysis: false, set: set_draw_move_count, },
+        EngineOption { uci_name: None, xboard_name: Some("MinDrawOfferMoveNumber"), option_type: OptionType::Spin(thinker.min_draw_offer_move_number() as i64, 0, Adjudicator::MAX_MIN_DRAW_OFFER_MOVE_NUMBER as i64), can_restart_analysis: false, set: set_min_draw_offer_move_number, },
+        EngineOption { uci_name: Some("MoveOverhead"), xboard_name: Some("MoveOverhead"), option_type: OptionType::Spin(engine.move_overhead().as_millis() as i64, Engine::MIN_MOVE_OVERHEAD.as_millis() as i64, Engine::MAX_MOVE_OVERHEAD.as_millis() as i64), can_restart_analysis: false, set: set_move_overhead, },
+        EngineOption { uci_name: Some("Threads"), xboard_name: Some("Threads"), option_type: OptionType::Spin(thread_count as i64, OneSearcher::MIN_THREAD_COUNT as i64, OneSearcher::MAX_THREAD_COUNT as i64), can_restart_analysis: false, set: set_threads, },
+        EngineOption { uci_name: Some("MiddleDepth"), xboard_name: Some("MiddleDepth"), option_type: OptionType::Spin(middle_depth as i64, OneSearcher::MIN_MIDDLE_DEPTH as i64, OneSearcher::MAX_MIDDLE_DEPTH as i64), can_restart_analysis: true, set: set_middle_depth, },
+        EngineOption { uci_name: Some("EvalRandomRange"), xboard_name: Some("EvalRandomRange"), option_type: OptionType::Spin(eval_random_range as i64, RandomEvalFun::MIN_RANGE as i64, RandomEvalFun::MAX_RANGE as i64), can_restart_analysis: false, set: set_eval_random_range, },
+        EngineOption { uci_name: Some("MoveEpsilon"), xboard_name: Some("MoveEpsilon"), option_type: OptionType::String(move_eps.to_string()), can_restart_analysis: false, set: set_move_eps, },
+        EngineOption { uci_name: Some("NetworkFile"), xboard_name: Some("NetworkFile"), option_type: OptionType::File(String::from(engine.network_file())), can_restart_analysis: false, set: set_network_file, },
+        EngineOption { uci_name: Some("NetworkVersion"), xboard_name: Some("NetworkVersion"), option_type: OptionType::Combo(engine.network_version().name(), &NETWORK_VERSION_NAMES), can_restart_analysis: false, set: set_network_version, },
+    ]
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,186 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::io::Cursor;
+use std::sync::Arc;
+use std::sync::Mutex;
+use crate::engine::mcts_searcher::*;
+use crate::engine::print::*;
+use crate::engine::simple_eval_fun::*;
+use crate::shared::converter::*;
+use crate::shared::cpu_matrix::*;
+use crate::shared::cpu_network::*;
+use crate::shared::cpu_network_v3::*;
+use crate::shared::index_converter::*;
+use crate::shared::intr_check::*;
+use crate::shared::xavier_init::*;
+use super::*;
+
+fn cpu_layer(input_count: usize, output_count: usize) -> CpuLayer
+{
+    let mut w_elems = vec![0.0f32; output_count * input_count];
+    xavier_init(w_elems.as_mut_slice(), input_count, output_count);
+    let b_elems = vec![0.0f32; output_count];
+    CpuLayer::new(CpuMatrix::new_with_elems(output_count, input_count, w_elems.as_slice()), CpuMatrix::new_with_elems(output_count, 1, b_elems.as_slice()))
+}
+
+fn mcts_engine() -> Engine
+{
+    let converter = Converter::new(IndexConverter::new());
+    let input_layer = cpu_layer(Converter::BOARD_ROW_COUNT, 4);
+    let search_layer = cpu_layer(4, 4);
+    let pv_layer = cpu_layer(4, 4);
+    let output_layer = cpu_layer(4, converter.move_row_count());
+    let network = CpuNetworkV3::new(input_layer, search_layer, pv_layer, output_layer);
+    let searcher = Arc::new(MctsSearcher::new_with_size(Arc::new(EmptyIntrChecker::new()), converter, network, Arc::new(SimpleEvalFun::new()), 1));
+    let cursor = Arc::new(Mutex::new(Cursor::new(Vec::<u8>::new())));
+    let printer = Arc::new(EmptyPrinter::new());
+    let thinker = Arc::new(Thinker::new(searcher, cursor, printer, Arc::new(Mutex::new(None))));
+    Engine::new(thinker)
+}
+
+fn find_option(engine: &Engine, uci_name: &str) -> EngineOption
+{ engine_options(engine).into_iter().find(|option| option.uci_name == Some(uci_name)).unwrap() }
+
+fn find_xboard_option(engine: &Engine, xboard_name: &str) -> EngineOption
+{ engine_options(engine).into_iter().find(|option| option.xboard_name == Some(xboard_name)).unwrap() }
+
+#[test]
+fn test_engine_option_parse_value_parses_check_values()
+{
+    let engine = mcts_engine();
+    let option = find_option(&engine, "BookBestMove");
+    assert_eq!(Some(OptionValue::Check(true)), option.parse_value("true", false).ok());
+    assert_eq!(Some(OptionValue::Check(false)), option.parse_value("false", false).ok());
+    assert_eq!(Some(OptionValue::Check(true)), option.parse_value("1", true).ok());
+    assert_eq!(Some(OptionValue::Check(false)), option.parse_value("0", true).ok());
+    assert!(option.parse_value("1", false).is_err());
+    assert!(option.parse_value("true", true).is_err());
+    engine.quit();
+    engine.join_thread();
+}
+
+#[test]
+fn test_engine_option_parse_value_parses_spin_values()
+{
+    let engine = mcts_engine();
+    let option = find_option(&engine, "MultiPV");
+    assert_eq!(Some(OptionValue::Spin(4)), option.parse_value("4", false).ok());
+    assert_eq!(Some(OptionValue::Spin(-2)), option.parse_value("-2", true).ok());
+    assert!(option.parse_value("x", false).is_err());
+    engine.quit();
+    engine.join_thread();
+}
+
+#[test]
+fn test_engine_option_parse_value_parses_combo_values()
+{
+    let engine = mcts_engine();
+    let option = find_option(&engine, "NetworkVersion");
+    assert_eq!(Some(OptionValue::String(String::from("v2"))), option.parse_value("v2", false).ok());
+    assert!(option.parse_value("v4", false).is_err());
+    engine.quit();
+    engine.join_thread();
+}
+
+#[test]
+fn test_engine_options_have_unique_names()
+{
+    let engine = mcts_engine();
+    let options = engine_options(&engine);
+    for (i, option) in options.iter().enumerate() {
+        for option2 in &options[(i + 1)..] {
+            assert!(option.uci_name.is_none() || option.uci_name != option2.uci_name);
+            assert!(option.xboard_name.is_none() || option.xboard_name != option2.xboard_name);
+        }
+    }
+    engine.quit();
+    engine.join_thread();
+}
+
+#[test]
+fn test_engine_options_have_current_values_of_engine_as_default_values()
+{
+    let mut engine = mcts_engine();
+    engine.set_hash_size(2);
+    engine.set_move_overhead(Duration::from_millis(50));
+    engine.set_network_file(String::from("neurina.nnet"));
+    engine.set_network_version(NetworkVersionKind::V2);
+    engine.thinker().set_multi_pv(3);
+    engine.thinker().set_book_best_move(true);
+    engine.thinker().set_elo(2000);
+    match find_option(&engine, "Hash").option_type {
+        OptionType::Spin(default, _, _) => assert_eq!(2, default),
+        _ => assert!(false),
+    }
+    match find_option(&engine, "MoveOverhead").option_type {
+        OptionType::Spin(default, _, _) => assert_eq!(50, default),
+        _ => assert!(false),
+    }
+    match find_option(&engine, "NetworkFile").option_type {
+        OptionType::File(default) => assert_eq!("neurina.nnet", default),
+        _ => assert!(false),
+    }
+    match find_option(&engine, "NetworkVersion").option_type {
+        OptionType::Combo(default, _) => assert_eq!("v2", default),
+        _ => assert!(false),
+    }
+    match find_option(&engine, "MultiPV").option_type {
+        OptionType::Spin(default, _, _) => assert_eq!(3, default),
+        _ => assert!(false),
+    }
+    match find_option(&engine, "BookBestMove").option_type {
+        OptionType::Check(default) => assert!(default),
+        _ => assert!(false),
+    }
+    match find_option(&engine, "UCI_Elo").option_type {
+        OptionType::Spin(default, _, _) => assert_eq!(2000, default),
+        _ => assert!(false),
+    }
+    engine.quit();
+    engine.join_thread();
+}
+
+#[test]
+fn test_engine_option_set_returns_unsupported_option_error_for_searcher_without_component()
+{
+    let mut engine = mcts_engine();
+    let option = find_option(&engine, "MoveEpsilon");
+    match (option.set)(&mut engine, &OptionValue::String(String::from("0.5"))) {
+        Err(OptionError::UnsupportedOption) => (),
+        _ => assert!(false),
+    }
+    let option2 = find_option(&engine, "Threads");
+    match (option2.set)(&mut engine, &OptionValue::Spin(2)) {
+        Err(OptionError::UnsupportedOption) => (),
+        _ => assert!(false),
+    }
+    let option3 = find_option(&engine, "EvalRandomRange");
+    assert!((option3.set)(&mut engine, &OptionValue::Spin(10)).is_ok());
+    engine.quit();
+    engine.join_thread();
+}
+
+#[test]
+fn test_engine_option_set_sets_minimal_move_number_for_draw_offer()
+{
+    let mut engine = mcts_engine();
+    let option = find_xboard_option(&engine, "MinDrawOfferMoveNumber");
+    match option.option_type {
+        OptionType::Spin(default, _, _) => assert_eq!(Adjudicator::DEFAULT_MIN_DRAW_OFFER_MOVE_NUMBER as i64, default),
+        _ => assert!(false),
+    }
+    assert!((option.set)(&mut engine, &OptionValue::Spin(20)).is_ok());
+    assert_eq!(20, engine.thinker().min_draw_offer_move_number());
+    let option2 = find_xboard_option(&engine, "MinDrawOfferMoveNumber");
+    match option2.option_type {
+        OptionType::Spin(default, _, _) => assert_eq!(20, default),
+        _ => assert!(false),
+    }
+    engine.quit();
+    engine.join_thread();
+}
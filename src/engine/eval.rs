@@ -17,6 +17,11 @@ pub const MAX_EVAL_MATE_VALUE: i32 = MAX_EVAL_VALUE - 384;
 /// A minimal evaluation value of checkmate.
 pub const MIN_EVAL_MATE_VALUE: i32 = MIN_EVAL_VALUE + 384;
 
+/// A maximal evaluation value of tablebase win.
+pub const MAX_EVAL_TB_VALUE: i32 = MAX_EVAL_MATE_VALUE - 128;
+/// A minimal evaluation value of tablebase loss.
+pub const MIN_EVAL_TB_VALUE: i32 = MIN_EVAL_MATE_VALUE + 128;
+
 /// A maximal evaluation value of checkmate of middle search.
 pub const MAX_EVAL_MIDDLE_MATE_VALUE: i32 = MAX_EVAL_VALUE - 256;
 /// A minimal evaluation value of checkmate of middle search.
@@ -34,4 +39,14 @@ pub trait Eval
 {
     /// Evaluates the board.
     fn evaluate(&self, board: &Board) -> i32;
+
+    /// Returns the range of random component of evaluation function.
+    ///
+    /// The evaluation function without a random component returns zero.
+    fn random_range(&self) -> i32;
+
+    /// Sets the range of random component of evaluation function.
+    ///
+    /// The evaluation function without a random component ignores the range.
+    fn set_random_range(&self, range: i32);
 }
@@ -16,13 +16,14 @@ use crate::shared::Interruption;
 /// A structure of interruption checker.
 ///
 /// The interruption checker checks whether a timeout or a searching stopping is occurred. Pressed
-/// keys `Ctrl-C` are ignored.
+/// keys `Ctrl-C` are ignored. The timeout is also ignored during pondering.
 #[derive(Debug)]
 pub struct IntrChecker
 {
     timeout_pair: Mutex<Option<(Instant, Duration)>>,
     is_stopped: AtomicBool,
     has_first: AtomicBool,
+    has_ponder: AtomicBool,
 }
 
 impl IntrChecker
@@ -34,6 +35,7 @@ impl IntrChecker
             timeout_pair: Mutex::new(None),
             is_stopped: AtomicBool::new(true),
             has_first: AtomicBool::new(true),
+            has_ponder: AtomicBool::new(false),
         }
     }
 }
@@ -46,6 +48,9 @@ impl IntrCheck for IntrChecker
             if self.is_stopped.load(Ordering::SeqCst) {
                 return Err(Interruption::Stop);
             }
+            if self.has_ponder.load(Ordering::SeqCst) {
+                return Ok(());
+            }
             let timeout_pair_g = self.timeout_pair.lock().unwrap();
             match *timeout_pair_g {
                 Some((now, duration)) => {
@@ -90,4 +95,10 @@ impl IntrCheck for IntrChecker
         self.has_first.store(is_first, Ordering::SeqCst);
         true
     }
+
+    fn set_ponder(&self, is_ponder: bool) -> bool
+    {
+        self.has_ponder.store(is_ponder, Ordering::SeqCst);
+        true
+    }
 }
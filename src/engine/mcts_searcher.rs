@@ -0,0 +1,587 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::cmp::min;
+use std::mem::size_of;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use crate::chess::types::OutcomeFilter;
+use crate::chess::Board;
+use crate::chess::Move;
+use crate::chess::MoveChain;
+use crate::chess::Outcome;
+use crate::engine::draw::*;
+use crate::engine::eval::*;
+use crate::engine::neural_search::*;
+use crate::engine::search::*;
+use crate::engine::transposition_table::*;
+use crate::shared::converter::*;
+use crate::shared::intr_check::*;
+use crate::shared::matrix_buffer::*;
+use crate::shared::net::*;
+use crate::shared::Interruption;
+
+const VALUE_SCALE: f32 = 400.0;
+
+const MAX_PLAYOUT_SHIFT: usize = 24;
+
+// An estimated number of edges of node for the maximal number of nodes.
+const AVERAGE_EDGE_COUNT: usize = 32;
+
+#[derive(Clone, Debug)]
+struct MctsEdge
+{
+    mv: Move,
+    prior: f32,
+    child: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+struct MctsNode
+{
+    edges: Vec<MctsEdge>,
+    visit_count: u32,
+    // A sum of scores from the view of the side that made a move to the node.
+    value_sum: f32,
+    // A proven value from the view of the side to move.
+    proven_value: Option<i32>,
+    is_expanded: bool,
+    is_pending: bool,
+}
+
+impl MctsNode
+{
+    fn new() -> Self
+    { MctsNode { edges: Vec::new(), visit_count: 0, value_sum: 0.0, proven_value: None, is_expanded: false, is_pending: false, } }
+}
+
+struct MctsTree
+{
+    zobrist_hash: u64,
+    history: Vec<u64>,
+    search_moves: Option<Vec<Move>>,
+    nodes: Vec<MctsNode>,
+}
+
+impl MctsTree
+{
+    fn new(zobrist_hash: u64, history: Vec<u64>, search_moves: Option<Vec<Move>>) -> Self
+    { MctsTree { zobrist_hash, history, search_moves, nodes: vec![MctsNode::new()], } }
+}
+
+fn value_to_score(value: i32) -> f32
+{
+    if value >= MAX_EVAL_MATE_VALUE {
+        1.0
+    } else if value <= MIN_EVAL_MATE_VALUE {
+        -1.0
+    } else {
+        ((value as f32) / VALUE_SCALE).tanh()
+    }
+}
+
+fn score_to_value(score: f32) -> i32
+{
+    let value = (score.clamp(-0.9999, 0.9999).atanh() * VALUE_SCALE).round() as i32;
+    value.clamp(MIN_EVAL_MATE_VALUE + 1, MAX_EVAL_MATE_VALUE - 1)
+}
+
+fn negated_proven_value(value: i32) -> i32
+{
+    let neg_value = -value;
+    if neg_value >= MAX_EVAL_MATE_VALUE {
+        neg_value - 1
+    } else if neg_value <= MIN_EVAL_MATE_VALUE {
+        neg_value + 1
+    } else {
+        neg_value
+    }
+}
+
+/// A structure of Monte-Carlo tree searcher.
+///
+/// The Monte-Carlo tree searcher searches a game tree by the PUCT algorithm. Probabilities of moves
+/// from a neural network are priors of moves and leaves are evaluated by the evaluation function.
+/// Leaves are collected into batches with using virtual losses so that the neural network computes
+/// priors for many leaves at once. Checkmates and draws are proven and backed up by the negamax
+/// algorithm. A depth of search defines a number of playouts that doubles for each next depth. The
+/// game tree is reused by the next search from the same board. The number of nodes of the game
+/// tree is limited by the size of memory and the search doesn't go deeper if the game tree is full.
+pub struct MctsSearcher<T>
+{
+    intr_checker: Arc<dyn IntrCheck + Send + Sync>,
+    converter: Converter,
+    matrix_buf: Mutex<MatrixBuffer<Vec<f32>>>,
+    network: T,
+    eval_fun: Arc<dyn Eval + Send + Sync>,
+    tree: Mutex<Option<MctsTree>>,
+    searched_node_count: AtomicU64,
+    max_node_count: AtomicUsize,
+    is_full: AtomicBool,
+}
+
+impl<T> MctsSearcher<T>
+{
+    /// A maximal number of columns.
+    pub const MAX_COL_COUNT: usize = 1024;
+
+    /// A number of playouts for depth one.
+    pub const PLAYOUT_COUNT: usize = 64;
+
+    /// A maximal number of leaves in batch.
+    pub const BATCH_SIZE: usize = 64;
+
+    /// An exploration constant of the PUCT algorithm.
+    pub const C_PUCT: f32 = 1.5;
+
+    /// Creates a Monte-Carlo tree searcher.
+    pub fn new(intr_checker: Arc<dyn IntrCheck + Send + Sync>, converter: Converter, network: T, eval_fun: Arc<dyn Eval + Send + Sync>) -> Self
+    { Self::new_with_size(intr_checker, converter, network, eval_fun, TranspositionTable::DEFAULT_SIZE) }
+
+    /// Creates a Monte-Carlo tree searcher with the size of memory in megabytes.
+    pub fn new_with_size(intr_checker: Arc<dyn IntrCheck + Send + Sync>, converter: Converter, network: T, eval_fun: Arc<dyn Eval + Send + Sync>, size: usize) -> Self
+    {
+        let matrix_buf = Mutex::new(MatrixBuffer::new(Converter::BOARD_ROW_COUNT, 0, Self::MAX_COL_COUNT, 0, vec![0.0; converter.move_row_count() * Self::MAX_COL_COUNT]));
+        MctsSearcher {
+            intr_checker,
+            converter,
+            matrix_buf,
+            network,
+            eval_fun,
+            tree: Mutex::new(None),
+            searched_node_count: AtomicU64::new(0),
+            max_node_count: AtomicUsize::new(Self::max_node_count_for_size(size)),
+            is_full: AtomicBool::new(false),
+        }
+    }
+
+    fn max_node_count_for_size(size: usize) -> usize
+    { ((size * 1024 * 1024) / (size_of::<MctsNode>() + AVERAGE_EDGE_COUNT * size_of::<MctsEdge>())).max(1) }
+
+    /// Returns the maximal number of nodes of the game tree.
+    pub fn max_node_count(&self) -> usize
+    { self.max_node_count.load(Ordering::SeqCst) }
+
+    /// Sets the maximal number of nodes of the game tree from the size of memory in megabytes.
+    pub fn set_size(&self, size: usize)
+    { self.max_node_count.store(Self::max_node_count_for_size(size), Ordering::SeqCst); }
+
+    /// Returns `true` if the game tree of the last search has the maximal number of nodes,
+    /// otherwise `false`.
+    pub fn is_tree_full(&self) -> bool
+    { self.is_full.load(Ordering::SeqCst) }
+
+    /// Returns the converter.
+    pub fn converter(&self) -> &Converter
+    { &self.converter }
+
+    /// Returns the neural network.
+    pub fn network(&self) -> &T
+    { &self.network }
+
+    fn half_move_count_for_value(value: i32) -> Option<usize>
+    {
+        if value >= MAX_EVAL_MATE_VALUE {
+            Some((MAX_EVAL_VALUE - value) as usize)
+        } else if value <= MIN_EVAL_MATE_VALUE {
+            Some((value - MIN_EVAL_VALUE) as usize)
+        } else {
+            None
+        }
+    }
+
+    fn playout_count_for_depth(depth: usize) -> usize
+    { Self::PLAYOUT_COUNT << (depth.max(1) - 1).min(MAX_PLAYOUT_SHIFT) }
+
+    fn select_edge(nodes: &[MctsNode], node_idx: usize) -> Option<usize>
+    {
+        let node = &nodes[node_idx];
+        let sqrt_visit_count = (node.visit_count.max(1) as f32).sqrt();
+        let first_play_score = if node.visit_count > 0 {
+            -node.value_sum / (node.visit_count as f32)
+        } else {
+            0.0
+        };
+        let mut best_edge_idx: Option<usize> = None;
+        let mut best_puct = -f32::INFINITY;
+        for (edge_idx, edge) in node.edges.iter().enumerate() {
+            let (score, visit_count) = match edge.child {
+                Some(child_idx) => {
+                    let child = &nodes[child_idx];
+                    match child.proven_value {
+                        Some(value) => (value_to_score(negated_proven_value(value)), child.visit_count),
+                        None if child.visit_count > 0 => (child.value_sum / (child.visit_count as f32), child.visit_count),
+                        None => (first_play_score, 0),
+                    }
+                },
+                None => (first_play_score, 0),
+            };
+            let puct = score + Self::C_PUCT * edge.prior * sqrt_visit_count / ((visit_count + 1) as f32);
+            if puct > best_puct {
+                best_puct = puct;
+                best_edge_idx = Some(edge_idx);
+            }
+        }
+        best_edge_idx
+    }
+
+    fn update_proven_value(nodes: &mut [MctsNode], node_idx: usize) -> bool
+    {
+        let node = &nodes[node_idx];
+        if node.proven_value.is_some() {
+            return true;
+        }
+        if !node.is_expanded || node.edges.is_empty() {
+            return false;
+        }
+        let mut best_value: Option<i32> = None;
+        let mut are_all_proven = true;
+        for edge in &node.edges {
+            match edge.child.and_then(|child_idx| nodes[child_idx].proven_value) {
+                Some(value) => {
+                    let value = negated_proven_value(value);
+                    match best_value {
+                        Some(best_value) if value <= best_value => (),
+                        _ => best_value = Some(value),
+                    }
+                },
+                None => are_all_proven = false,
+            }
+        }
+        match best_value {
+            Some(best_value) if best_value >= MAX_EVAL_MATE_VALUE || are_all_proven => {
+                nodes[node_idx].proven_value = Some(best_value);
+                true
+            },
+            _ => false,
+        }
+    }
+
+    fn back_up(nodes: &mut [MctsNode], path: &[usize], score: f32)
+    {
+        for (i, node_idx) in path.iter().rev().enumerate() {
+            let node = &mut nodes[*node_idx];
+            node.visit_count += 1;
+            if i % 2 == 0 {
+                node.value_sum -= score;
+            } else {
+                node.value_sum += score;
+            }
+        }
+        for node_idx in path.iter().rev().skip(1) {
+            if !Self::update_proven_value(nodes, *node_idx) {
+                break;
+            }
+        }
+    }
+
+    fn add_virtual_loss(nodes: &mut [MctsNode], path: &[usize])
+    {
+        for node_idx in path {
+            nodes[*node_idx].visit_count += 1;
+            nodes[*node_idx].value_sum -= 1.0;
+        }
+    }
+
+    fn remove_virtual_loss(nodes: &mut [MctsNode], path: &[usize])
+    {
+        for node_idx in path {
+            nodes[*node_idx].visit_count -= 1;
+            nodes[*node_idx].value_sum += 1.0;
+        }
+    }
+
+    fn edge_rank(nodes: &[MctsNode], edge: &MctsEdge) -> Option<(u8, i64, i64)>
+    {
+        match edge.child {
+            Some(child_idx) if nodes[child_idx].visit_count > 0 => {
+                let child = &nodes[child_idx];
+                match child.proven_value.map(negated_proven_value) {
+                    Some(value) if value >= MAX_EVAL_MATE_VALUE => Some((2, value as i64, child.visit_count as i64)),
+                    Some(value) if value <= MIN_EVAL_MATE_VALUE => Some((0, value as i64, child.visit_count as i64)),
+                    Some(value) => Some((1, child.visit_count as i64, value as i64)),
+                    None => Some((1, child.visit_count as i64, score_to_value(child.value_sum / (child.visit_count as f32)) as i64)),
+                }
+            },
+            _ => None,
+        }
+    }
+
+    fn edge_value(nodes: &[MctsNode], edge: &MctsEdge) -> i32
+    {
+        match edge.child {
+            Some(child_idx) => {
+                let child = &nodes[child_idx];
+                match child.proven_value {
+                    Some(value) => negated_proven_value(value),
+                    None if child.visit_count > 0 => score_to_value(child.value_sum / (child.visit_count as f32)),
+                    None => 0,
+                }
+            },
+            None => 0,
+        }
+    }
+
+    fn best_edge(nodes: &[MctsNode], node_idx: usize) -> Option<&MctsEdge>
+    {
+        let mut best_edge: Option<(&MctsEdge, (u8, i64, i64))> = None;
+        for edge in &nodes[node_idx].edges {
+            if let Some(rank) = Self::edge_rank(nodes, edge) {
+                match best_edge {
+                    Some((_, best_rank)) if rank <= best_rank => (),
+                    _ => best_edge = Some((edge, rank)),
+                }
+            }
+        }
+        best_edge.map(|(edge, _)| edge)
+    }
+
+    fn pv_for_edge(nodes: &[MctsNode], edge: &MctsEdge) -> Vec<Move>
+    {
+        let mut pv = vec![edge.mv];
+        let mut node_idx = edge.child;
+        while let Some(tmp_node_idx) = node_idx {
+            match Self::best_edge(nodes, tmp_node_idx) {
+                Some(best_edge) => {
+                    pv.push(best_edge.mv);
+                    node_idx = best_edge.child;
+                },
+                None => break,
+            }
+        }
+        pv
+    }
+
+    fn root_lines(tree: &MctsTree) -> SearchLines
+    {
+        let mut ranked_edges: Vec<(&MctsEdge, (u8, i64, i64))> = tree.nodes[0].edges.iter().filter_map(|edge| Self::edge_rank(&tree.nodes, edge).map(|rank| (edge, rank))).collect();
+        ranked_edges.sort_by(|(_, rank1), (_, rank2)| rank2.cmp(rank1));
+        ranked_edges.iter().map(|(edge, _)| (Self::edge_value(&tree.nodes, edge), Self::pv_for_edge(&tree.nodes, edge))).collect()
+    }
+
+    fn run_playouts<F>(&self, tree: &mut MctsTree, root_board: &Board, playout_count: usize, node_count: &mut u64, mut f: F) -> Result<(), Interruption>
+        where F: FnMut(&mut [(Board, Vec<(Move, f32)>)]) -> Result<(), Interruption>
+    {
+        let max_node_count = self.max_node_count();
+        while (tree.nodes[0].visit_count as usize) < playout_count && tree.nodes[0].proven_value.is_none() && tree.nodes.len() < max_node_count {
+            self.searched_node_count.store(*node_count, Ordering::SeqCst);
+            self.intr_checker.check()?;
+            let batch_size = min(Self::BATCH_SIZE, playout_count - (tree.nodes[0].visit_count as usize));
+            let mut paths: Vec<Vec<usize>> = Vec::new();
+            let mut leaves: Vec<(Board, Vec<(Move, f32)>)> = Vec::new();
+            let mut is_progress = false;
+            for _ in 0..batch_size {
+                if tree.nodes.len() >= max_node_count {
+                    break;
+                }
+                let mut node_idx = 0usize;
+                let mut board = root_board.clone();
+                let mut history = tree.history.clone();
+                let mut path = vec![0usize];
+                loop {
+                    let node = &tree.nodes[node_idx];
+                    if node.proven_value.is_some() || !node.is_expanded {
+                        break;
+                    }
+                    let edge_idx = match Self::select_edge(&tree.nodes, node_idx) {
+                        Some(edge_idx) => edge_idx,
+                        None => break,
+                    };
+                    let edge = &tree.nodes[node_idx].edges[edge_idx];
+                    match board.make_move(edge.mv) {
+                        Ok(new_board) => board = new_board,
+                        Err(_) => break,
+                    }
+                    let child_idx = match edge.child {
+                        Some(child_idx) => child_idx,
+                        None => {
+                            let child_idx = tree.nodes.len();
+                            tree.nodes.push(MctsNode::new());
+                            tree.nodes[node_idx].edges[edge_idx].child = Some(child_idx);
+                            if is_draw_by_history(&board, history.as_slice()) {
+                                tree.nodes[child_idx].proven_value = Some(0);
+                            } else if !board.has_legal_moves() {
+                                if board.is_check() {
+                                    tree.nodes[child_idx].proven_value = Some(MIN_EVAL_VALUE);
+                                } else {
+                                    tree.nodes[child_idx].proven_value = Some(0);
+                                }
+                            }
+                            child_idx
+                        },
+                    };
+                    history.push(board.zobrist_hash());
+                    path.push(child_idx);
+                    node_idx = child_idx;
+                }
+                let node = &tree.nodes[node_idx];
+                match node.proven_value {
+                    Some(value) => {
+                        Self::back_up(&mut tree.nodes, path.as_slice(), value_to_score(value));
+                        *node_count += 1;
+                        is_progress = true;
+                    },
+                    None if node.is_expanded || node.is_pending => break,
+                    None => {
+                        tree.nodes[node_idx].is_pending = true;
+                        Self::add_virtual_loss(&mut tree.nodes, path.as_slice());
+                        paths.push(path);
+                        leaves.push((board, Vec::new()));
+                    },
+                }
+                if tree.nodes[0].proven_value.is_some() {
+                    break;
+                }
+            }
+            let res = if !leaves.is_empty() {
+                f(leaves.as_mut_slice())
+            } else {
+                Ok(())
+            };
+            for path in &paths {
+                Self::remove_virtual_loss(&mut tree.nodes, path.as_slice());
+                if let Some(leaf_idx) = path.last() {
+                    tree.nodes[*leaf_idx].is_pending = false;
+                }
+            }
+            res?;
+            for (path, (board, probs)) in paths.iter().zip(leaves.iter()) {
+                if let Some(leaf_idx) = path.last() {
+                    let is_root = *leaf_idx == 0;
+                    let leaf = &mut tree.nodes[*leaf_idx];
+                    for (mv, prob) in probs {
+                        match &tree.search_moves {
+                            Some(search_moves) if is_root && !search_moves.contains(mv) => continue,
+                            _ => (),
+                        }
+                        leaf.edges.push(MctsEdge { mv: *mv, prior: *prob, child: None, });
+                    }
+                    leaf.is_expanded = true;
+                    Self::back_up(&mut tree.nodes, path.as_slice(), value_to_score(self.eval_fun.evaluate(board)));
+                    *node_count += 1;
+                    is_progress = true;
+                }
+            }
+            if !is_progress {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn search_lines_with_fun<F>(&self, move_chain: &mut MoveChain, depth: usize, search_moves: &Option<Vec<Move>>, f: F) -> Result<(SearchLines, u64, u64), Interruption>
+        where F: FnMut(&mut [(Board, Vec<(Move, f32)>)]) -> Result<(), Interruption>
+    {
+        self.searched_node_count.store(1, Ordering::SeqCst);
+        self.intr_checker.check()?;
+        let mut node_count = 1u64;
+        if let Some(outcome) = move_chain.set_auto_outcome(OutcomeFilter::Relaxed) {
+            let value = match outcome {
+                Outcome::Win { .. } => MIN_EVAL_VALUE,
+                Outcome::Draw(_) => 0,
+            };
+            return Ok((vec![(value, Vec::new())], 0, node_count));
+        }
+        move_chain.clear_outcome();
+        let zobrist_hash = move_chain.last().zobrist_hash();
+        let history = move_chain_history(move_chain);
+        let mut tree_g = self.tree.lock().unwrap();
+        let mut tree = match tree_g.take() {
+            Some(tree) if tree.zobrist_hash == zobrist_hash && tree.history == history && tree.search_moves == *search_moves && tree.nodes.len() <= self.max_node_count() => tree,
+            _ => MctsTree::new(zobrist_hash, history, search_moves.clone()),
+        };
+        let res = self.run_playouts(&mut tree, move_chain.last(), Self::playout_count_for_depth(depth), &mut node_count, f);
+        self.is_full.store(tree.nodes.len() >= self.max_node_count(), Ordering::SeqCst);
+        let lines = Self::root_lines(&tree);
+        *tree_g = Some(tree);
+        res?;
+        Ok((lines, 0, node_count))
+    }
+}
+
+impl<T: ForwardNet> MctsSearcher<T>
+{
+    fn compute_move_probs(&self, leaves: &mut [(Board, Vec<(Move, f32)>)], intr_checker: &dyn IntrCheck) -> Result<(), Interruption>
+    {
+        let mut matrix_buf_g = self.matrix_buf.lock().unwrap();
+        compute_move_probs(&mut *matrix_buf_g, &self.converter, &self.network, leaves, intr_checker, |output_elems| output_elems.as_mut_slice())
+    }
+
+    fn search_lines(&self, move_chain: &mut MoveChain, depth: usize, search_moves: &Option<Vec<Move>>) -> Result<(SearchLines, u64, u64), Interruption>
+    { self.search_lines_with_fun(move_chain, depth, search_moves, |leaves| self.compute_move_probs(leaves, &*self.intr_checker)) }
+}
+
+impl<T: ForwardNet> Search for MctsSearcher<T>
+{
+    fn intr_checker(&self) -> &Arc<dyn IntrCheck + Send + Sync>
+    { &self.intr_checker }
+
+    fn transposition_table(&self) -> Option<&Arc<TranspositionTable>>
+    { None }
+
+    fn resize(&self, size: usize)
+    { self.set_size(size); }
+
+    fn is_full(&self) -> bool
+    { self.is_tree_full() }
+
+    fn tb_hit_count(&self) -> u64
+    { 0 }
+
+    fn clear_tb_hit_count(&self)
+    {}
+
+    fn curr_move(&self) -> Option<(Move, usize)>
+    { None }
+
+    fn searched_node_count(&self) -> u64
+    { self.searched_node_count.load(Ordering::SeqCst) }
+
+    fn eval_fun(&self) -> Option<&Arc<dyn Eval + Send + Sync>>
+    { Some(&self.eval_fun) }
+
+    fn neural_searcher(&self) -> Option<&Arc<dyn NeuralSearch + Send + Sync>>
+    { None }
+
+    fn move_probs(&self, board: &Board) -> Vec<(Move, f32)>
+    {
+        let mut leaves = vec![(board.clone(), Vec::new())];
+        match self.compute_move_probs(leaves.as_mut_slice(), &EmptyIntrChecker::new()) {
+            Ok(()) => leaves.pop().map(|(_, probs)| probs).unwrap_or(Vec::new()),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn search(&self, move_chain: &mut MoveChain, depth: usize, search_moves: &Option<Vec<Move>>) -> Result<(i32, u64, u64, Vec<Move>), Interruption>
+    {
+        let (lines, middle_node_count, node_count) = self.search_lines(move_chain, depth, search_moves)?;
+        match lines.first() {
+            Some((value, pv)) => Ok((*value, middle_node_count, node_count, pv.clone())),
+            None => Ok((MIN_EVAL_VALUE, middle_node_count, node_count, Vec::new())),
+        }
+    }
+
+    fn search_multi_pv(&self, move_chain: &mut MoveChain, depth: usize, search_moves: &Option<Vec<Move>>, multi_pv: usize) -> Result<(SearchLines, u64, u64), Interruption>
+    {
+        let (mut lines, middle_node_count, node_count) = self.search_lines(move_chain, depth, search_moves)?;
+        lines.truncate(multi_pv.max(1));
+        Ok((lines, middle_node_count, node_count))
+    }
+
+    fn move_count_to_checkmate(&self, value: i32, _depth: usize) -> Option<usize>
+    { Self::half_move_count_for_value(value) }
+
+    fn min_depth(&self) -> usize
+    { 1 }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,146 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::chess::movegen::legal;
+use crate::matrix::Matrix;
+use crate::engine::simple_eval_fun::*;
+use crate::shared::index_converter::*;
+use crate::shared::network::*;
+use crate::shared::xavier_init::*;
+use super::*;
+
+fn uniform_move_probs(leaves: &mut [(Board, Vec<(Move, f32)>)]) -> Result<(), Interruption>
+{
+    for (board, probs) in leaves.iter_mut() {
+        let moves = legal::gen_all(board);
+        *probs = moves.iter().map(|mv| (*mv, 1.0 / (moves.len() as f32))).collect();
+    }
+    Ok(())
+}
+
+#[test]
+fn test_mcts_searcher_search_searches_without_panic()
+{
+    let converter = Converter::new(IndexConverter::new());
+    let mut iw_elems = vec![0.0f32; 256 * Converter::BOARD_ROW_COUNT];
+    xavier_init(iw_elems.as_mut_slice(), Converter::BOARD_ROW_COUNT, 256);
+    let iw = Matrix::new_with_elems(256, Converter::BOARD_ROW_COUNT, iw_elems.as_slice());
+    let mut ib_elems = vec![0.0f32; 256];
+    xavier_init(ib_elems.as_mut_slice(), Converter::BOARD_ROW_COUNT, 256);
+    let ib = Matrix::new_with_elems(256, 1, ib_elems.as_slice());
+    let mut sw_elems = vec![0.0f32; 256 * 256];
+    xavier_init(sw_elems.as_mut_slice(), 256, 256);
+    let sw = Matrix::new_with_elems(256, 256, sw_elems.as_slice());
+    let mut sb_elems = vec![0.0f32; 256];
+    xavier_init(sb_elems.as_mut_slice(), 256, 256);
+    let sb = Matrix::new_with_elems(256, 1, sb_elems.as_slice());
+    let mut pw_elems = vec![0.0f32; 256 * 256];
+    xavier_init(pw_elems.as_mut_slice(), 256, 256);
+    let pw = Matrix::new_with_elems(256, 256, pw_elems.as_slice());
+    let mut pb_elems = vec![0.0f32; 256];
+    xavier_init(pb_elems.as_mut_slice(), 256, 256);
+    let pb = Matrix::new_with_elems(256, 1, pb_elems.as_slice());
+    let mut ow_elems = vec![0.0f32; converter.move_row_count() * 256];
+    xavier_init(ow_elems.as_mut_slice(), 256, converter.move_row_count());
+    let ow = Matrix::new_with_elems(converter.move_row_count(), 256, ow_elems.as_slice());
+    let mut ob_elems = vec![0.0f32; converter.move_row_count()];
+    xavier_init(ob_elems.as_mut_slice(), 256, converter.move_row_count());
+    let ob = Matrix::new_with_elems(converter.move_row_count(), 1, ob_elems.as_slice());
+    let network = Network::new(iw, ib, sw, sb, pw, pb, ow, ob);
+    let intr_checker = Arc::new(EmptyIntrChecker::new());
+    let eval_fun = Arc::new(SimpleEvalFun::new());
+    let mcts_searcher = MctsSearcher::new(intr_checker, converter, network, eval_fun);
+    let mut move_chain = MoveChain::new_initial();
+    move_chain.push_uci_list("e2e4 e7e5").unwrap();
+    let mut tmp_board = move_chain.last().clone();
+    let (_, _, node_count, pv) = mcts_searcher.search(&mut move_chain, 3, &None).unwrap();
+    assert!(node_count <= 257);
+    assert!(1 <= pv.len());
+    for mv in &pv {
+        match tmp_board.make_move(*mv) {
+            Ok(tmp_new_board) => {
+                tmp_board = tmp_new_board;
+                assert!(true);
+            },
+            Err(_) => assert!(false),
+        }
+    }
+}
+
+#[test]
+fn test_mcts_searcher_search_lines_with_fun_finds_checkmate()
+{
+    let intr_checker = Arc::new(EmptyIntrChecker::new());
+    let eval_fun = Arc::new(SimpleEvalFun::new());
+    let mcts_searcher = MctsSearcher::new(intr_checker, Converter::new(IndexConverter::new()), (), eval_fun);
+    let mut move_chain = MoveChain::from_fen("6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 1").unwrap();
+    let (lines, _, _) = mcts_searcher.search_lines_with_fun(&mut move_chain, 4, &None, uniform_move_probs).unwrap();
+    assert_eq!(MAX_EVAL_VALUE - 1, lines[0].0);
+    assert_eq!(vec![Move::from_uci("d1d8", move_chain.last()).unwrap()], lines[0].1);
+    assert_eq!(0, move_chain.len());
+}
+
+#[test]
+fn test_mcts_searcher_search_lines_with_fun_searches_search_moves()
+{
+    let intr_checker = Arc::new(EmptyIntrChecker::new());
+    let eval_fun = Arc::new(SimpleEvalFun::new());
+    let mcts_searcher = MctsSearcher::new(intr_checker, Converter::new(IndexConverter::new()), (), eval_fun);
+    let mut move_chain = MoveChain::new_initial();
+    move_chain.push_uci_list("e2e4 e7e5").unwrap();
+    let search_moves = vec![Move::from_uci("g1f3", move_chain.last()).unwrap(), Move::from_uci("b1c3", move_chain.last()).unwrap()];
+    let (lines, _, node_count) = mcts_searcher.search_lines_with_fun(&mut move_chain, 2, &Some(search_moves.clone()), uniform_move_probs).unwrap();
+    assert_eq!(2, lines.len());
+    assert!(node_count <= 129);
+    for (_, pv) in &lines {
+        assert!(search_moves.contains(&pv[0]));
+    }
+    let (_, _, node_count2) = mcts_searcher.search_lines_with_fun(&mut move_chain, 3, &Some(search_moves.clone()), uniform_move_probs).unwrap();
+    assert!(node_count2 <= 129);
+}
+
+#[test]
+fn test_mcts_searcher_search_lines_with_fun_limits_number_of_nodes()
+{
+    let intr_checker = Arc::new(EmptyIntrChecker::new());
+    let eval_fun = Arc::new(SimpleEvalFun::new());
+    let mcts_searcher = MctsSearcher::new_with_size(intr_checker, Converter::new(IndexConverter::new()), (), eval_fun, 1);
+    let mut move_chain = MoveChain::new_initial();
+    move_chain.push_uci_list("e2e4 e7e5").unwrap();
+    let (lines, _, _) = mcts_searcher.search_lines_with_fun(&mut move_chain, 2, &None, uniform_move_probs).unwrap();
+    assert!(!lines.is_empty());
+    assert!(!mcts_searcher.is_tree_full());
+    let (lines, _, _) = mcts_searcher.search_lines_with_fun(&mut move_chain, 10, &None, uniform_move_probs).unwrap();
+    assert!(!lines.is_empty());
+    assert!(mcts_searcher.is_tree_full());
+    {
+        let tree_g = mcts_searcher.tree.lock().unwrap();
+        match &*tree_g {
+            Some(tree) => assert_eq!(mcts_searcher.max_node_count(), tree.nodes.len()),
+            None => assert!(false),
+        }
+    }
+    mcts_searcher.set_size(2);
+    let (_, _, node_count) = mcts_searcher.search_lines_with_fun(&mut move_chain, 10, &None, uniform_move_probs).unwrap();
+    assert!(node_count > 1);
+    assert!(mcts_searcher.is_tree_full());
+}
+
+#[test]
+fn test_mcts_searcher_search_lines_with_fun_finds_checkmate_for_side_to_move()
+{
+    let intr_checker = Arc::new(EmptyIntrChecker::new());
+    let eval_fun = Arc::new(SimpleEvalFun::new());
+    let mcts_searcher = MctsSearcher::new(intr_checker, Converter::new(IndexConverter::new()), (), eval_fun);
+    let mut move_chain = MoveChain::from_fen("7k/8/6K1/8/8/8/8/R7 b - - 0 1").unwrap();
+    let (lines, _, _) = mcts_searcher.search_lines_with_fun(&mut move_chain, 4, &None, uniform_move_probs).unwrap();
+    assert_eq!(MIN_EVAL_VALUE + 2, lines[0].0);
+    assert_eq!(Move::from_uci("h8g8", move_chain.last()).unwrap(), lines[0].1[0]);
+    assert_eq!(Some(2), MctsSearcher::<()>::half_move_count_for_value(lines[0].0));
+    assert_eq!(Some(1), MctsSearcher::<()>::half_move_count_for_value(MAX_EVAL_VALUE - 1));
+    assert_eq!(None, MctsSearcher::<()>::half_move_count_for_value(0));
+}
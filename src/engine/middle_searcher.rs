@@ -5,26 +5,103 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 //
+use std::collections::HashMap;
 use std::mem::swap;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::Mutex;
 use crate::chess::movegen::semilegal;
 use crate::chess::Board;
+use crate::chess::Color;
 use crate::chess::Move;
 use crate::chess::MoveList;
+use crate::engine::draw::*;
 use crate::engine::eval::*;
+use crate::engine::fathom;
+use crate::engine::move_orderer::*;
 use crate::engine::neural_search::*;
+use crate::engine::quiescence_searcher::*;
+use crate::engine::syzygy::*;
+use crate::engine::transposition_table::*;
 use crate::shared::intr_check::*;
 use crate::shared::Interruption;
 
+const TB_PLY_RANGE: i32 = MAX_EVAL_MATE_VALUE - MAX_EVAL_TB_VALUE;
+
+fn is_tb_value(value: i32) -> bool
+{ ((MAX_EVAL_TB_VALUE - TB_PLY_RANGE + 1)..=MAX_EVAL_TB_VALUE).contains(&value) || (MIN_EVAL_TB_VALUE..(MIN_EVAL_TB_VALUE + TB_PLY_RANGE)).contains(&value) }
+
+// A value of Syzygy endgame tablebases depends on a ply of the probed board, so this value is
+// stored in the transposition table as relative to the node.
+fn value_to_tt(value: i32, ply: usize) -> i32
+{
+    if is_tb_value(value) {
+        if value > 0 { value + (ply as i32) } else { value - (ply as i32) }
+    } else {
+        value
+    }
+}
+
+fn value_from_tt(value: i32, ply: usize) -> i32
+{
+    if is_tb_value(value) {
+        if value > 0 { value - (ply as i32) } else { value + (ply as i32) }
+    } else {
+        value
+    }
+}
+
+#[derive(Copy, Clone)]
+struct SearchContext
+{
+    alpha: i32,
+    beta: i32,
+    ply: usize,
+    middle_depth: usize,
+    neural_depth: usize,
+    can_store: bool,
+    has_pruning: bool,
+}
+
+impl SearchContext
+{
+    fn new(middle_depth: usize, neural_depth: usize, can_store: bool, has_pruning: bool) -> Self
+    { SearchContext { alpha: MIN_EVAL_VALUE, beta: MAX_EVAL_VALUE, ply: 0, middle_depth, neural_depth, can_store, has_pruning, } }
+
+    fn child(&self, alpha: i32) -> Self
+    { SearchContext { alpha: -self.beta, beta: -alpha, ply: self.ply + 1, middle_depth: self.middle_depth - 1, ..*self } }
+}
+
+struct SearchState
+{
+    history: Vec<u64>,
+    current_pv: Vec<Move>,
+    pvs: Vec<Vec<Move>>,
+    move_orderer: MoveOrderer,
+    node_count: u64,
+    history_draw_count: u64,
+}
+
 /// A structure of middle searcher.
 ///
 /// The middle search is a search of game tree that is between a classical tree search and a neural
-/// search.
-#[derive(Clone)]
+/// search. The middle searcher can probe the Syzygy endgame tablebases for results of boards
+/// with few pieces. The pruning of middle search can be disabled so that the middle search is a
+/// plain negamax search that visits all moves.
 pub struct MiddleSearcher
 {
     eval_fun: Arc<dyn Eval + Send + Sync>,
     neural_searcher: Arc<dyn NeuralSearch + Send + Sync>,
+    quiescence_searcher: QuiescenceSearcher,
+    transposition_table: Arc<TranspositionTable>,
+    syzygy: Arc<Mutex<Option<Syzygy>>>,
+    syzygy_probe_limit: AtomicUsize,
+    syzygy_probe_depth: AtomicUsize,
+    tb_hit_count: AtomicU64,
+    has_pruning: AtomicBool,
 }
 
 impl MiddleSearcher
@@ -32,9 +109,56 @@ impl MiddleSearcher
     /// A number of nodes to check interruption.
     pub const NODE_COUNT_TO_INTR_CHECK: u64 = 1024;
 
+    /// A default limit of number of pieces for probing the Syzygy endgame tablebases.
+    pub const DEFAULT_SYZYGY_PROBE_LIMIT: usize = 7;
+
+    /// A minimal limit of number of pieces for probing the Syzygy endgame tablebases.
+    pub const MIN_SYZYGY_PROBE_LIMIT: usize = 0;
+
+    /// A maximal limit of number of pieces for probing the Syzygy endgame tablebases.
+    pub const MAX_SYZYGY_PROBE_LIMIT: usize = 7;
+
+    /// A default depth limit of middle search for probing the Syzygy endgame tablebases.
+    pub const DEFAULT_SYZYGY_PROBE_DEPTH: usize = 1;
+
+    /// A minimal depth limit of middle search for probing the Syzygy endgame tablebases.
+    pub const MIN_SYZYGY_PROBE_DEPTH: usize = 0;
+
+    /// A maximal depth limit of middle search for probing the Syzygy endgame tablebases.
+    pub const MAX_SYZYGY_PROBE_DEPTH: usize = 8;
+
     /// Creates a a middle searcher.
+    ///
+    /// The middle searcher has a transposition table with the default size.
     pub fn new(eval_fun: Arc<dyn Eval + Send + Sync>, neural_searcher: Arc<dyn NeuralSearch + Send + Sync>) -> Self
-    { MiddleSearcher { eval_fun, neural_searcher, } }
+    { Self::new_with_transposition_table(eval_fun, neural_searcher, Arc::new(TranspositionTable::new(TranspositionTable::DEFAULT_SIZE))) }
+
+    /// Creates a a middle searcher with the transposition table.
+    ///
+    /// The middle searcher doesn't probe the Syzygy endgame tablebases.
+    pub fn new_with_transposition_table(eval_fun: Arc<dyn Eval + Send + Sync>, neural_searcher: Arc<dyn NeuralSearch + Send + Sync>, transposition_table: Arc<TranspositionTable>) -> Self
+    { Self::new_with_transposition_table_and_syzygy(eval_fun, neural_searcher, transposition_table, Arc::new(Mutex::new(None))) }
+
+    /// Creates a a middle searcher with the transposition table and the Syzygy endgame
+    /// tablebases.
+    ///
+    /// The Syzygy endgame tablebases are shared with the thinker so that they can be loaded after
+    /// a creation of the middle searcher.
+    pub fn new_with_transposition_table_and_syzygy(eval_fun: Arc<dyn Eval + Send + Sync>, neural_searcher: Arc<dyn NeuralSearch + Send + Sync>, transposition_table: Arc<TranspositionTable>, syzygy: Arc<Mutex<Option<Syzygy>>>) -> Self
+    {
+        let quiescence_searcher = QuiescenceSearcher::new(eval_fun.clone(), neural_searcher.intr_checker().clone());
+        MiddleSearcher {
+            eval_fun,
+            neural_searcher,
+            quiescence_searcher,
+            transposition_table,
+            syzygy,
+            syzygy_probe_limit: AtomicUsize::new(Self::DEFAULT_SYZYGY_PROBE_LIMIT),
+            syzygy_probe_depth: AtomicUsize::new(Self::DEFAULT_SYZYGY_PROBE_DEPTH),
+            tb_hit_count: AtomicU64::new(0),
+            has_pruning: AtomicBool::new(true),
+        }
+    }
 
     /// Returns the interruption checker.
     pub fn intr_checker(&self) -> &Arc<dyn IntrCheck + Send + Sync>
@@ -47,17 +171,102 @@ impl MiddleSearcher
     /// Returns the neural searcher.
     pub fn neural_searcher(&self) -> &Arc<dyn NeuralSearch + Send + Sync>
     { &self.neural_searcher }
+
+    /// Returns the quiescence searcher.
+    pub fn quiescence_searcher(&self) -> &QuiescenceSearcher
+    { &self.quiescence_searcher }
+
+    /// Returns the transposition table.
+    pub fn transposition_table(&self) -> &Arc<TranspositionTable>
+    { &self.transposition_table }
+
+    /// Returns the Syzygy endgame tablebases.
+    pub fn syzygy(&self) -> &Arc<Mutex<Option<Syzygy>>>
+    { &self.syzygy }
+
+    /// Returns the maximal number of pieces of board that is probed in the Syzygy endgame
+    /// tablebases.
+    pub fn syzygy_probe_limit(&self) -> usize
+    { self.syzygy_probe_limit.load(Ordering::SeqCst) }
+
+    /// Sets the maximal number of pieces of board that is probed in the Syzygy endgame
+    /// tablebases.
+    pub fn set_syzygy_probe_limit(&self, probe_limit: usize)
+    { self.syzygy_probe_limit.store(probe_limit.clamp(Self::MIN_SYZYGY_PROBE_LIMIT, Self::MAX_SYZYGY_PROBE_LIMIT), Ordering::SeqCst); }
+
+    /// Returns the minimal depth of middle search for probing the Syzygy endgame tablebases.
+    pub fn syzygy_probe_depth(&self) -> usize
+    { self.syzygy_probe_depth.load(Ordering::SeqCst) }
+
+    /// Sets the minimal depth of middle search for probing the Syzygy endgame tablebases.
+    pub fn set_syzygy_probe_depth(&self, probe_depth: usize)
+    { self.syzygy_probe_depth.store(probe_depth.clamp(Self::MIN_SYZYGY_PROBE_DEPTH, Self::MAX_SYZYGY_PROBE_DEPTH), Ordering::SeqCst); }
+
+    /// Returns the number of hits of the Syzygy endgame tablebases.
+    pub fn tb_hit_count(&self) -> u64
+    { self.tb_hit_count.load(Ordering::SeqCst) }
+
+    /// Resets the number of hits of the Syzygy endgame tablebases.
+    pub fn clear_tb_hit_count(&self)
+    { self.tb_hit_count.store(0, Ordering::SeqCst); }
+
+    /// Returns `true` if the middle search uses the alpha-beta pruning and cutoffs of transposition
+    /// table, otherwise `false`.
+    pub fn has_pruning(&self) -> bool
+    { self.has_pruning.load(Ordering::SeqCst) }
+
+    /// Sets the flag of pruning.
+    ///
+    /// If the flag of pruning is unset, the middle search is a plain negamax search that visits all
+    /// moves. The plain negamax search returns the same value as the middle search with pruning.
+    pub fn set_pruning(&self, has_pruning: bool)
+    { self.has_pruning.store(has_pruning, Ordering::SeqCst); }
+
+    fn probe_syzygy(&self, board: &Board, middle_depth: usize, ply: usize) -> Option<i32>
+    {
+        if middle_depth < self.syzygy_probe_depth() {
+            return None;
+        }
+        let piece_count = (board.color(Color::White).len() + board.color(Color::Black).len()) as usize;
+        if piece_count > self.syzygy_probe_limit() {
+            return None;
+        }
+        let mut syzygy_g = self.syzygy.lock().unwrap();
+        match &mut *syzygy_g {
+            Some(syzygy) if piece_count <= syzygy.max_piece_count() => {
+                let value = match syzygy.probe_wdl(board)? {
+                    fathom::Wdl::Win => MAX_EVAL_TB_VALUE - (ply as i32),
+                    fathom::Wdl::Loss => MIN_EVAL_TB_VALUE + (ply as i32),
+                    _ => 0,
+                };
+                self.tb_hit_count.fetch_add(1, Ordering::SeqCst);
+                Some(value)
+            },
+            _ => None,
+        }
+    }
     
-    fn nega_max_with_fun_ref<F>(&self, board: &Board, current_pv: &mut Vec<Move>, pvs: &mut [Vec<Move>], node_count: &mut u64, leaf_count: &mut usize, ply: usize, middle_depth: usize, f: &mut F) -> Result<(i32, Option<usize>), Interruption>
-        where F: FnMut(&Board, &[Move], usize) -> i32
+    fn nega_max_with_fun_ref<F>(&self, board: &Board, state: &mut SearchState, context: SearchContext, f: &mut F) -> Result<(i32, Option<usize>), Interruption>
+        where F: FnMut(&Board, &[u64], &[Move], &mut u64) -> Result<(i32, Option<usize>), Interruption>
     {
-        if *node_count % Self::NODE_COUNT_TO_INTR_CHECK == 0 {
+        let SearchContext { alpha, beta, ply, middle_depth, neural_depth, can_store, has_pruning, } = context;
+        if state.node_count % Self::NODE_COUNT_TO_INTR_CHECK == 0 {
             self.neural_searcher.intr_checker().check()?;
         }
-        *node_count += 1;
+        state.node_count += 1;
+        if is_draw_by_history(board, state.history.as_slice()) {
+            state.history_draw_count += 1;
+            state.pvs[ply] = Vec::new();
+            return Ok((0, None));
+        }
+        if ply > 0 {
+            if let Some(value) = self.probe_syzygy(board, middle_depth, ply) {
+                state.pvs[ply] = Vec::new();
+                return Ok((value, None));
+            }
+        }
         if middle_depth <= 0 {
-            let leaf_idx = *leaf_count;
-            pvs[ply] = Vec::new();
+            state.pvs[ply] = Vec::new();
             if !board.has_legal_moves() {
                 if board.is_check() {
                     Ok((MIN_EVAL_MIDDLE_MATE_VALUE, None))
@@ -65,90 +274,181 @@ impl MiddleSearcher
                     Ok((0, None))
                 }
             } else {
-                *leaf_count += 1;
-                Ok((f(board, current_pv.as_slice(), leaf_idx), Some(leaf_idx)))
+                f(board, state.history.as_slice(), state.current_pv.as_slice(), &mut state.history_draw_count)
             }
         } else {
+            let zobrist_hash = board.zobrist_hash();
+            let mut tt_best_move: Option<Move> = None;
+            // An entry can't be used if the fifty-move rule can be applied in the searched subtree.
+            let can_use_entry = (board.raw().move_counter as usize) + middle_depth + neural_depth < FIFTY_MOVE_HALF_MOVE_COUNT;
+            if let Some(entry) = self.transposition_table.get(zobrist_hash) {
+                tt_best_move = entry.best_move;
+                if ply > 0 && !entry.is_root && (entry.is_fixed || (has_pruning && can_use_entry && entry.middle_depth == middle_depth)) && entry.depth >= middle_depth + neural_depth {
+                    let entry_value = if entry.is_fixed { entry.value } else { value_from_tt(entry.value, ply) };
+                    let is_cutoff = match entry.bound {
+                        Bound::Exact => true,
+                        Bound::Lower => entry_value >= beta,
+                        Bound::Upper => entry_value <= alpha,
+                    };
+                    if is_cutoff {
+                        state.pvs[ply] = entry.best_move.map(|mv| vec![mv]).unwrap_or(Vec::new());
+                        return Ok((entry_value, None));
+                    }
+                }
+            }
             let mut moves: MoveList = MoveList::new();
             let mut are_moves = false;
             let mut best_value = MIN_EVAL_VALUE;
+            let mut best_move: Option<Move> = None;
             let mut best_leaf_idx = None;
+            let mut tmp_alpha = alpha;
+            let start_history_draw_count = state.history_draw_count;
             semilegal::gen_all_into(board, &mut moves);
+            state.move_orderer.order_moves(board, &mut moves, ply, tt_best_move);
             for mv in &moves {
                 match board.make_move(*mv) {
                     Ok(new_board) => {
-                        current_pv.push(*mv);
-                        let (neg_value, leaf_idx) = self.nega_max_with_fun_ref(&new_board, current_pv, pvs, node_count, leaf_count, ply + 1, middle_depth - 1, f)?;
+                        state.history.push(zobrist_hash);
+                        state.current_pv.push(*mv);
+                        let res = self.nega_max_with_fun_ref(&new_board, state, context.child(tmp_alpha), f);
+                        state.current_pv.pop();
+                        state.history.pop();
+                        let (neg_value, leaf_idx) = res?;
                         let value = -neg_value;
-                        current_pv.pop();
                         if value > best_value {
                             best_value = value;
+                            best_move = Some(*mv);
                             best_leaf_idx = leaf_idx;
                             let mut pv: Vec<Move> = Vec::new();
-                            swap(&mut pv, &mut pvs[ply]);
+                            swap(&mut pv, &mut state.pvs[ply]);
                             pv.clear();
                             pv.push(*mv);
-                            pv.extend_from_slice(pvs[ply + 1].as_slice());
-                            swap(&mut pv, &mut pvs[ply]);
+                            pv.extend_from_slice(state.pvs[ply + 1].as_slice());
+                            swap(&mut pv, &mut state.pvs[ply]);
                         }
                         are_moves = true;
+                        if value > tmp_alpha {
+                            tmp_alpha = value;
+                        }
+                        if has_pruning && tmp_alpha >= beta {
+                            if !is_capture_or_promotion(board, *mv) {
+                                state.move_orderer.add_killer_move(*mv, ply);
+                                state.move_orderer.add_history(*mv, middle_depth);
+                            }
+                            break;
+                        }
                     },
                     Err(_) => (),
                 }
             }
             if !are_moves {
+                state.pvs[ply] = Vec::new();
                 if board.is_check() {
                     Ok((MIN_EVAL_MIDDLE_MATE_VALUE - (middle_depth as i32),  None))
                 } else {
                     Ok((0, None))
                 }
             } else {
+                // A value that depends on draws by the history isn't stored.
+                if can_store && state.history_draw_count == start_history_draw_count {
+                    let bound = if best_value <= alpha {
+                        Bound::Upper
+                    } else if best_value >= beta {
+                        Bound::Lower
+                    } else {
+                        Bound::Exact
+                    };
+                    self.transposition_table.set(zobrist_hash, best_move, bound, value_to_tt(best_value, ply), middle_depth + neural_depth, middle_depth);
+                }
                 Ok((best_value, best_leaf_idx))
             }
         }
     }
 
-    fn nega_max<F>(&self, board: &Board, current_pv: &mut Vec<Move>, pvs: &mut [Vec<Move>], node_count: &mut u64, leaf_count: &mut usize, ply: usize, middle_depth: usize, mut f: F) -> Result<(i32, Option<usize>), Interruption>
-        where F: FnMut(&Board, &[Move], usize) -> i32
-    { self.nega_max_with_fun_ref(board, current_pv, pvs, node_count, leaf_count, ply, middle_depth, &mut f) }
+    fn nega_max<F>(&self, board: &Board, state: &mut SearchState, context: SearchContext, mut f: F) -> Result<(i32, Option<usize>), Interruption>
+        where F: FnMut(&Board, &[u64], &[Move], &mut u64) -> Result<(i32, Option<usize>), Interruption>
+    {
+        state.history_draw_count = 0;
+        self.nega_max_with_fun_ref(board, state, context, &mut f)
+    }
 
     /// Searches a game tree from the board.
     ///
-    /// This method returns a value, a number of nodes of middle search, a number of nodes, and
-    /// a principal variation.
+    /// This method is equivalent to the [`search_with_history`](Self::search_with_history) method
+    /// with an empty history.
     pub fn search(&self, board: &Board, middle_depth: usize, depth: usize) -> Result<(i32, u64, u64, Vec<Move>), Interruption>
+    { self.search_with_history(board, &[], middle_depth, depth) }
+
+    /// Searches a game tree from the board with the history.
+    ///
+    /// The history contains Zobrist hashes of boards that precede the board in a game. A board that
+    /// repeats a board from the history or from the searched variation or that is a draw by the
+    /// fifty-move rule is evaluated as a draw. This also applies to boards of principal variations
+    /// of the neural search that are truncated at such boards. The middle search uses the
+    /// alpha-beta pruning with move ordering and is performed in two passes. The first pass evaluates
+    /// leaves by the evaluation function and collects the visited leaves for the neural search. The
+    /// second pass evaluates the leaves at the ends of principal variations of the neural search by
+    /// the quiescence search. A leaf that isn't visited by the first pass is evaluated by the
+    /// quiescence search without the neural search. Only the second pass stores entries in the
+    /// transposition table. This method returns a value, a number of nodes of middle search, a
+    /// number of nodes, and a principal variation.
+    pub fn search_with_history(&self, board: &Board, history: &[u64], middle_depth: usize, depth: usize) -> Result<(i32, u64, u64, Vec<Move>), Interruption>
     {
-        let mut current_pv: Vec<Move> = Vec::new();
-        let mut pvs: Vec<Vec<Move>> = vec![Vec::with_capacity(middle_depth); middle_depth + 1];
+        let mut state = SearchState {
+            history: history.to_vec(),
+            current_pv: Vec::new(),
+            pvs: vec![Vec::with_capacity(middle_depth); middle_depth + 1],
+            move_orderer: MoveOrderer::new(middle_depth + 1),
+            node_count: 1,
+            history_draw_count: 0,
+        };
         let mut neural_pvs: Vec<Vec<Move>> = Vec::new();
-        let mut middle_node_count = 1u64;
-        let mut leaf_count = 0usize;
-        let (value, _) = self.nega_max(board, &mut current_pv, pvs.as_mut_slice(), &mut middle_node_count, &mut leaf_count, 0, middle_depth, |_, pv, _| {
+        let mut leaf_indices: HashMap<Vec<Move>, usize> = HashMap::new();
+        let has_pruning = self.has_pruning();
+        let (value, _) = self.nega_max(board, &mut state, SearchContext::new(middle_depth, depth - middle_depth, false, has_pruning), |leaf_board, _, pv, _| {
+                let leaf_idx = neural_pvs.len();
                 let mut neural_pv: Vec<Move> = Vec::with_capacity(depth);
                 neural_pv.extend_from_slice(pv);
                 neural_pvs.push(neural_pv);
-                0
+                leaf_indices.insert(pv.to_vec(), leaf_idx);
+                Ok((self.eval_fun.evaluate(leaf_board), Some(leaf_idx)))
         })?;
         if value <= MIN_EVAL_MATE_VALUE || value >= MAX_EVAL_MATE_VALUE || neural_pvs.is_empty() {
-            return Ok((value, middle_node_count, middle_node_count, pvs[0].clone()));
+            return Ok((value, state.node_count, state.node_count, state.pvs[0].clone()));
         }
         self.neural_searcher.search(board, &mut neural_pvs, depth - middle_depth)?;
         let mut neural_node_count = 0u64;
-        pvs = vec![Vec::new(); middle_depth + 1];
-        middle_node_count += 1;
-        leaf_count = 0usize;
-        let (value, leaf_idx) = self.nega_max(board, &mut current_pv, pvs.as_mut_slice(), &mut middle_node_count, &mut leaf_count, 0, middle_depth, |new_board, _, leaf_idx| {
+        state.pvs = vec![Vec::new(); middle_depth + 1];
+        state.node_count += 1;
+        let (value, leaf_idx) = self.nega_max(board, &mut state, SearchContext::new(middle_depth, depth - middle_depth, true, has_pruning), |new_board, leaf_history, pv, history_draw_count| {
+                let leaf_idx = match leaf_indices.get(pv) {
+                    Some(leaf_idx) => *leaf_idx,
+                    None => return Ok((self.quiescence_searcher.search(new_board, &mut neural_node_count)?, None)),
+                };
+                let mut neural_history = leaf_history.to_vec();
                 let mut tmp_board = new_board.clone();
                 let mut neural_ply = 0usize;
+                let mut is_draw = false;
                 for mv in &neural_pvs[leaf_idx][middle_depth..] {
                     match tmp_board.make_move(*mv) {
-                        Ok(tmp_new_board) => tmp_board = tmp_new_board,
+                        Ok(tmp_new_board) => {
+                            neural_history.push(tmp_board.zobrist_hash());
+                            tmp_board = tmp_new_board;
+                        },
                         Err(_) => break,
                     }
                     neural_node_count += 1;
                     neural_ply += 1;
+                    if is_draw_by_history(&tmp_board, neural_history.as_slice()) {
+                        *history_draw_count += 1;
+                        is_draw = true;
+                        break;
+                    }
                 }
-                let value = if !tmp_board.has_legal_moves() {
+                let value = if is_draw {
+                    neural_pvs[leaf_idx].truncate(middle_depth + neural_ply);
+                    0
+                } else if !tmp_board.has_legal_moves() {
                     if tmp_board.is_check() {
                         if neural_ply > 0 {
                             MIN_EVAL_MATE_VALUE - ((depth - middle_depth - neural_ply) as i32)
@@ -159,18 +459,18 @@ impl MiddleSearcher
                         0
                     }
                 } else {
-                    self.eval_fun.evaluate(&tmp_board)
+                    self.quiescence_searcher.search(&tmp_board, &mut neural_node_count)?
                 };
                 if neural_ply % 2 == 0 {
-                    value
+                    Ok((value, Some(leaf_idx)))
                 } else {
-                    -value
+                    Ok((-value, Some(leaf_idx)))
                 }
         })?;
-        let node_count = middle_node_count + neural_node_count;
+        let node_count = state.node_count + neural_node_count;
         match leaf_idx {
-            Some(leaf_idx) => Ok((value, middle_node_count, node_count, neural_pvs[leaf_idx].clone())),
-            None => Ok((value, middle_node_count, node_count, pvs[0].clone())),
+            Some(leaf_idx) => Ok((value, state.node_count, node_count, neural_pvs[leaf_idx].clone())),
+            None => Ok((value, state.node_count, node_count, state.pvs[0].clone())),
         }
     }
 }
@@ -5,10 +5,13 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 //
+use crate::chess::Move;
+use crate::chess::MoveChain;
 use crate::matrix::Matrix;
 use crate::engine::neural_searcher::*;
 use crate::engine::one_neural_searcher::*;
 use crate::engine::simple_eval_fun::*;
+use crate::engine::test_neural_searcher::*;
 use crate::shared::converter::*;
 use crate::shared::index_converter::*;
 use crate::shared::network::*;
@@ -16,6 +19,20 @@ use crate::shared::network_v2::*;
 use crate::shared::xavier_init::*;
 use super::*;
 
+struct HashEvalFun;
+
+impl Eval for HashEvalFun
+{
+    fn evaluate(&self, board: &Board) -> i32
+    { ((board.zobrist_hash() % 20001) as i32) - 10000 }
+
+    fn random_range(&self) -> i32
+    { 0 }
+
+    fn set_random_range(&self, _range: i32)
+    {}
+}
+
 #[test]
 fn test_middle_searcher_search_searches_without_panic_with_neural_searcher()
 {
@@ -49,6 +66,7 @@ fn test_middle_searcher_search_searches_without_panic_with_neural_searcher()
     let eval_fun = Arc::new(SimpleEvalFun::new());
     let neural_searcher = Arc::new(NeuralSearcher::new(intr_checker, converter, network));
     let middle_searcher = MiddleSearcher::new(eval_fun, neural_searcher);
+    middle_searcher.set_pruning(false);
     let board = Board::from_fen("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2").unwrap();
     let (_, middle_node_count, node_count, pv) = middle_searcher.search(&board, 2, 5).unwrap();
     assert_eq!(1732, middle_node_count);
@@ -87,6 +105,7 @@ fn test_middle_searcher_search_searches_without_panic_with_one_neural_searcher()
     let eval_fun = Arc::new(SimpleEvalFun::new());
     let neural_searcher = Arc::new(OneNeuralSearcher::new(intr_checker, converter, network));
     let middle_searcher = MiddleSearcher::new(eval_fun, neural_searcher);
+    middle_searcher.set_pruning(false);
     let board = Board::from_fen("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2").unwrap();
     let (_, middle_node_count, node_count, pv) = middle_searcher.search(&board, 2, 5).unwrap();
     assert_eq!(1732, middle_node_count);
@@ -103,3 +122,78 @@ fn test_middle_searcher_search_searches_without_panic_with_one_neural_searcher()
         }
     }
 }
+
+#[test]
+fn test_middle_searcher_search_visits_all_nodes_without_pruning()
+{
+    let middle_searcher = MiddleSearcher::new(Arc::new(SimpleEvalFun::new()), Arc::new(TestNeuralSearcher::new()));
+    middle_searcher.set_pruning(false);
+    let board = Board::from_fen("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2").unwrap();
+    let (_, middle_node_count, _, _) = middle_searcher.search(&board, 2, 2).unwrap();
+    assert_eq!(1732, middle_node_count);
+}
+
+#[test]
+fn test_middle_searcher_search_returns_same_value_and_pv_as_plain_negamax()
+{
+    let fens = [
+        "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2",
+        "r1bqkbnr/pppp1ppp/2n5/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 3 3",
+        "r3k2r/pppq1ppp/2npbn2/2b1p3/2B1P3/2NPBN2/PPPQ1PPP/R3K2R w KQkq - 4 8",
+        "6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 1"
+    ];
+    for fen in &fens {
+        let board = Board::from_fen(fen).unwrap();
+        let middle_searcher = MiddleSearcher::new(Arc::new(HashEvalFun), Arc::new(TestNeuralSearcher::new()));
+        let plain_middle_searcher = MiddleSearcher::new(Arc::new(HashEvalFun), Arc::new(TestNeuralSearcher::new()));
+        plain_middle_searcher.set_pruning(false);
+        let (value, middle_node_count, _, pv) = middle_searcher.search(&board, 3, 3).unwrap();
+        let (plain_value, plain_middle_node_count, _, plain_pv) = plain_middle_searcher.search(&board, 3, 3).unwrap();
+        assert_eq!(plain_value, value);
+        assert_eq!(plain_pv, pv);
+        assert!(middle_node_count < plain_middle_node_count);
+    }
+}
+
+#[test]
+fn test_middle_searcher_search_with_history_does_not_store_values_of_draws_by_history()
+{
+    let mut move_chain = MoveChain::new_initial();
+    move_chain.push_uci_list("g1f3 g8f6 f3g1").unwrap();
+    let history = move_chain_history(&move_chain);
+    let board = move_chain.last().clone();
+    let middle_searcher = MiddleSearcher::new(Arc::new(SimpleEvalFun::new()), Arc::new(TestNeuralSearcher::new()));
+    middle_searcher.search_with_history(&board, &history[0..(history.len() - 1)], 2, 2).unwrap();
+    assert!(middle_searcher.transposition_table().get(board.zobrist_hash()).is_none());
+    let middle_searcher2 = MiddleSearcher::new(Arc::new(SimpleEvalFun::new()), Arc::new(TestNeuralSearcher::new()));
+    middle_searcher2.search(&board, 2, 2).unwrap();
+    assert!(middle_searcher2.transposition_table().get(board.zobrist_hash()).is_some());
+}
+
+#[test]
+fn test_middle_searcher_search_converts_tablebase_values_for_same_board_at_different_plies()
+{
+    let board = Board::from_fen("4k3/8/8/7P/8/1q6/8/K7 w - - 0 1").unwrap();
+    let board2 = board.make_move(Move::from_uci("h5h6", &board).unwrap()).unwrap();
+    let board3 = board2.make_move(Move::from_uci("e8d8", &board2).unwrap()).unwrap();
+    // A loss that is probed at ply 6 for the board at ply 3.
+    let value3 = value_to_tt(MIN_EVAL_TB_VALUE + 6, 3);
+    assert_eq!(MIN_EVAL_TB_VALUE + 3, value3);
+    assert_eq!(MIN_EVAL_TB_VALUE + 4, value_from_tt(value3, 1));
+    assert_eq!(MIN_EVAL_TB_VALUE + 5, value_from_tt(value3, 2));
+    assert_eq!(MAX_EVAL_TB_VALUE - 3, value_to_tt(MAX_EVAL_TB_VALUE - 6, 3));
+    assert_eq!(MAX_EVAL_TB_VALUE - 6, value_from_tt(MAX_EVAL_TB_VALUE - 3, 3));
+    assert_eq!(100, value_to_tt(100, 3));
+    assert_eq!(100, value_from_tt(100, 3));
+    // The third board is probed at ply 1.
+    let middle_searcher = MiddleSearcher::new(Arc::new(SimpleEvalFun::new()), Arc::new(TestNeuralSearcher::new()));
+    middle_searcher.transposition_table().set(board3.zobrist_hash(), None, Bound::Exact, value3, 1, 1);
+    let (value, _, _, pv) = middle_searcher.search(&board2, 2, 2).unwrap();
+    assert_eq!(MAX_EVAL_TB_VALUE - 4, value);
+    assert_eq!(vec![Move::from_uci("e8d8", &board2).unwrap()], pv);
+    // The third board is probed at ply 2.
+    let middle_searcher2 = MiddleSearcher::new(Arc::new(SimpleEvalFun::new()), Arc::new(TestNeuralSearcher::new()));
+    middle_searcher2.transposition_table().set(board3.zobrist_hash(), None, Bound::Exact, value3, 1, 1);
+    let (value2, _, _, _) = middle_searcher2.search(&board, 3, 3).unwrap();
+    assert_eq!(MIN_EVAL_TB_VALUE + 5, value2);
+}
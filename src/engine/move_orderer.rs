@@ -0,0 +1,159 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::chess::Board;
+use crate::chess::Move;
+use crate::chess::MoveKind;
+use crate::chess::MoveList;
+use crate::chess::Piece;
+
+const MVV_LVA_PIECE_VALUES: [i32; 6] = [
+    1,      // Pawn
+    6,      // King
+    2,      // Knight
+    3,      // Bishop
+    4,      // Rook
+    5       // Queen
+];
+
+const BEST_MOVE_SCORE: i32 = 1 << 30;
+
+const CAPTURE_MOVE_SCORE: i32 = 1 << 29;
+
+const KILLER_MOVE_SCORE: i32 = 1 << 28;
+
+const MAX_HISTORY_VALUE: i32 = (1 << 27) - 1;
+
+/// Returns `true` if the move is a capture or a promotion, otherwise `false`.
+pub fn is_capture_or_promotion(board: &Board, mv: Move) -> bool
+{
+    match mv.kind() {
+        MoveKind::Enpassant | MoveKind::PromoteKnight | MoveKind::PromoteBishop | MoveKind::PromoteRook | MoveKind::PromoteQueen => true,
+        MoveKind::Simple => board.get(mv.dst()).is_occupied(),
+        _ => false,
+    }
+}
+
+/// A structure of move orderer.
+///
+/// The move orderer sorts moves before a search of their subtrees so that an alpha-beta pruning
+/// cuts off as many subtrees as possible. The moves are ordered as follows:
+///
+/// - a best move (for example from a transposition table)
+/// - captures and promotions sorted by the MVV-LVA heuristic
+/// - killer moves
+/// - remaining moves sorted by the history heuristic
+#[derive(Clone, Debug)]
+pub struct MoveOrderer
+{
+    killer_moves: Vec<[Option<Move>; 2]>,
+    history: Vec<i32>,
+}
+
+impl MoveOrderer
+{
+    /// Creates a move orderer for the maximal number of plies.
+    pub fn new(max_ply_count: usize) -> Self
+    { MoveOrderer { killer_moves: vec![[None, None]; max_ply_count], history: vec![0; 64 * 64], } }
+
+    /// Returns the killer moves for the ply.
+    pub fn killer_moves(&self, ply: usize) -> [Option<Move>; 2]
+    {
+        match self.killer_moves.get(ply) {
+            Some(killer_moves) => *killer_moves,
+            None => [None, None],
+        }
+    }
+
+    /// Returns a value of the history heuristic for the move.
+    pub fn history_value(&self, mv: Move) -> i32
+    { self.history[mv.src().index() * 64 + mv.dst().index()] }
+
+    /// Adds the move that caused a beta cutoff to the killer moves for the ply.
+    pub fn add_killer_move(&mut self, mv: Move, ply: usize)
+    {
+        match self.killer_moves.get_mut(ply) {
+            Some(killer_moves) if killer_moves[0] != Some(mv) => {
+                killer_moves[1] = killer_moves[0];
+                killer_moves[0] = Some(mv);
+            },
+            _ => (),
+        }
+    }
+
+    /// Increases the value of the history heuristic for the move that caused a beta cutoff at
+    /// the depth.
+    pub fn add_history(&mut self, mv: Move, depth: usize)
+    {
+        let idx = mv.src().index() * 64 + mv.dst().index();
+        let bonus = (depth * depth) as i32;
+        self.history[idx] += bonus;
+        if self.history[idx] > MAX_HISTORY_VALUE {
+            for value in &mut self.history {
+                *value /= 2;
+            }
+        }
+    }
+
+    /// Clears the killer moves and the history heuristic.
+    pub fn clear(&mut self)
+    {
+        for killer_moves in &mut self.killer_moves {
+            *killer_moves = [None, None];
+        }
+        for value in &mut self.history {
+            *value = 0;
+        }
+    }
+
+    fn move_score(&self, board: &Board, mv: Move, ply: usize, best_move: Option<Move>) -> i32
+    {
+        if best_move == Some(mv) {
+            return BEST_MOVE_SCORE;
+        }
+        if is_capture_or_promotion(board, mv) {
+            let victim_value = match mv.kind() {
+                MoveKind::Enpassant => MVV_LVA_PIECE_VALUES[Piece::Pawn.index()],
+                _ => {
+                    match board.get(mv.dst()).piece() {
+                        Some(piece) => MVV_LVA_PIECE_VALUES[piece.index()],
+                        None => 0,
+                    }
+                },
+            };
+            let promotion_value = match mv.kind() {
+                MoveKind::PromoteQueen => MVV_LVA_PIECE_VALUES[Piece::Queen.index()],
+                MoveKind::PromoteRook => MVV_LVA_PIECE_VALUES[Piece::Rook.index()],
+                MoveKind::PromoteBishop => MVV_LVA_PIECE_VALUES[Piece::Bishop.index()],
+                MoveKind::PromoteKnight => MVV_LVA_PIECE_VALUES[Piece::Knight.index()],
+                _ => 0,
+            };
+            let attacker_value = match mv.src_cell().piece() {
+                Some(piece) => MVV_LVA_PIECE_VALUES[piece.index()],
+                None => 0,
+            };
+            return CAPTURE_MOVE_SCORE + (victim_value + promotion_value) * 16 - attacker_value;
+        }
+        let killer_moves = self.killer_moves(ply);
+        if killer_moves[0] == Some(mv) {
+            KILLER_MOVE_SCORE + 1
+        } else if killer_moves[1] == Some(mv) {
+            KILLER_MOVE_SCORE
+        } else {
+            self.history_value(mv)
+        }
+    }
+
+    /// Sorts the moves for the board at the ply.
+    ///
+    /// The best move is a move that is searched as first if it is in the moves.
+    pub fn order_moves(&self, board: &Board, moves: &mut MoveList, ply: usize, best_move: Option<Move>)
+    { moves.sort_by_cached_key(|mv| -self.move_score(board, *mv, ply, best_move)); }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,59 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::chess::movegen::semilegal;
+use super::*;
+
+#[test]
+fn test_move_orderer_order_moves_orders_captures_by_mvv_lva()
+{
+    let board = Board::from_fen("4k3/8/8/3q4/1pP2r2/3N4/8/4K3 w - - 0 1").unwrap();
+    let mut moves = semilegal::gen_all(&board);
+    let move_orderer = MoveOrderer::new(4);
+    move_orderer.order_moves(&board, &mut moves, 0, None);
+    assert_eq!(Move::from_uci("c4d5", &board).unwrap(), moves[0]);
+    assert_eq!(Move::from_uci("d3f4", &board).unwrap(), moves[1]);
+    assert_eq!(Move::from_uci("d3b4", &board).unwrap(), moves[2]);
+    assert!(is_capture_or_promotion(&board, moves[2]));
+    assert!(!is_capture_or_promotion(&board, moves[3]));
+}
+
+#[test]
+fn test_move_orderer_order_moves_orders_best_move_and_killer_moves()
+{
+    let board = Board::from_fen("4k3/8/8/3q4/2P5/3N4/8/4K3 w - - 0 1").unwrap();
+    let mut moves = semilegal::gen_all(&board);
+    let mut move_orderer = MoveOrderer::new(4);
+    let best_move = Move::from_uci("e1f2", &board).unwrap();
+    let killer_move1 = Move::from_uci("d3b4", &board).unwrap();
+    let killer_move2 = Move::from_uci("d3f4", &board).unwrap();
+    move_orderer.add_killer_move(killer_move1, 1);
+    move_orderer.add_killer_move(killer_move2, 1);
+    move_orderer.order_moves(&board, &mut moves, 1, Some(best_move));
+    assert_eq!(best_move, moves[0]);
+    assert_eq!(Move::from_uci("c4d5", &board).unwrap(), moves[1]);
+    assert_eq!(killer_move2, moves[2]);
+    assert_eq!(killer_move1, moves[3]);
+}
+
+#[test]
+fn test_move_orderer_order_moves_orders_moves_by_history()
+{
+    let board = Board::from_fen("4k3/8/8/8/8/3N4/8/4K3 w - - 0 1").unwrap();
+    let mut moves = semilegal::gen_all(&board);
+    let mut move_orderer = MoveOrderer::new(4);
+    let mv1 = Move::from_uci("d3c5", &board).unwrap();
+    let mv2 = Move::from_uci("e1d2", &board).unwrap();
+    move_orderer.add_history(mv1, 2);
+    move_orderer.add_history(mv2, 3);
+    move_orderer.order_moves(&board, &mut moves, 0, None);
+    assert_eq!(mv2, moves[0]);
+    assert_eq!(mv1, moves[1]);
+    move_orderer.clear();
+    assert_eq!(0, move_orderer.history_value(mv1));
+    assert_eq!([None, None], move_orderer.killer_moves(0));
+}
@@ -6,11 +6,33 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 //
 use std::sync::Arc;
+use crate::chess::movegen::legal;
 use crate::chess::Board;
 use crate::chess::Move;
+use crate::shared::converter::*;
 use crate::shared::intr_check::*;
+use crate::shared::matrix_buffer::*;
+use crate::shared::net::*;
 use crate::shared::Interruption;
 
+/// A default move epsilon.
+pub const DEFAULT_MOVE_EPS: f32 = 0.01;
+
+/// A minimal move epsilon.
+pub const MIN_MOVE_EPS: f32 = 0.0;
+
+/// A maximal move epsilon.
+pub const MAX_MOVE_EPS: f32 = 1.0;
+
+/// A default move temperature.
+pub const DEFAULT_MOVE_TEMPERATURE: f32 = 0.0;
+
+/// A minimal move temperature.
+pub const MIN_MOVE_TEMPERATURE: f32 = 0.0;
+
+/// A maximal move temperature.
+pub const MAX_MOVE_TEMPERATURE: f32 = 10.0;
+
 /// A trait of neural searcher.
 ///
 /// The neural search is a search of game tree that uses a neural network. The neural network
@@ -24,4 +46,80 @@ pub trait NeuralSearch
     ///
     /// The principal variations are from a middle search and updated by the neural search.
     fn search(&self, board: &Board, pvs: &mut [Vec<Move>], depth: usize) -> Result<(), Interruption>;
+
+    /// Returns the legal moves with their probabilities for the board.
+    ///
+    /// The probabilities are computed by the neural network without interruptions.
+    fn move_probs(&self, board: &Board) -> Vec<(Move, f32)>;
+
+    /// Returns the move epsilon.
+    ///
+    /// The move epsilon defines margin of error for move scores from the neural network. If the
+    /// move scores are too close, the neural searcher doesn't select a move.
+    fn move_eps(&self) -> f32;
+
+    /// Sets the move epsilon.
+    fn set_move_eps(&self, move_eps: f32);
+
+    /// Returns the move temperature.
+    ///
+    /// If the move temperature is positive, the neural searcher samples a move from the softmax
+    /// function of move scores instead of selecting the best move. A higher move temperature makes
+    /// the neural searcher weaker. The move temperature equal to zero disables sampling.
+    fn move_temperature(&self) -> f32;
+
+    /// Sets the move temperature.
+    fn set_move_temperature(&self, move_temperature: f32);
+}
+
+/// Returns the board after the moves of the principal variation.
+///
+/// The moves are made on a copy of the board until a move that can't be made.
+pub(crate) fn board_after_pv(board: &Board, pv: &[Move]) -> Board
+{
+    let mut tmp_board = board.clone();
+    for mv in pv {
+        match tmp_board.make_move(*mv) {
+            Ok(tmp_new_board) => tmp_board = tmp_new_board,
+            Err(_) => break,
+        }
+    }
+    tmp_board
+}
+
+/// Computes the legal moves with their probabilities for the leaf boards by the neural network.
+///
+/// The closure returns the output elements from the middle buffer of the matrix buffer.
+pub(crate) fn compute_move_probs<T, N, F>(matrix_buf: &mut MatrixBuffer<T>, converter: &Converter, network: &N, leaves: &mut [(Board, Vec<(Move, f32)>)], intr_checker: &dyn IntrCheck, mut f: F) -> Result<(), Interruption>
+    where N: ForwardNet,
+        F: FnMut(&mut T) -> &mut [f32]
+{
+    matrix_buf.do_elem_bufs(leaves, 0, intr_checker, |(leaf_board, _), elems, _, j, col_count| {
+            converter.board_to_matrix_col(leaf_board, elems, j, col_count);
+    }, |input_elems, _, middle_buf, leaves| {
+            let col_count = leaves.len();
+            network.compute_elems(input_elems, col_count, 1, 1, f(middle_buf), |output_elems| {
+                    intr_checker.check()?;
+                    for (j, (leaf_board, probs)) in leaves.iter_mut().enumerate() {
+                        let moves = legal::gen_all(leaf_board);
+                        *probs = converter.matrix_col_to_move_probs(&moves, leaf_board.side(), output_elems, j, col_count);
+                    }
+                    Ok(())
+            })
+    })
+}
+
+/// Returns the legal moves with their probabilities for the board.
+///
+/// The probabilities are computed by the neural network without interruptions. See
+/// [`compute_move_probs`].
+pub(crate) fn move_probs_for_board<T, N, F>(matrix_buf: &mut MatrixBuffer<T>, converter: &Converter, network: &N, board: &Board, f: F) -> Vec<(Move, f32)>
+    where N: ForwardNet,
+        F: FnMut(&mut T) -> &mut [f32]
+{
+    let mut leaves = vec![(board.clone(), Vec::new())];
+    match compute_move_probs(matrix_buf, converter, network, leaves.as_mut_slice(), &EmptyIntrChecker::new(), f) {
+        Ok(()) => leaves.pop().map(|(_, probs)| probs).unwrap_or(Vec::new()),
+        Err(_) => Vec::new(),
+    }
 }
@@ -5,14 +5,18 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 //
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Mutex;
 use crate::chess::movegen::legal;
 use crate::chess::Board;
 use crate::chess::Color;
 use crate::chess::Move;
-use crate::matrix::Frontend;
+use crate::engine::eval::*;
 use crate::engine::neural_search::*;
+use crate::engine::simple_eval_fun::*;
 use crate::shared::converter::*;
 use crate::shared::intr_check::*;
 use crate::shared::matrix_buffer::*;
@@ -21,13 +25,22 @@ use crate::shared::Interruption;
 
 /// A structure of neural searcher.
 ///
-/// The neural searcher searches a geme tree by using a neural network.
+/// The neural searcher searches a geme tree by using a neural network. By default, the neural
+/// searcher selects only the best move at each ply. If a beam width is greater than one, the
+/// neural searcher branches into the best moves at the first plies of beam search. Lines of beam
+/// search are evaluated by the evaluation function and the best line is backed up by the negamax
+/// algorithm.
 pub struct NeuralSearcher<T>
 {
     intr_checker: Arc<dyn IntrCheck + Send + Sync>,
     converter: Converter,
     matrix_buf: Mutex<MatrixBuffer<(Vec<f32>, Vec<Option<(Board, Color)>>)>>,
     network: T,
+    eval_fun: Arc<dyn Eval + Send + Sync>,
+    move_eps: AtomicU32,
+    move_temperature: AtomicU32,
+    beam_width: AtomicUsize,
+    beam_depth: AtomicUsize,
 }
 
 impl<T> NeuralSearcher<T>
@@ -35,11 +48,35 @@ impl<T> NeuralSearcher<T>
     /// A maximal number of columns.
     pub const MAX_COL_COUNT: usize = 1024;
     
-    /// A move epsilon.
-    pub const MOVE_EPS: f32 = 0.01;
+    /// A default move epsilon.
+    pub const MOVE_EPS: f32 = DEFAULT_MOVE_EPS;
+
+    /// A default beam width.
+    pub const DEFAULT_BEAM_WIDTH: usize = 1;
+
+    /// A minimal beam width.
+    pub const MIN_BEAM_WIDTH: usize = 1;
+
+    /// A maximal beam width.
+    pub const MAX_BEAM_WIDTH: usize = 8;
+
+    /// A default depth of beam search.
+    pub const DEFAULT_BEAM_DEPTH: usize = 2;
+
+    /// A minimal depth of beam search.
+    pub const MIN_BEAM_DEPTH: usize = 0;
+
+    /// A maximal depth of beam search.
+    pub const MAX_BEAM_DEPTH: usize = 4;
     
     /// Creates a neural searcher.
+    ///
+    /// Lines of beam search are evaluated by the simple evaluation function.
     pub fn new(intr_checker: Arc<dyn IntrCheck + Send + Sync>, converter: Converter, network: T) -> Self
+    { Self::new_with_eval_fun(intr_checker, converter, network, Arc::new(SimpleEvalFun::new())) }
+
+    /// Creates a neural searcher with the evaluation function for lines of beam search.
+    pub fn new_with_eval_fun(intr_checker: Arc<dyn IntrCheck + Send + Sync>, converter: Converter, network: T, eval_fun: Arc<dyn Eval + Send + Sync>) -> Self
     {
         let matrix_buf = Mutex::new(MatrixBuffer::new(Converter::BOARD_ROW_COUNT, 0, Self::MAX_COL_COUNT, 0, (vec![0.0; converter.move_row_count() * Self::MAX_COL_COUNT], vec![None; Self::MAX_COL_COUNT])));
         NeuralSearcher {
@@ -47,6 +84,11 @@ impl<T> NeuralSearcher<T>
             converter,
             matrix_buf,
             network,
+            eval_fun,
+            move_eps: AtomicU32::new(Self::MOVE_EPS.to_bits()),
+            move_temperature: AtomicU32::new(DEFAULT_MOVE_TEMPERATURE.to_bits()),
+            beam_width: AtomicUsize::new(Self::DEFAULT_BEAM_WIDTH),
+            beam_depth: AtomicUsize::new(Self::DEFAULT_BEAM_DEPTH),
         }
     }
     
@@ -57,48 +99,126 @@ impl<T> NeuralSearcher<T>
     /// Returns the neural network.
     pub fn network(&self) -> &T
     { &self.network }
+
+    /// Returns the evaluation function for lines of beam search.
+    pub fn eval_fun(&self) -> &Arc<dyn Eval + Send + Sync>
+    { &self.eval_fun }
+
+    /// Returns the beam width.
+    ///
+    /// The beam width is a number of the best moves that are searched at each ply of beam search.
+    /// The beam search is disabled for the beam width that is equal to one.
+    pub fn beam_width(&self) -> usize
+    { self.beam_width.load(Ordering::SeqCst) }
+
+    /// Sets the beam width.
+    pub fn set_beam_width(&self, beam_width: usize)
+    { self.beam_width.store(beam_width.clamp(Self::MIN_BEAM_WIDTH, Self::MAX_BEAM_WIDTH), Ordering::SeqCst); }
+
+    /// Returns the depth of beam search.
+    ///
+    /// The depth of beam search is a number of the first plies of neural search that are branched.
+    pub fn beam_depth(&self) -> usize
+    { self.beam_depth.load(Ordering::SeqCst) }
+
+    /// Sets the depth of beam search.
+    pub fn set_beam_depth(&self, beam_depth: usize)
+    { self.beam_depth.store(beam_depth.clamp(Self::MIN_BEAM_DEPTH, Self::MAX_BEAM_DEPTH), Ordering::SeqCst); }
+
+    fn evaluate_line(&self, board: &Board, line: &[Move]) -> i32
+    {
+        let tmp_board = board_after_pv(board, line);
+        let value = if !tmp_board.has_legal_moves() {
+            if tmp_board.is_check() {
+                MIN_EVAL_MATE_VALUE
+            } else {
+                0
+            }
+        } else {
+            self.eval_fun.evaluate(&tmp_board)
+        };
+        if tmp_board.side() == board.side() {
+            value
+        } else {
+            -value
+        }
+    }
+
+    fn back_up_lines(lines: &[(&[Move], i32, usize)], ply: usize) -> (i32, usize)
+    {
+        match lines {
+            [(_, value, line_idx)] => (*value, *line_idx),
+            _ => {
+                let mut best: Option<(i32, usize)> = None;
+                for group in lines.chunk_by(|(line1, _, _), (line2, _, _)| line1.get(ply) == line2.get(ply)) {
+                    let (value, line_idx) = Self::back_up_lines(group, ply + 1);
+                    best = match best {
+                        Some((best_value, _)) if ply % 2 == 0 && value <= best_value => best,
+                        Some((best_value, _)) if ply % 2 == 1 && value >= best_value => best,
+                        _ => Some((value, line_idx)),
+                    };
+                }
+                best.unwrap_or((0, lines[0].2))
+            },
+        }
+    }
 }
 
-impl<T: Net> NeuralSearch for NeuralSearcher<T>
+impl<T: ForwardNet> NeuralSearcher<T>
 {
-    fn intr_checker(&self) -> &Arc<dyn IntrCheck + Send + Sync + 'static>
-    { &self.intr_checker }
-    
-    fn search(&self, board: &Board, pvs: &mut [Vec<Move>], depth: usize) -> Result<(), Interruption>
+    fn search_best_moves(&self, board: &Board, pvs: &mut [(Vec<Move>, Vec<Move>)], depth: usize, move_count: usize) -> Result<(), Interruption>
     {
         let mut matrix_buf_g = self.matrix_buf.lock().unwrap();
-        matrix_buf_g.do_elems(pvs, 0, &*self.intr_checker, |pv, elems, _, j, col_count| {
-                let mut tmp_board = board.clone();
-                for mv in pv {
-                    match tmp_board.make_move(*mv) {
-                        Ok(tmp_new_board) => tmp_board = tmp_new_board,
-                        Err(_) => break,
-                    }
+        matrix_buf_g.do_elem_bufs(pvs, 0, &*self.intr_checker, |(pv, _), elems, _, j, col_count| {
+                self.converter.board_to_matrix_col(&board_after_pv(board, pv), elems, j, col_count);
+        }, |input_elems, _, pair, pvs| {
+                let (output_elems, pairs) = pair;
+                let col_count = pvs.len();
+                for (j, (pv, _)) in pvs.iter().enumerate() {
+                    let tmp_board = board_after_pv(board, pv);
+                    let color = tmp_board.side();
+                    pairs[j] = Some((tmp_board, color));
                 }
-                self.converter.board_to_matrix_col(&tmp_board, elems, j, col_count);
-        }, |i, _, pair, pvs| {
+                self.network.compute_elems(input_elems, col_count, depth, 1, output_elems.as_mut_slice(), |output_elems| {
+                        self.intr_checker.check()?;
+                        for (j, (_, best_moves)) in pvs.iter_mut().enumerate() {
+                            if let Some((tmp_board, color)) = &pairs[j] {
+                                let moves = legal::gen_all(tmp_board);
+                                *best_moves = self.converter.matrix_col_to_moves(&moves, *color, output_elems, j, col_count, self.move_eps());
+                                best_moves.truncate(move_count);
+                            }
+                        }
+                        Ok(())
+                })
+        })
+    }
+    
+    fn search_lines(&self, board: &Board, pvs: &mut [Vec<Move>], depth: usize) -> Result<(), Interruption>
+    {
+        let move_temperature = self.move_temperature();
+        let mut matrix_buf_g = self.matrix_buf.lock().unwrap();
+        matrix_buf_g.do_elem_bufs(pvs, 0, &*self.intr_checker, |pv, elems, _, j, col_count| {
+                self.converter.board_to_matrix_col(&board_after_pv(board, pv), elems, j, col_count);
+        }, |input_elems, _, pair, pvs| {
                 let (output_elems, pairs) = pair;
                 let col_count = pvs.len();
                 for (j, pv) in pvs.iter().enumerate() {
-                    let mut tmp_board = board.clone();
-                    for mv in pv {
-                        match tmp_board.make_move(*mv) {
-                            Ok(tmp_new_board) => tmp_board = tmp_new_board,
-                            Err(_) => break,
-                        }
-                    }
-                    pairs[j] = Some((tmp_board.clone(), tmp_board.side()));
+                    let tmp_board = board_after_pv(board, pv);
+                    let color = tmp_board.side();
+                    pairs[j] = Some((tmp_board, color));
                 }
-                self.network.compute(&i, depth, depth, |_| self.intr_checker.check(), |o| {
+                self.network.compute_elems(input_elems, col_count, depth, depth, output_elems.as_mut_slice(), |output_elems| {
                         self.intr_checker.check()?;
-                        let frontend = Frontend::new().unwrap();
-                        let mut is_transposed = false;
-                        frontend.get_elems_and_transpose_flag(&o, &mut output_elems[0..(self.converter.move_row_count() * col_count)], &mut is_transposed).unwrap();
                         for (j, pv) in pvs.iter_mut().enumerate() {
                             match &pairs[j] {
                                 Some((tmp_board, color)) => {
                                     let moves = legal::gen_all(&tmp_board);
-                                    match self.converter.matrix_col_to_move(&moves, *color, output_elems.as_slice(), j, col_count, Self::MOVE_EPS) {
+                                    let mv = if move_temperature > 0.0 {
+                                        self.converter.matrix_col_to_random_move(&moves, *color, output_elems, j, col_count, move_temperature)
+                                    } else {
+                                        self.converter.matrix_col_to_move(&moves, *color, output_elems, j, col_count, self.move_eps())
+                                    };
+                                    match mv {
                                         Some(mv) => {
                                             match tmp_board.make_move(mv) {
                                                 Ok(tmp_new_board) => {
@@ -119,3 +239,96 @@ impl<T: Net> NeuralSearch for NeuralSearcher<T>
         })
     }
 }
+
+impl<T: ForwardNet> NeuralSearch for NeuralSearcher<T>
+{
+    fn intr_checker(&self) -> &Arc<dyn IntrCheck + Send + Sync + 'static>
+    { &self.intr_checker }
+    
+    fn search(&self, board: &Board, pvs: &mut [Vec<Move>], depth: usize) -> Result<(), Interruption>
+    {
+        let beam_width = self.beam_width();
+        let beam_depth = self.beam_depth().min(depth);
+        if beam_width <= 1 || beam_depth == 0 {
+            return self.search_lines(board, pvs, depth);
+        }
+        let mut lines: Vec<(usize, Vec<Move>, bool)> = pvs.iter().enumerate().map(|(j, pv)| (j, pv.clone(), true)).collect();
+        for ply in 0..beam_depth {
+            let mut tmp_lines: Vec<(Vec<Move>, Vec<Move>)> = lines.iter().filter(|(_, _, is_active)| *is_active).map(|(_, line, _)| (line.clone(), Vec::new())).collect();
+            self.search_best_moves(board, tmp_lines.as_mut_slice(), depth - ply, beam_width)?;
+            let mut tmp_line_iter = tmp_lines.into_iter();
+            let mut new_lines: Vec<(usize, Vec<Move>, bool)> = Vec::new();
+            for (j, line, is_active) in lines {
+                let best_moves = if is_active {
+                    match tmp_line_iter.next() {
+                        Some((_, best_moves)) => best_moves,
+                        None => Vec::new(),
+                    }
+                } else {
+                    Vec::new()
+                };
+                if best_moves.is_empty() {
+                    new_lines.push((j, line, false));
+                } else {
+                    for mv in &best_moves {
+                        let mut new_line = line.clone();
+                        new_line.push(*mv);
+                        new_lines.push((j, new_line, true));
+                    }
+                }
+            }
+            lines = new_lines;
+        }
+        if depth > beam_depth {
+            let mut tmp_lines: Vec<Vec<Move>> = lines.iter().filter(|(_, _, is_active)| *is_active).map(|(_, line, _)| line.clone()).collect();
+            self.search_lines(board, tmp_lines.as_mut_slice(), depth - beam_depth)?;
+            let mut tmp_line_iter = tmp_lines.into_iter();
+            for (_, line, is_active) in &mut lines {
+                if *is_active {
+                    if let Some(tmp_line) = tmp_line_iter.next() {
+                        *line = tmp_line;
+                    }
+                }
+            }
+        }
+        let mut k = 0usize;
+        for (j, pv) in pvs.iter_mut().enumerate() {
+            let mut l = k;
+            while l < lines.len() && lines[l].0 == j {
+                l += 1;
+            }
+            let tmp_board = board_after_pv(board, pv);
+            let evaluated_lines: Vec<(&[Move], i32, usize)> = lines[k..l].iter().enumerate().map(|(m, (_, line, _))| {
+                    let neural_line = &line[pv.len()..];
+                    (neural_line, self.evaluate_line(&tmp_board, neural_line), k + m)
+            }).collect();
+            if !evaluated_lines.is_empty() {
+                let (_, line_idx) = Self::back_up_lines(evaluated_lines.as_slice(), 0);
+                *pv = lines[line_idx].1.clone();
+            }
+            k = l;
+        }
+        Ok(())
+    }
+
+    fn move_probs(&self, board: &Board) -> Vec<(Move, f32)>
+    {
+        let mut matrix_buf_g = self.matrix_buf.lock().unwrap();
+        move_probs_for_board(&mut *matrix_buf_g, &self.converter, &self.network, board, |pair| pair.0.as_mut_slice())
+    }
+
+    fn move_eps(&self) -> f32
+    { f32::from_bits(self.move_eps.load(Ordering::SeqCst)) }
+
+    fn set_move_eps(&self, move_eps: f32)
+    { self.move_eps.store(move_eps.clamp(MIN_MOVE_EPS, MAX_MOVE_EPS).to_bits(), Ordering::SeqCst); }
+
+    fn move_temperature(&self) -> f32
+    { f32::from_bits(self.move_temperature.load(Ordering::SeqCst)) }
+
+    fn set_move_temperature(&self, move_temperature: f32)
+    { self.move_temperature.store(move_temperature.clamp(MIN_MOVE_TEMPERATURE, MAX_MOVE_TEMPERATURE).to_bits(), Ordering::SeqCst); }
+}
+
+#[cfg(test)]
+mod tests;
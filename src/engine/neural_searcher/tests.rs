@@ -0,0 +1,195 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::shared::cpu_matrix::*;
+use crate::shared::cpu_network::*;
+use crate::shared::cpu_network_v3::*;
+use crate::shared::index_converter::*;
+use crate::shared::xavier_init::*;
+use super::*;
+
+fn random_cpu_layer(input_count: usize, output_count: usize) -> CpuLayer
+{
+    let mut w_elems = vec![0.0f32; output_count * input_count];
+    xavier_init(w_elems.as_mut_slice(), input_count, output_count);
+    let mut b_elems = vec![0.0f32; output_count];
+    xavier_init(b_elems.as_mut_slice(), input_count, output_count);
+    CpuLayer::new(CpuMatrix::new_with_elems(output_count, input_count, w_elems.as_slice()), CpuMatrix::new_with_elems(output_count, 1, b_elems.as_slice()))
+}
+
+fn random_cpu_network_v3(converter: &Converter, count: usize) -> CpuNetworkV3
+{
+    let input_layer = random_cpu_layer(Converter::BOARD_ROW_COUNT, count);
+    let search_layer = random_cpu_layer(count, count);
+    let pv_layer = random_cpu_layer(count, count);
+    let output_layer = random_cpu_layer(count, converter.move_row_count());
+    CpuNetworkV3::new(input_layer, search_layer, pv_layer, output_layer)
+}
+
+#[test]
+fn test_neural_searcher_search_searches_with_cpu_network()
+{
+    let converter = Converter::new(IndexConverter::new());
+    let network = random_cpu_network_v3(&converter, 32);
+    let neural_searcher = NeuralSearcher::new(Arc::new(EmptyIntrChecker::new()), converter, network);
+    let board = Board::initial();
+    let mv = Move::from_uci("e2e4", &board).unwrap();
+    let mut pvs = vec![vec![mv], Vec::new()];
+    neural_searcher.search(&board, pvs.as_mut_slice(), 3).unwrap();
+    assert_eq!(mv, pvs[0][0]);
+    assert_eq!(4, pvs[0].len());
+    assert_eq!(3, pvs[1].len());
+    for pv in &pvs {
+        let mut tmp_board = board.clone();
+        for mv in pv {
+            tmp_board = tmp_board.make_move(*mv).unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_neural_searcher_move_probs_returns_probabilities_with_cpu_network()
+{
+    let converter = Converter::new(IndexConverter::new());
+    let network = random_cpu_network_v3(&converter, 32);
+    let neural_searcher = NeuralSearcher::new(Arc::new(EmptyIntrChecker::new()), converter, network);
+    let board = Board::initial();
+    let probs = neural_searcher.move_probs(&board);
+    assert_eq!(20, probs.len());
+    let sum: f32 = probs.iter().map(|(_, prob)| *prob).sum();
+    assert!((sum - 1.0).abs() < 0.001);
+}
+
+fn uci_moves(board: &Board, uci_list: &[&str]) -> Vec<Move>
+{
+    let mut tmp_board = board.clone();
+    let mut moves: Vec<Move> = Vec::new();
+    for uci in uci_list {
+        let mv = Move::from_uci(uci, &tmp_board).unwrap();
+        tmp_board = tmp_board.make_move(mv).unwrap();
+        moves.push(mv);
+    }
+    moves
+}
+
+#[test]
+fn test_neural_searcher_back_up_lines_maximizes_at_even_plies_and_minimizes_at_odd_plies()
+{
+    let board = Board::initial();
+    let line1 = uci_moves(&board, &["e2e4", "e7e5"]);
+    let line2 = uci_moves(&board, &["e2e4", "d7d5"]);
+    let line3 = uci_moves(&board, &["d2d4", "e7e5"]);
+    let line4 = uci_moves(&board, &["d2d4", "d7d5"]);
+    let lines: Vec<(&[Move], i32, usize)> = vec![
+        (line1.as_slice(), 10, 0),
+        (line2.as_slice(), -20, 1),
+        (line3.as_slice(), 5, 2),
+        (line4.as_slice(), 30, 3)
+    ];
+    assert_eq!((5, 2), NeuralSearcher::<()>::back_up_lines(lines.as_slice(), 0));
+    assert_eq!((-20, 1), NeuralSearcher::<()>::back_up_lines(&lines[0..2], 0));
+    assert_eq!((5, 2), NeuralSearcher::<()>::back_up_lines(&lines[2..4], 1));
+    assert_eq!((10, 0), NeuralSearcher::<()>::back_up_lines(&lines[0..1], 0));
+}
+
+#[test]
+fn test_neural_searcher_back_up_lines_backs_up_groups_with_different_lengths()
+{
+    let board = Board::initial();
+    let line1 = uci_moves(&board, &["e2e4"]);
+    let line2 = uci_moves(&board, &["d2d4"]);
+    let line3 = uci_moves(&board, &["d2d4", "e7e5"]);
+    let line4 = uci_moves(&board, &["d2d4", "d7d5", "c2c4"]);
+    let line5 = uci_moves(&board, &["d2d4", "d7d5", "g1f3"]);
+    let lines: Vec<(&[Move], i32, usize)> = vec![
+        (line1.as_slice(), 7, 0),
+        (line2.as_slice(), 40, 1),
+        (line3.as_slice(), 20, 2),
+        (line4.as_slice(), 15, 3),
+        (line5.as_slice(), 12, 4)
+    ];
+    // The second group is minimized to 15 of the line that is maximized at the third ply.
+    assert_eq!((15, 3), NeuralSearcher::<()>::back_up_lines(lines.as_slice(), 0));
+    let lines2: Vec<(&[Move], i32, usize)> = vec![
+        (line1.as_slice(), 25, 0),
+        (line3.as_slice(), 20, 2),
+        (line4.as_slice(), 15, 3)
+    ];
+    assert_eq!((25, 0), NeuralSearcher::<()>::back_up_lines(lines2.as_slice(), 0));
+}
+
+#[test]
+fn test_neural_searcher_back_up_lines_backs_up_lines_ending_in_checkmate_and_stalemate()
+{
+    let converter = Converter::new(IndexConverter::new());
+    let network = random_cpu_network_v3(&converter, 32);
+    let neural_searcher = NeuralSearcher::new(Arc::new(EmptyIntrChecker::new()), converter, network);
+    let board = Board::from_fen("k7/2P5/K7/8/8/8/8/2Q5 w - - 0 1").unwrap();
+    let line1 = uci_moves(&board, &["c1c2"]);
+    let line2 = uci_moves(&board, &["c7c8q"]);
+    let line3 = uci_moves(&board, &["a6a5"]);
+    let value1 = neural_searcher.evaluate_line(&board, line1.as_slice());
+    let value2 = neural_searcher.evaluate_line(&board, line2.as_slice());
+    let value3 = neural_searcher.evaluate_line(&board, line3.as_slice());
+    // The first line ends in a stalemate and the second line ends in a checkmate.
+    assert_eq!(0, value1);
+    assert_eq!(-MIN_EVAL_MATE_VALUE, value2);
+    assert!(value3 > 0);
+    let lines: Vec<(&[Move], i32, usize)> = vec![
+        (line1.as_slice(), value1, 0),
+        (line2.as_slice(), value2, 1),
+        (line3.as_slice(), value3, 2)
+    ];
+    assert_eq!((value2, 1), NeuralSearcher::<()>::back_up_lines(lines.as_slice(), 0));
+    let lines2: Vec<(&[Move], i32, usize)> = vec![
+        (line3.as_slice(), -value3, 2),
+        (line1.as_slice(), value1, 0)
+    ];
+    assert_eq!((value1, 0), NeuralSearcher::<()>::back_up_lines(lines2.as_slice(), 0));
+}
+
+#[test]
+fn test_neural_searcher_search_searches_with_beam_search()
+{
+    let converter = Converter::new(IndexConverter::new());
+    let network = random_cpu_network_v3(&converter, 32);
+    let neural_searcher = NeuralSearcher::new(Arc::new(EmptyIntrChecker::new()), converter, network);
+    neural_searcher.set_beam_width(2);
+    neural_searcher.set_beam_depth(2);
+    let board = Board::initial();
+    let mv = Move::from_uci("e2e4", &board).unwrap();
+    let mut pvs = vec![vec![mv], Vec::new()];
+    neural_searcher.search(&board, pvs.as_mut_slice(), 3).unwrap();
+    assert_eq!(mv, pvs[0][0]);
+    assert_eq!(4, pvs[0].len());
+    assert_eq!(3, pvs[1].len());
+    for pv in &pvs {
+        let mut tmp_board = board.clone();
+        for mv in pv {
+            tmp_board = tmp_board.make_move(*mv).unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_neural_searcher_search_selects_checkmate_by_beam_search()
+{
+    let converter = Converter::new(IndexConverter::new());
+    let network = random_cpu_network_v3(&converter, 32);
+    let neural_searcher = NeuralSearcher::new(Arc::new(EmptyIntrChecker::new()), converter, network);
+    neural_searcher.set_beam_width(NeuralSearcher::<CpuNetworkV3>::MAX_BEAM_WIDTH);
+    neural_searcher.set_beam_depth(1);
+    neural_searcher.set_move_eps(0.0);
+    // The board has seven legal moves and the promotions to a queen and a rook are checkmates.
+    let board = Board::from_fen("k7/2P5/K7/8/8/8/8/8 w - - 0 1").unwrap();
+    let mut pvs = vec![Vec::new()];
+    neural_searcher.search(&board, pvs.as_mut_slice(), 1).unwrap();
+    assert_eq!(1, pvs[0].len());
+    let new_board = board.make_move(pvs[0][0]).unwrap();
+    assert!(new_board.is_check());
+    assert!(!new_board.has_legal_moves());
+}
@@ -5,13 +5,13 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 //
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Mutex;
 use crate::chess::movegen::legal;
 use crate::chess::Board;
 use crate::chess::Move;
-use crate::matrix::Frontend;
-use crate::matrix::Matrix;
 use crate::engine::neural_search::*;
 use crate::shared::converter::*;
 use crate::shared::intr_check::*;
@@ -25,13 +25,15 @@ pub struct OneNeuralSearcher<T>
     converter: Converter,
     matrix_buf: Mutex<MatrixBuffer<(Vec<f32>, Vec<f32>, Vec<Board>)>>,
     network: T,
+    move_eps: AtomicU32,
+    move_temperature: AtomicU32,
 }
 
 impl<T> OneNeuralSearcher<T>
 {
     pub const MAX_COL_COUNT: usize = 1024;
     
-    pub const MOVE_EPS: f32 = 0.01;
+    pub const MOVE_EPS: f32 = DEFAULT_MOVE_EPS;
     
     pub fn new(intr_checker: Arc<dyn IntrCheck + Send + Sync>, converter: Converter, network: T) -> Self
     {
@@ -41,6 +43,8 @@ impl<T> OneNeuralSearcher<T>
             converter,
             matrix_buf,
             network,
+            move_eps: AtomicU32::new(Self::MOVE_EPS.to_bits()),
+            move_temperature: AtomicU32::new(DEFAULT_MOVE_TEMPERATURE.to_bits()),
         }
     }
     
@@ -51,63 +55,45 @@ impl<T> OneNeuralSearcher<T>
     { &self.network }
 }
 
-impl<T: Net> NeuralSearch for OneNeuralSearcher<T>
+impl<T: ForwardNet> NeuralSearch for OneNeuralSearcher<T>
 {
     fn intr_checker(&self) -> &Arc<dyn IntrCheck + Send + Sync + 'static>
     { &self.intr_checker }
     
     fn search(&self, board: &Board, pvs: &mut [Vec<Move>], depth: usize) -> Result<(), Interruption>
     {
+        let move_temperature = self.move_temperature();
         let mut matrix_buf_g = self.matrix_buf.lock().unwrap();
-        matrix_buf_g.do_elems(pvs, 0, &*self.intr_checker, |pv, elems, _, j, col_count| {
-                let mut tmp_board = board.clone();
-                for mv in pv {
-                    match tmp_board.make_move(*mv) {
-                        Ok(tmp_new_board) => tmp_board = tmp_new_board,
-                        Err(_) => break,
-                    }
-                }
-                self.converter.board_to_matrix_col(&tmp_board, elems, j, col_count);
-        }, |i, _, tuple, pvs| {
+        matrix_buf_g.do_elem_bufs(pvs, 0, &*self.intr_checker, |pv, elems, _, j, col_count| {
+                self.converter.board_to_matrix_col(&board_after_pv(board, pv), elems, j, col_count);
+        }, |first_input_elems, _, tuple, pvs| {
                 let (input_elems, output_elems, boards) = tuple;
                 let col_count = pvs.len();
                 for (j, pv) in pvs.iter().enumerate() {
-                    let mut tmp_board = board.clone();
-                    for mv in pv {
-                        match tmp_board.make_move(*mv) {
-                            Ok(tmp_new_board) => tmp_board = tmp_new_board,
-                            Err(_) => break,
-                        }
-                    }
-                    boards[j] = tmp_board.clone();
+                    boards[j] = board_after_pv(board, pv);
                 }
-                let mut tmp_i = i.clone();
                 let mut is_first = true;
                 for _ in 0..depth {
                     if !is_first {
                         for j in 0..col_count {
                             self.converter.board_to_matrix_col(&boards[j], &mut input_elems[0..(Converter::BOARD_ROW_COUNT * col_count)], j, col_count);
                         }
-                        tmp_i = Matrix::new_with_elems(Converter::BOARD_ROW_COUNT, col_count, &input_elems[0..(Converter::BOARD_ROW_COUNT * col_count)]);
                     }
-                    self.network.compute(&tmp_i, 1, 1, |_| self.intr_checker.check(), |o| {
+                    let tmp_input_elems = if is_first { first_input_elems } else { &input_elems[0..(Converter::BOARD_ROW_COUNT * col_count)] };
+                    self.network.compute_elems(tmp_input_elems, col_count, 1, 1, output_elems.as_mut_slice(), |output_elems| {
                             self.intr_checker.check()?;
-                            let frontend = Frontend::new().unwrap();
-                            let mut is_transposed = false;
-                            frontend.get_elems_and_transpose_flag(&o, &mut output_elems[0..(self.converter.move_row_count() * col_count)], &mut is_transposed).unwrap();
                             for (j, pv) in pvs.iter_mut().enumerate() {
                                 let moves = legal::gen_all(&boards[j]);
-                                match self.converter.matrix_col_to_move(&moves, boards[j].side(), output_elems.as_slice(), j, col_count, Self::MOVE_EPS) {
-                                    Some(mv) => {
-                                        match boards[j].make_move(mv) {
-                                            Ok(tmp_new_board) => {
-                                                pv.push(mv);
-                                                boards[j] = tmp_new_board;
-                                            },
-                                            Err(_) => (),
-                                        }
-                                    },
-                                    None => (),
+                                let mv = if move_temperature > 0.0 {
+                                    self.converter.matrix_col_to_random_move(&moves, boards[j].side(), output_elems, j, col_count, move_temperature)
+                                } else {
+                                    self.converter.matrix_col_to_move(&moves, boards[j].side(), output_elems, j, col_count, self.move_eps())
+                                };
+                                if let Some(mv) = mv {
+                                    if let Ok(tmp_new_board) = boards[j].make_move(mv) {
+                                        pv.push(mv);
+                                        boards[j] = tmp_new_board;
+                                    }
                                 }
                             }
                             Ok(())
@@ -117,4 +103,22 @@ impl<T: Net> NeuralSearch for OneNeuralSearcher<T>
                 Ok(())
         })
     }
+
+    fn move_probs(&self, board: &Board) -> Vec<(Move, f32)>
+    {
+        let mut matrix_buf_g = self.matrix_buf.lock().unwrap();
+        move_probs_for_board(&mut *matrix_buf_g, &self.converter, &self.network, board, |tuple| tuple.1.as_mut_slice())
+    }
+
+    fn move_eps(&self) -> f32
+    { f32::from_bits(self.move_eps.load(Ordering::SeqCst)) }
+
+    fn set_move_eps(&self, move_eps: f32)
+    { self.move_eps.store(move_eps.clamp(MIN_MOVE_EPS, MAX_MOVE_EPS).to_bits(), Ordering::SeqCst); }
+
+    fn move_temperature(&self) -> f32
+    { f32::from_bits(self.move_temperature.load(Ordering::SeqCst)) }
+
+    fn set_move_temperature(&self, move_temperature: f32)
+    { self.move_temperature.store(move_temperature.clamp(MIN_MOVE_TEMPERATURE, MAX_MOVE_TEMPERATURE).to_bits(), Ordering::SeqCst); }
 }
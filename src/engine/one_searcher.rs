@@ -5,64 +5,183 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 //
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread::scope;
 use crate::chess::movegen::semilegal;
 use crate::chess::types::OutcomeFilter;
+use crate::chess::Board;
 use crate::chess::Move;
 use crate::chess::MoveChain;
 use crate::chess::Outcome;
+use crate::engine::draw::*;
 use crate::engine::eval::*;
 use crate::engine::middle_searcher::*;
+use crate::engine::neural_search::*;
 use crate::engine::search::*;
+use crate::engine::transposition_table::*;
 use crate::shared::intr_check::*;
 use crate::shared::Interruption;
 
+type MoveLine = (i32, u64, u64, Vec<Move>);
+
 /// A structure of searcher for depth one.
 ///
 /// The searcher classically searches a game tree to depth one and then uses a middle search and a
-/// neural search.
-#[derive(Clone)]
+/// neural search. The moves at depth one can be distributed between many threads that share the
+/// interruption checker and the transposition table. A value that is fixed in the transposition
+/// table is used instead of a search for the root and the boards after the moves at depth one.
 pub struct OneSearcher
 {
     middle_searcher: MiddleSearcher,
-    middle_depth: usize,
+    middle_depth: AtomicUsize,
+    thread_count: AtomicUsize,
+    curr_move: Mutex<Option<(Move, usize)>>,
+    searched_node_count: AtomicU64,
 }
 
 impl OneSearcher
 {
+    /// A minimal number of threads.
+    pub const MIN_THREAD_COUNT: usize = 1;
+
+    /// A maximal number of threads.
+    pub const MAX_THREAD_COUNT: usize = 256;
+
+    /// A default depth of middle search.
+    pub const DEFAULT_MIDDLE_DEPTH: usize = 2;
+
+    /// A minimal depth of middle search.
+    pub const MIN_MIDDLE_DEPTH: usize = 1;
+
+    /// A maximal depth of middle search.
+    pub const MAX_MIDDLE_DEPTH: usize = 8;
+    
     /// Creates a searcher for depth one.
     pub fn new(middle_searcher: MiddleSearcher, middle_depth: usize) -> Self
-    { OneSearcher { middle_searcher, middle_depth, } }
+    { Self::new_with_thread_count(middle_searcher, middle_depth, 1) }
+
+    /// Creates a searcher for depth one with the number of threads.
+    pub fn new_with_thread_count(middle_searcher: MiddleSearcher, middle_depth: usize, thread_count: usize) -> Self
+    {
+        let middle_depth = AtomicUsize::new(middle_depth.clamp(Self::MIN_MIDDLE_DEPTH, Self::MAX_MIDDLE_DEPTH));
+        let thread_count = AtomicUsize::new(thread_count.clamp(Self::MIN_THREAD_COUNT, Self::MAX_THREAD_COUNT));
+        OneSearcher {
+            middle_searcher,
+            middle_depth,
+            thread_count,
+            curr_move: Mutex::new(None),
+            searched_node_count: AtomicU64::new(0),
+        }
+    }
 
     /// Returns the middle searcher.
     pub fn middle_searcher(&self) -> &MiddleSearcher
     { &self.middle_searcher }
-}
 
-impl Search for OneSearcher
-{
-    fn intr_checker(&self) -> &Arc<dyn IntrCheck + Send + Sync>
-    { self.middle_searcher.intr_checker() }
-    
-    fn search(&self, move_chain: &mut MoveChain, depth: usize, search_moves: &Option<Vec<Move>>) -> Result<(i32, u64, u64, Vec<Move>), Interruption>
+    fn set_curr_move(&self, curr_move: Option<(Move, usize)>)
+    {
+        let mut curr_move_g = self.curr_move.lock().unwrap();
+        *curr_move_g = curr_move;
+    }
+
+    fn search_move(&self, move_chain: &mut MoveChain, history: &[u64], mv: Move, move_number: usize, depth: usize, middle_depth: usize) -> Result<Option<MoveLine>, Interruption>
+    {
+        self.set_curr_move(Some((mv, move_number)));
+        match move_chain.push(mv) {
+            Ok(()) => {
+                if let Some(outcome) = move_chain.set_auto_outcome(OutcomeFilter::Relaxed) {
+                    let value = match outcome {
+                        Outcome::Win { .. } => MAX_EVAL_MIDDLE_MATE_VALUE + (middle_depth as i32),
+                        Outcome::Draw(_) => 0,
+                    };
+                    move_chain.pop();
+                    self.searched_node_count.fetch_add(1, Ordering::SeqCst);
+                    return Ok(Some((value, 0, 1, vec![mv])));
+                }
+                match self.middle_searcher.transposition_table().get(move_chain.last().zobrist_hash()) {
+                    Some(entry) if entry.is_fixed && entry.depth >= depth - 1 => {
+                        let mut pv = vec![mv];
+                        pv.extend(entry.best_move);
+                        move_chain.pop();
+                        self.searched_node_count.fetch_add(1, Ordering::SeqCst);
+                        return Ok(Some((-entry.value, 1, 1, pv)));
+                    },
+                    _ => (),
+                }
+                let res = self.middle_searcher.search_with_history(move_chain.last(), history, middle_depth, depth - 1);
+                move_chain.pop();
+                let (neg_value, middle_node_count, node_count, tmp_pv) = res?;
+                self.searched_node_count.fetch_add(node_count, Ordering::SeqCst);
+                let mut pv = vec![mv];
+                pv.extend_from_slice(tmp_pv.as_slice());
+                Ok(Some((-neg_value, middle_node_count, node_count, pv)))
+            },
+            Err(_) => Ok(None),
+        }
+    }
+
+    fn search_moves_in_threads(&self, move_chain: &MoveChain, history: &[u64], moves: &[Move], depth: usize, middle_depth: usize, thread_count: usize) -> Result<Vec<Option<MoveLine>>, Interruption>
     {
+        let next_idx = AtomicUsize::new(0);
+        let results: Mutex<Vec<Option<MoveLine>>> = Mutex::new(vec![None; moves.len()]);
+        let thread_results: Vec<Result<(), Interruption>> = scope(|s| {
+                let mut handles = Vec::new();
+                for _ in 0..thread_count {
+                    handles.push(s.spawn(|| {
+                            let mut thread_move_chain = move_chain.clone();
+                            loop {
+                                let idx = next_idx.fetch_add(1, Ordering::SeqCst);
+                                if idx >= moves.len() {
+                                    break;
+                                }
+                                let res = self.search_move(&mut thread_move_chain, history, moves[idx], idx + 1, depth, middle_depth)?;
+                                let mut results_g = results.lock().unwrap();
+                                results_g[idx] = res;
+                            }
+                            Ok(())
+                    }));
+                }
+                handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+        });
+        for thread_res in thread_results {
+            thread_res?;
+        }
+        Ok(results.into_inner().unwrap())
+    }
+
+    fn search_lines(&self, move_chain: &mut MoveChain, depth: usize, search_moves: &Option<Vec<Move>>) -> Result<(SearchLines, u64, u64), Interruption>
+    {
+        self.set_curr_move(None);
+        self.searched_node_count.store(1, Ordering::SeqCst);
         self.middle_searcher.intr_checker().check()?;
-        let moves = semilegal::gen_all(move_chain.last());
+        let mut moves = semilegal::gen_all(move_chain.last());
+        let zobrist_hash = move_chain.last().zobrist_hash();
+        let root_entry = self.middle_searcher.transposition_table().get(zobrist_hash);
+        let root_best_move = root_entry.and_then(|entry| entry.best_move);
+        if let Some(i) = root_best_move.and_then(|best_move| moves.iter().position(|mv| *mv == best_move)) {
+            moves[0..=i].rotate_right(1);
+        }
         let mut middle_node_count = 1u64;
         let mut node_count = 1u64;
-        let mut pv: Vec<Move> = Vec::new();
-        let mut best_value = MIN_EVAL_VALUE;
+        let mut lines: SearchLines = Vec::new();
         match move_chain.set_auto_outcome(OutcomeFilter::Relaxed) {
             Some(outcome) => {
                 let value = match outcome {
                     Outcome::Win { .. } => MIN_EVAL_ROOT_MATE_VALUE,
                     Outcome::Draw(_) => 0,
                 };
-                return Ok((value, middle_node_count, node_count, pv));
+                lines.push((value, Vec::new()));
+                return Ok((lines, middle_node_count, node_count));
             },
             None => (),
         }
         move_chain.clear_outcome();
+        let history = move_chain_history(move_chain);
+        let mut root_moves: Vec<Move> = Vec::new();
         for mv in &moves {
             match search_moves {
                 Some(search_moves) => {
@@ -73,62 +192,122 @@ impl Search for OneSearcher
                 },
                 None => (),
             }
-            match move_chain.push(*mv) {
-                Ok(()) => {
-                    match move_chain.set_auto_outcome(OutcomeFilter::Relaxed) {
-                        Some(outcome) => {
-                            let value = match outcome {
-                                Outcome::Win { .. } => MAX_EVAL_MIDDLE_MATE_VALUE,
-                                Outcome::Draw(_) => 0,
-                            };
-                            if value > best_value {
-                                best_value = value;
-                                pv = vec![*mv];
-                            }
-                            node_count += 1;
-                            move_chain.pop();
-                            continue;
-                        },
-                        None => (),
-                    }
-                    let res = self.middle_searcher.search(move_chain.last(), self.middle_depth, depth - 1);
-                    match res {
-                        Ok((neg_value, tmp_middle_node_count, tmp_node_count, tmp_pv)) => {
-                            let value = -neg_value;
-                            if value > best_value {
-                                best_value = value;
-                                pv = vec![*mv];
-                                pv.extend_from_slice(tmp_pv.as_slice());
-                            }
-                            middle_node_count += tmp_middle_node_count;
-                            node_count += tmp_node_count;
-                        },
-                        Err(intr) => {
-                            move_chain.pop();
-                            return Err(intr);
-                        },
-                    }
-                    move_chain.pop();
-                },
-                Err(_) => (),
+            root_moves.push(*mv);
+        }
+        match root_entry {
+            Some(entry) if entry.is_fixed && entry.depth >= depth && search_moves.is_none() => {
+                match entry.best_move {
+                    Some(best_move) if root_moves.contains(&best_move) => {
+                        lines.push((entry.value, vec![best_move]));
+                        return Ok((lines, middle_node_count, node_count));
+                    },
+                    _ => (),
+                }
+            },
+            _ => (),
+        }
+        let middle_depth = self.middle_depth();
+        let thread_count = self.thread_count().min(root_moves.len());
+        let results = if thread_count > 1 {
+            self.search_moves_in_threads(move_chain, history.as_slice(), root_moves.as_slice(), depth, middle_depth, thread_count)?
+        } else {
+            let mut tmp_results: Vec<Option<MoveLine>> = Vec::new();
+            for (i, mv) in root_moves.iter().enumerate() {
+                tmp_results.push(self.search_move(move_chain, history.as_slice(), *mv, i + 1, depth, middle_depth)?);
+            }
+            tmp_results
+        };
+        for (value, tmp_middle_node_count, tmp_node_count, pv) in results.into_iter().flatten() {
+            lines.push((value, pv));
+            middle_node_count += tmp_middle_node_count;
+            node_count += tmp_node_count;
+        }
+        if search_moves.is_none() {
+            let mut best_value = MIN_EVAL_VALUE;
+            let mut best_move: Option<Move> = None;
+            for (value, pv) in &lines {
+                if *value > best_value {
+                    best_value = *value;
+                    best_move = pv.first().copied();
+                }
+            }
+            self.middle_searcher.transposition_table().set_root(zobrist_hash, best_move, best_value, depth, middle_depth);
+        }
+        Ok((lines, middle_node_count, node_count))
+    }
+}
+
+impl Search for OneSearcher
+{
+    fn intr_checker(&self) -> &Arc<dyn IntrCheck + Send + Sync>
+    { self.middle_searcher.intr_checker() }
+
+    fn transposition_table(&self) -> Option<&Arc<TranspositionTable>>
+    { Some(self.middle_searcher.transposition_table()) }
+
+    fn config(&self) -> Option<&dyn SearchConfig>
+    { Some(self) }
+
+    fn tb_hit_count(&self) -> u64
+    { self.middle_searcher.tb_hit_count() }
+
+    fn clear_tb_hit_count(&self)
+    { self.middle_searcher.clear_tb_hit_count(); }
+
+    fn curr_move(&self) -> Option<(Move, usize)>
+    {
+        let curr_move_g = self.curr_move.lock().unwrap();
+        *curr_move_g
+    }
+
+    fn searched_node_count(&self) -> u64
+    { self.searched_node_count.load(Ordering::SeqCst) }
+
+    fn eval_fun(&self) -> Option<&Arc<dyn Eval + Send + Sync>>
+    { Some(self.middle_searcher.eval_fun()) }
+
+    fn neural_searcher(&self) -> Option<&Arc<dyn NeuralSearch + Send + Sync>>
+    { Some(self.middle_searcher.neural_searcher()) }
+
+    fn move_probs(&self, board: &Board) -> Vec<(Move, f32)>
+    { self.middle_searcher.neural_searcher().move_probs(board) }
+    
+    fn search(&self, move_chain: &mut MoveChain, depth: usize, search_moves: &Option<Vec<Move>>) -> Result<(i32, u64, u64, Vec<Move>), Interruption>
+    {
+        let (lines, middle_node_count, node_count) = self.search_lines(move_chain, depth, search_moves)?;
+        let mut best_value = MIN_EVAL_VALUE;
+        let mut pv: Vec<Move> = Vec::new();
+        for (value, line_pv) in lines {
+            if value > best_value {
+                best_value = value;
+                pv = line_pv;
             }
         }
         Ok((best_value, middle_node_count, node_count, pv))
     }
 
+    fn search_multi_pv(&self, move_chain: &mut MoveChain, depth: usize, search_moves: &Option<Vec<Move>>, multi_pv: usize) -> Result<(SearchLines, u64, u64), Interruption>
+    {
+        let (mut lines, middle_node_count, node_count) = self.search_lines(move_chain, depth, search_moves)?;
+        lines.sort_by_key(|(value, _)| -value);
+        lines.truncate(multi_pv.max(1));
+        Ok((lines, middle_node_count, node_count))
+    }
+
     fn move_count_to_checkmate(&self, value: i32, depth: usize) -> Option<usize>
     {
+        let middle_depth = self.middle_depth();
         if value >= MAX_EVAL_ROOT_MATE_VALUE {
             let max_value = MAX_EVAL_ROOT_MATE_VALUE;
             let start_move_count = 0;
             Some((max_value - value + start_move_count) as usize)
         } else if value >= MAX_EVAL_MIDDLE_MATE_VALUE {
-            let max_value = MAX_EVAL_MIDDLE_MATE_VALUE + (self.middle_depth as i32);
+            let max_value = MAX_EVAL_MIDDLE_MATE_VALUE + (middle_depth as i32);
             let start_move_count = 1;
             Some((max_value - value + start_move_count) as usize)
         } else if value >= MAX_EVAL_MATE_VALUE {
-            let max_value = MAX_EVAL_MATE_VALUE + ((depth - self.middle_depth - 1) as i32);
-            let start_move_count = (self.middle_depth as i32) + 1;
+            let max_value = MAX_EVAL_MATE_VALUE + ((depth - middle_depth - 1) as i32);
+            let start_move_count = (middle_depth as i32) + 1;
             Some((max_value - value + start_move_count) as usize)
         } else {
             None
@@ -136,7 +315,34 @@ impl Search for OneSearcher
     }
 
     fn min_depth(&self) -> usize
-    { 1 + self.middle_depth + 1 }
+    { 1 + self.middle_depth() + 1 }
+}
+
+impl SearchConfig for OneSearcher
+{
+    fn thread_count(&self) -> usize
+    { self.thread_count.load(Ordering::SeqCst) }
+
+    fn set_thread_count(&self, thread_count: usize)
+    { self.thread_count.store(thread_count.clamp(Self::MIN_THREAD_COUNT, Self::MAX_THREAD_COUNT), Ordering::SeqCst); }
+
+    fn middle_depth(&self) -> usize
+    { self.middle_depth.load(Ordering::SeqCst) }
+
+    fn set_middle_depth(&self, middle_depth: usize)
+    { self.middle_depth.store(middle_depth.clamp(Self::MIN_MIDDLE_DEPTH, Self::MAX_MIDDLE_DEPTH), Ordering::SeqCst); }
+
+    fn syzygy_probe_limit(&self) -> usize
+    { self.middle_searcher.syzygy_probe_limit() }
+
+    fn set_syzygy_probe_limit(&self, probe_limit: usize)
+    { self.middle_searcher.set_syzygy_probe_limit(probe_limit); }
+
+    fn syzygy_probe_depth(&self) -> usize
+    { self.middle_searcher.syzygy_probe_depth() }
+
+    fn set_syzygy_probe_depth(&self, probe_depth: usize)
+    { self.middle_searcher.set_syzygy_probe_depth(probe_depth); }
 }
 
 #[cfg(test)]
@@ -8,6 +8,7 @@
 use crate::matrix::Matrix;
 use crate::engine::neural_searcher::*;
 use crate::engine::simple_eval_fun::*;
+use crate::engine::test_neural_searcher::*;
 use crate::shared::converter::*;
 use crate::shared::index_converter::*;
 use crate::shared::network::*;
@@ -47,6 +48,7 @@ fn test_one_searcher_search_searches_without_panic()
     let eval_fun = Arc::new(SimpleEvalFun::new());
     let neural_searcher = Arc::new(NeuralSearcher::new(intr_checker, converter, network));
     let middle_searcher = MiddleSearcher::new(eval_fun, neural_searcher);
+    middle_searcher.set_pruning(false);
     let one_searcher = OneSearcher::new(middle_searcher, 2);
     let mut move_chain = MoveChain::new_initial();
     move_chain.push_uci_list("e2e4 e7e5").unwrap();
@@ -65,3 +67,46 @@ fn test_one_searcher_search_searches_without_panic()
         }
     }
 }
+
+#[test]
+fn test_one_searcher_search_visits_all_nodes_without_pruning()
+{
+    let middle_searcher = MiddleSearcher::new(Arc::new(SimpleEvalFun::new()), Arc::new(TestNeuralSearcher::new()));
+    middle_searcher.set_pruning(false);
+    let one_searcher = OneSearcher::new(middle_searcher, 2);
+    let mut move_chain = MoveChain::new_initial();
+    move_chain.push_uci_list("e2e4 e7e5").unwrap();
+    let (_, middle_node_count, _, _) = one_searcher.search(&mut move_chain, 3, &None).unwrap();
+    assert_eq!(51437, middle_node_count);
+}
+
+#[test]
+fn test_one_searcher_search_uses_fixed_value_for_move_at_depth_one()
+{
+    let middle_searcher = MiddleSearcher::new(Arc::new(SimpleEvalFun::new()), Arc::new(TestNeuralSearcher::new()));
+    let one_searcher = OneSearcher::new(middle_searcher, 1);
+    let mut move_chain = MoveChain::new_initial();
+    move_chain.push_uci_list("e2e4 e7e5").unwrap();
+    let (_, _, _, pv) = one_searcher.search(&mut move_chain, 3, &None).unwrap();
+    let mv = Move::from_uci("a2a3", move_chain.last()).unwrap();
+    assert_ne!(Some(&mv), pv.first());
+    let zobrist_hash = move_chain.last().make_move(mv).unwrap().zobrist_hash();
+    one_searcher.transposition_table().unwrap().set_fixed_value(zobrist_hash, -1000, 10);
+    let (value, _, _, pv) = one_searcher.search(&mut move_chain, 3, &None).unwrap();
+    assert_eq!(1000, value);
+    assert_eq!(Some(&mv), pv.first());
+}
+
+#[test]
+fn test_one_searcher_search_uses_fixed_value_for_root()
+{
+    let middle_searcher = MiddleSearcher::new(Arc::new(SimpleEvalFun::new()), Arc::new(TestNeuralSearcher::new()));
+    let one_searcher = OneSearcher::new(middle_searcher, 1);
+    let mut move_chain = MoveChain::new_initial();
+    move_chain.push_uci_list("e2e4 e7e5").unwrap();
+    let (_, _, _, pv) = one_searcher.search(&mut move_chain, 3, &None).unwrap();
+    one_searcher.transposition_table().unwrap().set_fixed_value(move_chain.last().zobrist_hash(), 500, 10);
+    let (value, _, _, pv2) = one_searcher.search(&mut move_chain, 3, &None).unwrap();
+    assert_eq!(500, value);
+    assert_eq!(pv.first(), pv2.first());
+}
@@ -11,22 +11,86 @@ use std::time::Duration;
 use crate::chess::Board;
 use crate::chess::Move;
 use crate::chess::Outcome;
+use crate::engine::wdl_model::*;
+
+/// A structure of line of principal variation.
+///
+/// The line of principal variation contains the depth, the value, the time, the nodes, the hits
+/// of the Syzygy endgame tablebases, and the principal variation.
+#[derive(Copy, Clone, Debug)]
+pub struct PvLine<'a>
+{
+    /// The searched depth.
+    pub depth: usize,
+    /// The value of principal variation.
+    pub value: i32,
+    /// The number of half moves to checkmate if the value is a mate value.
+    ///
+    /// This number is positive if the side to move checkmates, otherwise this number isn't
+    /// positive.
+    pub half_move_count_to_checkmate: Option<i32>,
+    /// The WDL statistics if they should be printed.
+    pub wdl: Option<WdlStats>,
+    /// The time from the start of search.
+    pub time: Duration,
+    /// The number of searched nodes.
+    pub node_count: u64,
+    /// The number of hits of the Syzygy endgame tablebases.
+    pub tb_hit_count: u64,
+    /// The principal variation.
+    pub pv: &'a [Move],
+    /// The number of principal variation counted from one for the best principal variation.
+    pub multi_pv: usize,
+}
+
+/// A structure of search progress.
+///
+/// The search progress contains the depth, the time, the nodes, the occupancy of transposition
+/// table, and the currently searched move while the depth is searched.
+#[derive(Copy, Clone, Debug)]
+pub struct SearchProgress
+{
+    /// The searched depth.
+    pub depth: usize,
+    /// The time from the start of search.
+    pub time: Duration,
+    /// The number of searched nodes.
+    pub node_count: u64,
+    /// The occupancy of transposition table in permille.
+    pub hashfull: Option<usize>,
+    /// The currently searched move at depth one with its number counted from one.
+    pub curr_move: Option<(Move, usize)>,
+}
 
 /// A printer trait.
 ///
-/// This trait provides methods which print a line of principal variation, a best move, and a game
-/// outcome.
+/// This trait provides methods which print a line of principal variation, a search progress, a
+/// best move, a game outcome, resignation, and a draw offer.
 pub trait Print
 {
-    /// Prints line of principal variation from the depth, the value, the time, the nodes, and
-    /// the principal variation.
-    fn print_pv(&self, w: &mut dyn Write, board: &Board, depth: usize, value: i32, time: Duration, node_count: u64, pv: &[Move]) -> Result<()>;
+    /// Prints the line of principal variation.
+    fn print_pv(&self, w: &mut dyn Write, board: &Board, line: &PvLine) -> Result<()>;
+
+    /// Prints the search progress.
+    ///
+    /// The search progress is printed periodically while a depth is searched.
+    fn print_progress(&self, w: &mut dyn Write, board: &Board, progress: &SearchProgress) -> Result<()>;
     
     /// Prints the best move.
-    fn print_best_move(&self, w: &mut dyn Write, board: &Board, mv: Move) -> Result<()>;
+    ///
+    /// The ponder move is an expected move of opponent after the best move.
+    fn print_best_move(&self, w: &mut dyn Write, board: &Board, mv: Move, ponder_move: Option<Move>) -> Result<()>;
     
     /// Prints the game outcome.
     fn print_outcome(&self, w: &mut dyn Write, outcome: Outcome) -> Result<()>;
+
+    /// Prints resignation.
+    fn print_resign(&self, w: &mut dyn Write) -> Result<()>;
+
+    /// Prints a draw offer.
+    ///
+    /// The draw offer is printed before the best move.
+    fn print_draw_offer(&self, w: &mut dyn Write) -> Result<()>;
 }
 
 /// A structure of empty printer.
@@ -44,12 +108,21 @@ impl EmptyPrinter
 
 impl Print for EmptyPrinter
 {
-    fn print_pv(&self, _w: &mut dyn Write, _board: &Board, _depth: usize, _value: i32, _time: Duration, _node_count: u64, _pv: &[Move]) -> Result<()>
+    fn print_pv(&self, _w: &mut dyn Write, _board: &Board, _line: &PvLine) -> Result<()>
+    { Ok(()) }
+
+    fn print_progress(&self, _w: &mut dyn Write, _board: &Board, _progress: &SearchProgress) -> Result<()>
     { Ok(()) }
     
-    fn print_best_move(&self, _w: &mut dyn Write, _board: &Board, _mv: Move) -> Result<()>
+    fn print_best_move(&self, _w: &mut dyn Write, _board: &Board, _mv: Move, _ponder_move: Option<Move>) -> Result<()>
     { Ok(()) }
     
     fn print_outcome(&self, _w: &mut dyn Write, _outcome: Outcome) -> Result<()>
     { Ok(()) }
+
+    fn print_resign(&self, _w: &mut dyn Write) -> Result<()>
+    { Ok(()) }
+
+    fn print_draw_offer(&self, _w: &mut dyn Write) -> Result<()>
+    { Ok(()) }
 }
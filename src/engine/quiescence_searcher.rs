@@ -0,0 +1,147 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::sync::Arc;
+use crate::chess::movegen::semilegal;
+use crate::chess::Board;
+use crate::chess::Move;
+use crate::chess::MoveKind;
+use crate::chess::MoveList;
+use crate::chess::Piece;
+use crate::engine::eval::*;
+use crate::shared::intr_check::*;
+use crate::shared::Interruption;
+
+const PIECE_GAIN_VALUES: [i32; 6] = [
+    100,    // Pawn
+    0,      // King
+    300,    // Knight
+    300,    // Bishop
+    600,    // Rook
+    1000    // Queen
+];
+
+/// A structure of quiescence searcher.
+///
+/// The quiescence searcher searches only captures and promotions until a board is quiet so that
+/// the board isn't evaluated in the middle of a capture sequence. The quiescence search uses a
+/// stand-pat value and a delta pruning. The number of nodes of one quiescence search is limited;
+/// after exceeding this limit, the quiescence searcher returns stand-pat values.
+#[derive(Clone)]
+pub struct QuiescenceSearcher
+{
+    eval_fun: Arc<dyn Eval + Send + Sync>,
+    intr_checker: Arc<dyn IntrCheck + Send + Sync>,
+    max_node_count: u64,
+}
+
+impl QuiescenceSearcher
+{
+    /// A number of nodes to check interruption.
+    pub const NODE_COUNT_TO_INTR_CHECK: u64 = 1024;
+
+    /// A default maximal number of nodes of one quiescence search.
+    pub const DEFAULT_MAX_NODE_COUNT: u64 = 1024;
+
+    /// A margin of delta pruning.
+    pub const DELTA_MARGIN: i32 = 200;
+
+    /// Creates a quiescence searcher.
+    pub fn new(eval_fun: Arc<dyn Eval + Send + Sync>, intr_checker: Arc<dyn IntrCheck + Send + Sync>) -> Self
+    { Self::new_with_max_node_count(eval_fun, intr_checker, Self::DEFAULT_MAX_NODE_COUNT) }
+
+    /// Creates a quiescence searcher with the maximal number of nodes of one quiescence search.
+    pub fn new_with_max_node_count(eval_fun: Arc<dyn Eval + Send + Sync>, intr_checker: Arc<dyn IntrCheck + Send + Sync>, max_node_count: u64) -> Self
+    { QuiescenceSearcher { eval_fun, intr_checker, max_node_count, } }
+
+    /// Returns the evaluation function.
+    pub fn eval_fun(&self) -> &Arc<dyn Eval + Send + Sync>
+    { &self.eval_fun }
+
+    /// Returns the interruption checker.
+    pub fn intr_checker(&self) -> &Arc<dyn IntrCheck + Send + Sync>
+    { &self.intr_checker }
+
+    /// Returns the maximal number of nodes of one quiescence search.
+    pub fn max_node_count(&self) -> u64
+    { self.max_node_count }
+
+    fn gain_value(board: &Board, mv: Move) -> i32
+    {
+        let victim_value = match mv.kind() {
+            MoveKind::Enpassant => PIECE_GAIN_VALUES[Piece::Pawn.index()],
+            _ => {
+                match board.get(mv.dst()).piece() {
+                    Some(piece) => PIECE_GAIN_VALUES[piece.index()],
+                    None => 0,
+                }
+            },
+        };
+        let promotion_value = match mv.kind() {
+            MoveKind::PromoteQueen => PIECE_GAIN_VALUES[Piece::Queen.index()] - PIECE_GAIN_VALUES[Piece::Pawn.index()],
+            MoveKind::PromoteRook => PIECE_GAIN_VALUES[Piece::Rook.index()] - PIECE_GAIN_VALUES[Piece::Pawn.index()],
+            MoveKind::PromoteBishop => PIECE_GAIN_VALUES[Piece::Bishop.index()] - PIECE_GAIN_VALUES[Piece::Pawn.index()],
+            MoveKind::PromoteKnight => PIECE_GAIN_VALUES[Piece::Knight.index()] - PIECE_GAIN_VALUES[Piece::Pawn.index()],
+            _ => 0,
+        };
+        victim_value + promotion_value
+    }
+
+    fn attacker_value(mv: Move) -> i32
+    {
+        match mv.src_cell().piece() {
+            Some(piece) => PIECE_GAIN_VALUES[piece.index()],
+            None => 0,
+        }
+    }
+
+    fn quiescence(&self, board: &Board, alpha: i32, beta: i32, node_count: &mut u64, search_node_count: &mut u64) -> Result<i32, Interruption>
+    {
+        if *node_count % Self::NODE_COUNT_TO_INTR_CHECK == 0 {
+            self.intr_checker.check()?;
+        }
+        *node_count += 1;
+        *search_node_count += 1;
+        let stand_pat = self.eval_fun.evaluate(board);
+        if stand_pat >= beta || *search_node_count >= self.max_node_count {
+            return Ok(stand_pat);
+        }
+        let mut tmp_alpha = alpha.max(stand_pat);
+        let mut moves: MoveList = MoveList::new();
+        semilegal::gen_capture_into(board, &mut moves);
+        semilegal::gen_simple_promote_into(board, &mut moves);
+        moves.sort_by_cached_key(|mv| -(Self::gain_value(board, *mv) * 16 - Self::attacker_value(*mv)));
+        for mv in &moves {
+            if stand_pat + Self::gain_value(board, *mv) + Self::DELTA_MARGIN <= tmp_alpha {
+                continue;
+            }
+            if let Ok(new_board) = board.make_move(*mv) {
+                let value = -self.quiescence(&new_board, -beta, -tmp_alpha, node_count, search_node_count)?;
+                if value > tmp_alpha {
+                    tmp_alpha = value;
+                }
+                if tmp_alpha >= beta {
+                    break;
+                }
+            }
+        }
+        Ok(tmp_alpha)
+    }
+
+    /// Searches captures and promotions from the board and returns a value of the board.
+    ///
+    /// The number of nodes is increased by the number of nodes of the quiescence search and is
+    /// used to check interruption.
+    pub fn search(&self, board: &Board, node_count: &mut u64) -> Result<i32, Interruption>
+    {
+        let mut search_node_count = 0u64;
+        self.quiescence(board, MIN_EVAL_VALUE, MAX_EVAL_VALUE, node_count, &mut search_node_count)
+    }
+}
+
+#[cfg(test)]
+mod tests;
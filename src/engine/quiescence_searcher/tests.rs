@@ -0,0 +1,46 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::engine::simple_eval_fun::*;
+use super::*;
+
+#[test]
+fn test_quiescence_searcher_search_returns_stand_pat_value_for_quiet_board()
+{
+    let eval_fun = Arc::new(SimpleEvalFun::new());
+    let quiescence_searcher = QuiescenceSearcher::new(eval_fun.clone(), Arc::new(EmptyIntrChecker::new()));
+    let board = Board::from_fen("4k3/pppp4/8/8/8/8/PPPP4/4K3 w - - 0 1").unwrap();
+    let mut node_count = 0u64;
+    assert_eq!(eval_fun.evaluate(&board), quiescence_searcher.search(&board, &mut node_count).unwrap());
+    assert_eq!(1, node_count);
+}
+
+#[test]
+fn test_quiescence_searcher_search_searches_captures()
+{
+    let eval_fun = Arc::new(SimpleEvalFun::new());
+    let quiescence_searcher = QuiescenceSearcher::new(eval_fun.clone(), Arc::new(EmptyIntrChecker::new()));
+    let board = Board::from_fen("4k3/8/8/3p4/8/8/8/3QK3 b - - 0 1").unwrap();
+    let mut node_count = 0u64;
+    assert_eq!(eval_fun.evaluate(&board), quiescence_searcher.search(&board, &mut node_count).unwrap());
+    let board2 = Board::from_fen("4k3/8/8/3q4/8/8/8/3QK3 w - - 0 1").unwrap();
+    let board3 = board2.make_move(Move::from_uci("d1d5", &board2).unwrap()).unwrap();
+    let mut node_count2 = 0u64;
+    assert_eq!(-eval_fun.evaluate(&board3), quiescence_searcher.search(&board2, &mut node_count2).unwrap());
+    assert!(node_count2 > 1);
+}
+
+#[test]
+fn test_quiescence_searcher_search_returns_stand_pat_value_after_exceeding_node_limit()
+{
+    let eval_fun = Arc::new(SimpleEvalFun::new());
+    let quiescence_searcher = QuiescenceSearcher::new_with_max_node_count(eval_fun.clone(), Arc::new(EmptyIntrChecker::new()), 1);
+    let board = Board::from_fen("4k3/8/8/3q4/8/8/8/3QK3 w - - 0 1").unwrap();
+    let mut node_count = 0u64;
+    assert_eq!(eval_fun.evaluate(&board), quiescence_searcher.search(&board, &mut node_count).unwrap());
+    assert_eq!(1, node_count);
+}
@@ -5,6 +5,8 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 //
+use std::sync::atomic::AtomicI32;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use rand::random_range;
 use crate::chess::Board;
@@ -12,23 +14,41 @@ use crate::engine::eval::*;
 
 /// A structure of random evaluation function.
 ///
-/// The random evaluation function adds a random component to a value of evaluation function.
-#[derive(Clone)]
+/// The random evaluation function adds a random component to a value of evaluation function. The
+/// range of random component can be changed when the random evaluation function is used.
 pub struct RandomEvalFun
 {
     eval_fun: Arc<dyn Eval + Send + Sync>,
-    range: i32,
+    range: AtomicI32,
 }
 
 impl RandomEvalFun
 {
+    /// A default range of random component.
+    pub const DEFAULT_RANGE: i32 = 5;
+
+    /// A minimal range of random component.
+    pub const MIN_RANGE: i32 = 0;
+
+    /// A maximal range of random component.
+    pub const MAX_RANGE: i32 = 1000;
+    
     /// Creates a random evaluation function.
     pub fn new(eval_fun: Arc<dyn Eval + Send + Sync>, range: i32) -> Self
-    { RandomEvalFun { eval_fun, range, } }
+    { RandomEvalFun { eval_fun, range: AtomicI32::new(range.clamp(Self::MIN_RANGE, Self::MAX_RANGE)), } }
 }
 
 impl Eval for RandomEvalFun
 {
     fn evaluate(&self, board: &Board) -> i32
-    { self.eval_fun.evaluate(board) + random_range(-self.range..=self.range) }
+    {
+        let range = self.random_range();
+        self.eval_fun.evaluate(board) + random_range(-range..=range)
+    }
+
+    fn random_range(&self) -> i32
+    { self.range.load(Ordering::SeqCst) }
+
+    fn set_random_range(&self, range: i32)
+    { self.range.store(range.clamp(Self::MIN_RANGE, Self::MAX_RANGE), Ordering::SeqCst); }
 }
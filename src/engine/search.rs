@@ -6,24 +6,90 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 //
 use std::sync::Arc;
+use crate::chess::Board;
 use crate::chess::Move;
 use crate::chess::MoveChain;
+use crate::engine::eval::*;
+use crate::engine::neural_search::*;
+use crate::engine::transposition_table::*;
 use crate::shared::intr_check::*;
 use crate::shared::Interruption;
 
+/// A type of search lines.
+///
+/// The search lines are search values with principal variations.
+pub type SearchLines = Vec<(i32, Vec<Move>)>;
+
 /// A search trait.
 ///
-/// This trait provides method that searches a game tree and other methods.
+/// This trait provides methods that search a game tree and other methods.
 pub trait Search
 {
     /// Returns the interruption checker.
     fn intr_checker(&self) -> &Arc<dyn IntrCheck + Send + Sync>;
+
+    /// Returns the transposition table if the searcher uses it.
+    fn transposition_table(&self) -> Option<&Arc<TranspositionTable>>;
+
+    /// Resizes the memory of searcher to the size in megabytes.
+    ///
+    /// The default implementation resizes the transposition table if the searcher uses it.
+    fn resize(&self, size: usize)
+    {
+        if let Some(transposition_table) = self.transposition_table() {
+            transposition_table.resize(size);
+        }
+    }
+
+    /// Returns `true` if the memory of searcher is full so that a deeper search doesn't change
+    /// the result, otherwise `false`.
+    fn is_full(&self) -> bool
+    { false }
+
+    /// Returns the configuration of searcher if the searcher can be configured.
+    ///
+    /// The searcher that doesn't support the number of threads, the depth of middle search, and
+    /// the limits of probing the Syzygy endgame tablebases returns `None`.
+    fn config(&self) -> Option<&dyn SearchConfig>
+    { None }
+
+    /// Returns the number of hits of the Syzygy endgame tablebases.
+    fn tb_hit_count(&self) -> u64;
+
+    /// Resets the number of hits of the Syzygy endgame tablebases.
+    fn clear_tb_hit_count(&self);
+
+    /// Returns the currently searched move at depth one with its number counted from one.
+    ///
+    /// The searcher can return `None` if it doesn't search the moves at depth one one by one.
+    fn curr_move(&self) -> Option<(Move, usize)>;
+
+    /// Returns the number of nodes that are searched so far by the current search.
+    fn searched_node_count(&self) -> u64;
+
+    /// Returns the evaluation function if the searcher uses it.
+    fn eval_fun(&self) -> Option<&Arc<dyn Eval + Send + Sync>>;
+
+    /// Returns the neural searcher if the searcher uses it.
+    fn neural_searcher(&self) -> Option<&Arc<dyn NeuralSearch + Send + Sync>>;
+
+    /// Returns the legal moves with their probabilities for the board.
+    ///
+    /// The probabilities are computed by the neural network of searcher without interruptions.
+    fn move_probs(&self, board: &Board) -> Vec<(Move, f32)>;
     
     /// Searches a game tree.
     ///
     /// The search moves are moves from which the search begins. This method returns a value, a
     /// number of nodes of middle search, a number of nodes, and a principal variation.
     fn search(&self, move_chain: &mut MoveChain, depth: usize, search_moves: &Option<Vec<Move>>) -> Result<(i32, u64, u64, Vec<Move>), Interruption>;
+
+    /// Searches a game tree for many principal variations.
+    ///
+    /// The search moves are moves from which the search begins. This method returns at most the
+    /// specified number of values with principal variations that are sorted from the best value,
+    /// a number of nodes of middle search, and a number of nodes.
+    fn search_multi_pv(&self, move_chain: &mut MoveChain, depth: usize, search_moves: &Option<Vec<Move>>, multi_pv: usize) -> Result<(SearchLines, u64, u64), Interruption>;
     
     /// Calculates a number of moves to checkmate.
     ///
@@ -33,3 +99,34 @@ pub trait Search
     /// Returns a minimal depth that can be used in search.
     fn min_depth(&self) -> usize;
 }
+
+/// A trait of search configuration.
+///
+/// This trait provides methods that get and set parameters of search that aren't supported by all
+/// searchers.
+pub trait SearchConfig
+{
+    /// Returns the number of threads that are used in search.
+    fn thread_count(&self) -> usize;
+
+    /// Sets the number of threads that are used in search.
+    fn set_thread_count(&self, thread_count: usize);
+
+    /// Returns the depth of middle search.
+    fn middle_depth(&self) -> usize;
+
+    /// Sets the depth of middle search.
+    fn set_middle_depth(&self, middle_depth: usize);
+
+    /// Returns the limit of number of pieces for probing the Syzygy endgame tablebases.
+    fn syzygy_probe_limit(&self) -> usize;
+
+    /// Sets the limit of number of pieces for probing the Syzygy endgame tablebases.
+    fn set_syzygy_probe_limit(&self, probe_limit: usize);
+
+    /// Returns the depth limit of middle search for probing the Syzygy endgame tablebases.
+    fn syzygy_probe_depth(&self) -> usize;
+
+    /// Sets the depth limit of middle search for probing the Syzygy endgame tablebases.
+    fn set_syzygy_probe_depth(&self, probe_depth: usize);
+}
@@ -156,4 +156,10 @@ impl Eval for SimpleEvalFun
             Color::Black => -value,
         }
     }
+
+    fn random_range(&self) -> i32
+    { 0 }
+
+    fn set_random_range(&self, _range: i32)
+    {}
 }
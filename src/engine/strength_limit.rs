@@ -0,0 +1,76 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::engine::one_searcher::*;
+use crate::engine::random_eval_fun::*;
+
+/// A maximal number of searched nodes for the minimal Elo rating.
+const MIN_ELO_MAX_NODE_COUNT: u64 = 100;
+
+/// A maximal number of searched nodes for the maximal Elo rating.
+const MAX_ELO_MAX_NODE_COUNT: u64 = 1000000;
+
+/// A range of random component of evaluation function for the minimal Elo rating.
+const MIN_ELO_EVAL_RANDOM_RANGE: i32 = 300;
+
+/// A move temperature for the minimal Elo rating.
+const MIN_ELO_MOVE_TEMPERATURE: f32 = 1.5;
+
+/// A structure of strength limit.
+///
+/// The strength limit weakens the engine to an Elo rating. The Elo rating is mapped to a middle
+/// depth, a maximal number of searched nodes, a range of random component of evaluation function,
+/// and a move temperature of neural searcher. These parameters are interpolated between the
+/// weakest parameters for the minimal Elo rating and the default parameters for the maximal Elo
+/// rating. The maximal number of searched nodes is interpolated logarithmically.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StrengthLimit
+{
+    /// An Elo rating.
+    pub elo: u32,
+    /// A middle depth.
+    pub middle_depth: usize,
+    /// A maximal number of searched nodes.
+    pub max_node_count: u64,
+    /// A range of random component of evaluation function.
+    pub eval_random_range: i32,
+    /// A move temperature of neural searcher.
+    pub move_temperature: f32,
+}
+
+impl StrengthLimit
+{
+    /// A default Elo rating.
+    pub const DEFAULT_ELO: u32 = 1500;
+
+    /// A minimal Elo rating.
+    pub const MIN_ELO: u32 = 800;
+
+    /// A maximal Elo rating.
+    pub const MAX_ELO: u32 = 2500;
+
+    /// Creates a strength limit for the Elo rating.
+    pub fn new(elo: u32) -> Self
+    {
+        let elo = elo.clamp(Self::MIN_ELO, Self::MAX_ELO);
+        let t = ((elo - Self::MIN_ELO) as f64) / ((Self::MAX_ELO - Self::MIN_ELO) as f64);
+        let middle_depth = OneSearcher::MIN_MIDDLE_DEPTH + (((OneSearcher::DEFAULT_MIDDLE_DEPTH - OneSearcher::MIN_MIDDLE_DEPTH) as f64) * t).round() as usize;
+        let max_node_count = ((MIN_ELO_MAX_NODE_COUNT as f64) * ((MAX_ELO_MAX_NODE_COUNT as f64) / (MIN_ELO_MAX_NODE_COUNT as f64)).powf(t)).round() as u64;
+        let eval_random_range = ((MIN_ELO_EVAL_RANDOM_RANGE as f64) + (((RandomEvalFun::DEFAULT_RANGE - MIN_ELO_EVAL_RANDOM_RANGE) as f64) * t)).round() as i32;
+        let move_temperature = ((MIN_ELO_MOVE_TEMPERATURE as f64) * (1.0 - t)) as f32;
+        StrengthLimit {
+            elo,
+            middle_depth,
+            max_node_count,
+            eval_random_range,
+            move_temperature,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
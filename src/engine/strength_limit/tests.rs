@@ -0,0 +1,48 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use super::*;
+
+#[test]
+fn test_strength_limit_new_creates_weakest_strength_limit_for_minimal_elo()
+{
+    let strength_limit = StrengthLimit::new(StrengthLimit::MIN_ELO);
+    assert_eq!(StrengthLimit::MIN_ELO, strength_limit.elo);
+    assert_eq!(OneSearcher::MIN_MIDDLE_DEPTH, strength_limit.middle_depth);
+    assert_eq!(MIN_ELO_MAX_NODE_COUNT, strength_limit.max_node_count);
+    assert_eq!(MIN_ELO_EVAL_RANDOM_RANGE, strength_limit.eval_random_range);
+    assert_eq!(MIN_ELO_MOVE_TEMPERATURE, strength_limit.move_temperature);
+}
+
+#[test]
+fn test_strength_limit_new_creates_default_parameters_for_maximal_elo()
+{
+    let strength_limit = StrengthLimit::new(StrengthLimit::MAX_ELO);
+    assert_eq!(StrengthLimit::MAX_ELO, strength_limit.elo);
+    assert_eq!(OneSearcher::DEFAULT_MIDDLE_DEPTH, strength_limit.middle_depth);
+    assert_eq!(MAX_ELO_MAX_NODE_COUNT, strength_limit.max_node_count);
+    assert_eq!(RandomEvalFun::DEFAULT_RANGE, strength_limit.eval_random_range);
+    assert_eq!(0.0, strength_limit.move_temperature);
+}
+
+#[test]
+fn test_strength_limit_new_creates_stronger_strength_limit_for_greater_elo()
+{
+    let strength_limit = StrengthLimit::new(1200);
+    let strength_limit2 = StrengthLimit::new(2000);
+    assert!(strength_limit.middle_depth <= strength_limit2.middle_depth);
+    assert!(strength_limit.max_node_count < strength_limit2.max_node_count);
+    assert!(strength_limit.eval_random_range > strength_limit2.eval_random_range);
+    assert!(strength_limit.move_temperature > strength_limit2.move_temperature);
+}
+
+#[test]
+fn test_strength_limit_new_clamps_elo()
+{
+    assert_eq!(StrengthLimit::new(StrengthLimit::MIN_ELO), StrengthLimit::new(0));
+    assert_eq!(StrengthLimit::new(StrengthLimit::MAX_ELO), StrengthLimit::new(4000));
+}
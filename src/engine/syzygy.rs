@@ -14,6 +14,7 @@ use crate::chess::Color;
 use crate::chess::Coord;
 use crate::chess::Move;
 use crate::chess::Piece;
+use crate::engine::draw::*;
 use crate::engine::fathom;
 
 fn squ_to_fathom_squ(squ: usize) -> usize
@@ -123,6 +124,9 @@ pub struct Syzygy
 
 impl Syzygy
 {
+    /// A maximal length of principal variation that is probed in the Syzygy endgame tablebases.
+    pub const MAX_PV_LEN: usize = 128;
+
     /// Creates an object of Syzygy endgame tablebases and loads the Syzygy endgame tablebases to
     /// the object.
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Syzygy, fathom::Error>
@@ -132,14 +136,73 @@ impl Syzygy
     pub fn reload<P: AsRef<Path>>(self, path: P) -> Result<Syzygy, fathom::Error>
     { Ok(Syzygy { fathom: self.fathom.reload(path)?, }) }
     
+    /// Returns the maximal number of pieces of the Syzygy endgame tablebases.
+    pub fn max_piece_count(&self) -> usize
+    { self.fathom.max_pieces() as usize }
+
     /// Probes whether the board with a best move are in the Syzygy endgame tablebases.
     pub fn probe(&mut self, board: &Board) -> Option<Move>
+    { self.probe_root(board).map(|(mv, _, _)| mv) }
+
+    /// Probes whether the board with a best move are in the Syzygy endgame tablebases and returns
+    /// the best move with a result and a DTZ.
+    ///
+    /// The result is a win, a draw, or a loss for the side to move. The DTZ is a number of half
+    /// moves to a zeroing of the fifty-move counter by a capture or a pawn move.
+    pub fn probe_root(&mut self, board: &Board) -> Option<(Move, fathom::Wdl, u16)>
     {
+        if !board.has_legal_moves() {
+            return None;
+        }
         let (mut root_probe, _) = self.fathom.get_probers();
         let pos = board_to_fathom_position(board);
         match root_probe.probe(&pos) {
-            Some(res) => fathom_move_to_move(board, res.best_move),
+            Some(res) => fathom_move_to_move(board, res.best_move).map(|mv| (mv, res.wdl, res.dtz)),
             None => None,
         }
     }
+
+    /// Probes a principal variation of the board in the Syzygy endgame tablebases.
+    ///
+    /// The principal variation is built by repeatedly probing best moves until a board isn't in
+    /// the Syzygy endgame tablebases, a game ends, or the principal variation has the maximal
+    /// length. This method returns a result and a DTZ of the board with the principal variation.
+    pub fn probe_pv(&mut self, board: &Board) -> Option<(fathom::Wdl, u16, Vec<Move>)>
+    {
+        let (mv, wdl, dtz) = self.probe_root(board)?;
+        let mut pv = vec![mv];
+        let mut history: Vec<u64> = vec![board.zobrist_hash()];
+        let mut tmp_board = board.make_move(mv).ok()?;
+        while pv.len() < Self::MAX_PV_LEN && !is_draw_by_history(&tmp_board, history.as_slice()) {
+            match self.probe_root(&tmp_board) {
+                Some((tmp_mv, _, _)) => {
+                    match tmp_board.make_move(tmp_mv) {
+                        Ok(tmp_new_board) => {
+                            pv.push(tmp_mv);
+                            history.push(tmp_board.zobrist_hash());
+                            tmp_board = tmp_new_board;
+                        },
+                        Err(_) => break,
+                    }
+                },
+                None => break,
+            }
+        }
+        Some((wdl, dtz, pv))
+    }
+
+    /// Probes a result of the board in the Syzygy endgame tablebases.
+    ///
+    /// The result is a win, a draw, or a loss for the side to move. This method returns `None` if
+    /// the board isn't in the Syzygy endgame tablebases or the board is a checkmate or a
+    /// stalemate.
+    pub fn probe_wdl(&mut self, board: &Board) -> Option<fathom::Wdl>
+    {
+        if !board.has_legal_moves() {
+            return None;
+        }
+        let (_, probe) = self.fathom.get_probers();
+        let pos = board_to_fathom_position(board);
+        probe.probe(&pos)
+    }
 }
@@ -0,0 +1,54 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::sync::Arc;
+use crate::chess::Board;
+use crate::chess::Move;
+use crate::engine::neural_search::*;
+use crate::shared::intr_check::*;
+use crate::shared::Interruption;
+
+/// A structure of test neural searcher.
+///
+/// The test neural searcher doesn't change principal variations and doesn't return move
+/// probabilities. This neural searcher is used by tests of searchers that don't need a neural
+/// network.
+pub(crate) struct TestNeuralSearcher
+{
+    intr_checker: Arc<dyn IntrCheck + Send + Sync>,
+}
+
+impl TestNeuralSearcher
+{
+    /// Creates a test neural searcher.
+    pub(crate) fn new() -> Self
+    { TestNeuralSearcher { intr_checker: Arc::new(EmptyIntrChecker::new()), } }
+}
+
+impl NeuralSearch for TestNeuralSearcher
+{
+    fn intr_checker(&self) -> &Arc<dyn IntrCheck + Send + Sync>
+    { &self.intr_checker }
+
+    fn search(&self, _board: &Board, _pvs: &mut [Vec<Move>], _depth: usize) -> Result<(), Interruption>
+    { Ok(()) }
+
+    fn move_probs(&self, _board: &Board) -> Vec<(Move, f32)>
+    { Vec::new() }
+
+    fn move_eps(&self) -> f32
+    { 0.0 }
+
+    fn set_move_eps(&self, _move_eps: f32)
+    {}
+
+    fn move_temperature(&self) -> f32
+    { 0.0 }
+
+    fn set_move_temperature(&self, _move_temperature: f32)
+    {}
+}
@@ -7,51 +7,257 @@
 //
 use std::io::Result;
 use std::io::Write;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Condvar;
 use std::sync::Mutex;
+use std::thread::scope;
 use std::time::Duration;
 use std::time::Instant;
 use crate::chess::types::OutcomeFilter;
+use crate::chess::Board;
 use crate::chess::Move;
 use crate::chess::MoveChain;
+use crate::engine::adjudicator::*;
+use crate::engine::book::*;
 use crate::engine::eval::*;
+use crate::engine::fathom;
+use crate::engine::neural_search::*;
 use crate::engine::print::*;
+use crate::engine::random_eval_fun::*;
 use crate::engine::search::*;
+use crate::engine::strength_limit::*;
 use crate::engine::syzygy::*;
+use crate::engine::transposition_table::*;
+use crate::engine::wdl_model::*;
 use crate::shared::intr_check::*;
 
+/// An interval of printing a search progress.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A factor of extension of the soft timeout for one change of best move.
+const BEST_MOVE_CHANGE_EXTENSION: f64 = 0.5;
+
+/// A maximal ratio of the extended soft timeout to the soft timeout.
+const MAX_SOFT_TIMEOUT_RATIO: f64 = 2.0;
+
+/// A minimal difference between values of the best move and the second move for that the best
+/// move dominates.
+const DOMINANT_VALUE_MARGIN: i32 = 300;
+
+/// A minimal number of iterations without a change of dominant best move.
+const DOMINANT_ITERATION_COUNT: usize = 3;
+
+/// A divisor of the soft timeout for the time after that search of dominant best move is stopped.
+const DOMINANT_SOFT_TIMEOUT_DIVISOR: u32 = 4;
+
+/// Returns a number of half-moves to checkmate for the principal variation from the Syzygy endgame
+/// tablebases if the principal variation ends with checkmate.
+fn tb_half_move_count_to_checkmate(board: &Board, wdl: fathom::Wdl, pv: &[Move]) -> Option<i32>
+{
+    let mut tmp_board = board.clone();
+    for mv in pv {
+        tmp_board = tmp_board.make_move(*mv).ok()?;
+    }
+    if !tmp_board.has_legal_moves() && tmp_board.is_check() {
+        match wdl {
+            fathom::Wdl::Win => Some(pv.len() as i32),
+            fathom::Wdl::Loss => Some(-(pv.len() as i32)),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+struct PonderData
+{
+    is_pondering: bool,
+    is_infinite: bool,
+    pondered_move: Option<(Board, Move)>,
+}
+
+struct StrengthData
+{
+    elo: u32,
+    unlimited_params: Option<(Option<usize>, i32, f32)>,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct SearchLimits
+{
+    max_depth: Option<usize>,
+    max_node_count: Option<u64>,
+    move_count_to_checkmate: Option<usize>,
+}
+
+/// A structure of thinking parameters.
+///
+/// The thinking parameters are passed to the [`Thinker::think`] method and determine limitations
+/// of an iterative search and actions of the thinker after the iterative search.
+#[derive(Clone, Debug)]
+pub struct ThinkingParams
+{
+    /// Moves from which a search begins.
+    pub search_moves: Option<Vec<Move>>,
+    /// A maximal depth.
+    pub depth: Option<usize>,
+    /// A maximal number of searched nodes.
+    pub node_count: Option<u64>,
+    /// A number of moves to checkmate.
+    pub move_count_to_checkmate: Option<usize>,
+    /// A start time of thinking.
+    pub now: Instant,
+    /// A soft timeout.
+    pub soft_timeout: Option<Duration>,
+    /// A hard timeout.
+    pub timeout: Option<Duration>,
+    /// A ponder flag.
+    pub is_ponder: bool,
+    /// A flag of making a best move.
+    pub can_make_best_move: bool,
+    /// A flag of pondering after a best move.
+    pub can_ponder: bool,
+    /// A flag of printing a principal variation.
+    pub can_print_pv: bool,
+    /// A flag of printing a best move and an outcome.
+    pub can_print_best_move_and_outcome: bool,
+}
+
+impl ThinkingParams
+{
+    /// Creates thinking parameters.
+    ///
+    /// The thinking parameters don't have limitations and flags and have the current start time.
+    pub fn new() -> Self
+    {
+        ThinkingParams {
+            search_moves: None,
+            depth: None,
+            node_count: None,
+            move_count_to_checkmate: None,
+            now: Instant::now(),
+            soft_timeout: None,
+            timeout: None,
+            is_ponder: false,
+            can_make_best_move: false,
+            can_ponder: false,
+            can_print_pv: false,
+            can_print_best_move_and_outcome: false,
+        }
+    }
+}
+
+impl Default for ThinkingParams
+{
+    fn default() -> Self
+    { Self::new() }
+}
+
 /// A thinker structure.
 ///
-/// The thinker iteratively searches a game tree.
+/// The thinker iteratively searches a game tree. The thinker also can ponder, that is, search a
+/// game tree on the opponent's time. Before a search, the thinker looks for a move in the opening
+/// book and then in the Syzygy endgame tablebases. An iterative search is aborted after a hard
+/// timeout and a new iteration isn't started after a soft timeout. The soft timeout is extended if
+/// a best move changes between iterations. The iterative search is finished before the soft timeout
+/// if the board has one legal move or the best move dominates over other moves. The thinker can be
+/// weakened to an Elo rating by a strength limit. While the thinker makes best moves, the thinker
+/// resigns or offers a draw from search values by an adjudicator.
 pub struct Thinker
 {
-    searcher: Arc<dyn Search + Send + Sync>,
+    searcher: Mutex<Arc<dyn Search + Send + Sync>>,
     writer: Arc<Mutex<dyn Write + Send + Sync>>,
     printer: Arc<dyn Print + Send + Sync>,
     syzygy: Arc<Mutex<Option<Syzygy>>>,
+    book: Arc<Mutex<Option<Book>>>,
+    book_max_depth: AtomicUsize,
+    has_book_best_move: AtomicBool,
+    wdl_model: Mutex<WdlModel>,
+    has_wdl: AtomicBool,
+    strength_data: Mutex<StrengthData>,
+    multi_pv: AtomicUsize,
+    soft_timeout: Mutex<Option<(Instant, Duration)>>,
     is_stopped: Mutex<bool>,
     condvar: Condvar,
+    ponder_data: Mutex<PonderData>,
+    ponder_condvar: Condvar,
+    hint_move: Mutex<Option<(Board, Move)>>,
+    adjudicator: Mutex<Adjudicator>,
 }
 
 impl Thinker
 {
+    /// A minimal number of principal variations.
+    pub const MIN_MULTI_PV: usize = 1;
+
+    /// A maximal number of principal variations.
+    pub const MAX_MULTI_PV: usize = 256;
+
     /// Creates a thinker.
+    ///
+    /// The thinker doesn't have an opening book.
     pub fn new(searcher: Arc<dyn Search + Send + Sync>, writer: Arc<Mutex<dyn Write + Send + Sync>>, printer: Arc<dyn Print  + Send + Sync>, syzygy: Arc<Mutex<Option<Syzygy>>>) -> Self
+    { Self::new_with_book(searcher, writer, printer, syzygy, Arc::new(Mutex::new(None))) }
+
+    /// Creates a thinker with the opening book.
+    pub fn new_with_book(searcher: Arc<dyn Search + Send + Sync>, writer: Arc<Mutex<dyn Write + Send + Sync>>, printer: Arc<dyn Print  + Send + Sync>, syzygy: Arc<Mutex<Option<Syzygy>>>, book: Arc<Mutex<Option<Book>>>) -> Self
     {
         Thinker {
-            searcher,
+            searcher: Mutex::new(searcher),
             writer,
             printer,
             syzygy,
+            book,
+            book_max_depth: AtomicUsize::new(Book::DEFAULT_MAX_DEPTH),
+            has_book_best_move: AtomicBool::new(false),
+            wdl_model: Mutex::new(WdlModel::new(WdlModel::DEFAULT_A, WdlModel::DEFAULT_B)),
+            has_wdl: AtomicBool::new(false),
+            strength_data: Mutex::new(StrengthData { elo: StrengthLimit::DEFAULT_ELO, unlimited_params: None, }),
+            multi_pv: AtomicUsize::new(1),
+            soft_timeout: Mutex::new(None),
             is_stopped: Mutex::new(true),
             condvar: Condvar::new(),
+            ponder_data: Mutex::new(PonderData { is_pondering: false, is_infinite: false, pondered_move: None, }),
+            ponder_condvar: Condvar::new(),
+            hint_move: Mutex::new(None),
+            adjudicator: Mutex::new(Adjudicator::new()),
         }
     }
 
     /// Returns the searcher.
-    pub fn searcher(&self) -> &Arc<dyn Search + Send + Sync>
-    { &self.searcher }
+    pub fn searcher(&self) -> Arc<dyn Search + Send + Sync>
+    {
+        let searcher_g = self.searcher.lock().unwrap();
+        searcher_g.clone()
+    }
+
+    /// Sets the searcher.
+    ///
+    /// The new searcher takes over the number of threads, the depth of middle search, the limits
+    /// of probing the Syzygy endgame tablebases, the range of random component of evaluation
+    /// function, the move epsilon, and the move temperature from the old searcher. This method
+    /// should be called when the thinker doesn't think.
+    pub fn set_searcher(&self, searcher: Arc<dyn Search + Send + Sync>)
+    {
+        let mut searcher_g = self.searcher.lock().unwrap();
+        if let (Some(config), Some(old_config)) = (searcher.config(), searcher_g.config()) {
+            config.set_thread_count(old_config.thread_count());
+            config.set_middle_depth(old_config.middle_depth());
+            config.set_syzygy_probe_limit(old_config.syzygy_probe_limit());
+            config.set_syzygy_probe_depth(old_config.syzygy_probe_depth());
+        }
+        if let (Some(eval_fun), Some(old_eval_fun)) = (searcher.eval_fun(), searcher_g.eval_fun()) {
+            eval_fun.set_random_range(old_eval_fun.random_range());
+        }
+        if let (Some(neural_searcher), Some(old_neural_searcher)) = (searcher.neural_searcher(), searcher_g.neural_searcher()) {
+            neural_searcher.set_move_eps(old_neural_searcher.move_eps());
+            neural_searcher.set_move_temperature(old_neural_searcher.move_temperature());
+        }
+        *searcher_g = searcher;
+    }
     
     /// Returns the writer.
     pub fn writer(&self) -> &Arc<Mutex<dyn Write + Send + Sync>>
@@ -65,9 +271,159 @@ impl Thinker
     pub fn syzygy(&self) -> &Arc<Mutex<Option<Syzygy>>>
     { &self.syzygy }
 
+    /// Returns the opening book.
+    pub fn book(&self) -> &Arc<Mutex<Option<Book>>>
+    { &self.book }
+
+    /// Returns the maximal depth of opening book in half moves.
+    pub fn book_max_depth(&self) -> usize
+    { self.book_max_depth.load(Ordering::SeqCst) }
+
+    /// Sets the maximal depth of opening book in half moves.
+    ///
+    /// The opening book is only used for the boards which are preceded by fewer half moves than
+    /// the maximal depth of opening book.
+    pub fn set_book_max_depth(&self, max_depth: usize)
+    { self.book_max_depth.store(max_depth.clamp(Book::MIN_MAX_DEPTH, Book::MAX_MAX_DEPTH), Ordering::SeqCst); }
+
+    /// Returns `true` if the thinker selects the book move with the greatest weight, otherwise
+    /// `false`.
+    pub fn has_book_best_move(&self) -> bool
+    { self.has_book_best_move.load(Ordering::SeqCst) }
+
+    /// Sets the flag of selection of book move with the greatest weight.
+    ///
+    /// If this flag is `false`, the thinker randomly selects a book move with a probability that
+    /// is proportional to a weight of book move.
+    pub fn set_book_best_move(&self, has_book_best_move: bool)
+    { self.has_book_best_move.store(has_book_best_move, Ordering::SeqCst); }
+
+    /// Returns the WDL model.
+    pub fn wdl_model(&self) -> WdlModel
+    {
+        let wdl_model_g = self.wdl_model.lock().unwrap();
+        *wdl_model_g
+    }
+
+    /// Sets the WDL model.
+    pub fn set_wdl_model(&self, wdl_model: WdlModel)
+    {
+        let mut wdl_model_g = self.wdl_model.lock().unwrap();
+        *wdl_model_g = wdl_model;
+    }
+
+    /// Returns `true` if principal variations are printed with WDL statistics, otherwise `false`.
+    pub fn has_wdl(&self) -> bool
+    { self.has_wdl.load(Ordering::SeqCst) }
+
+    /// Sets the WDL flag.
+    ///
+    /// If the WDL flag is set, principal variations are printed with WDL statistics that are
+    /// converted from values by the WDL model.
+    pub fn set_wdl(&self, has_wdl: bool)
+    { self.has_wdl.store(has_wdl, Ordering::SeqCst); }
+
+    /// Returns the Elo rating of strength limit.
+    pub fn elo(&self) -> u32
+    {
+        let strength_data_g = self.strength_data.lock().unwrap();
+        strength_data_g.elo
+    }
+
+    /// Sets the Elo rating of strength limit.
+    ///
+    /// If the strength is limited, the thinker is weakened to the new Elo rating.
+    pub fn set_elo(&self, elo: u32)
+    {
+        let mut strength_data_g = self.strength_data.lock().unwrap();
+        strength_data_g.elo = elo.clamp(StrengthLimit::MIN_ELO, StrengthLimit::MAX_ELO);
+        if strength_data_g.unlimited_params.is_some() {
+            self.apply_strength_limit(&StrengthLimit::new(strength_data_g.elo));
+        }
+    }
+
+    /// Returns `true` if the strength is limited, otherwise `false`.
+    pub fn has_strength_limit(&self) -> bool
+    {
+        let strength_data_g = self.strength_data.lock().unwrap();
+        strength_data_g.unlimited_params.is_some()
+    }
+
+    /// Sets the flag of strength limit.
+    ///
+    /// If this flag is set, the middle depth, the range of random component of evaluation
+    /// function, and the move temperature of neural searcher are replaced by parameters of the
+    /// strength limit for the Elo rating and the number of searched nodes is limited. If this flag
+    /// is unset, the replaced parameters are restored.
+    pub fn set_strength_limit(&self, has_strength_limit: bool)
+    {
+        let mut strength_data_g = self.strength_data.lock().unwrap();
+        match (has_strength_limit, strength_data_g.unlimited_params) {
+            (true, None) => {
+                let eval_random_range = self.searcher().eval_fun().map(|eval_fun| eval_fun.random_range()).unwrap_or(RandomEvalFun::DEFAULT_RANGE);
+                let move_temperature = self.searcher().neural_searcher().map(|neural_searcher| neural_searcher.move_temperature()).unwrap_or(DEFAULT_MOVE_TEMPERATURE);
+                let middle_depth = self.searcher().config().map(|config| config.middle_depth());
+                strength_data_g.unlimited_params = Some((middle_depth, eval_random_range, move_temperature));
+                self.apply_strength_limit(&StrengthLimit::new(strength_data_g.elo));
+            },
+            (false, Some((middle_depth, eval_random_range, move_temperature))) => {
+                if let (Some(config), Some(middle_depth)) = (self.searcher().config(), middle_depth) {
+                    config.set_middle_depth(middle_depth);
+                }
+                if let Some(eval_fun) = self.searcher().eval_fun() {
+                    eval_fun.set_random_range(eval_random_range);
+                }
+                if let Some(neural_searcher) = self.searcher().neural_searcher() {
+                    neural_searcher.set_move_temperature(move_temperature);
+                }
+                strength_data_g.unlimited_params = None;
+            },
+            (_, _) => (),
+        }
+    }
+
+    /// Returns the strength limit if the strength is limited.
+    pub fn strength_limit(&self) -> Option<StrengthLimit>
+    {
+        let strength_data_g = self.strength_data.lock().unwrap();
+        if strength_data_g.unlimited_params.is_some() {
+            Some(StrengthLimit::new(strength_data_g.elo))
+        } else {
+            None
+        }
+    }
+
+    fn apply_strength_limit(&self, strength_limit: &StrengthLimit)
+    {
+        if let Some(config) = self.searcher().config() {
+            config.set_middle_depth(strength_limit.middle_depth);
+        }
+        if let Some(eval_fun) = self.searcher().eval_fun() {
+            eval_fun.set_random_range(strength_limit.eval_random_range);
+        }
+        if let Some(neural_searcher) = self.searcher().neural_searcher() {
+            neural_searcher.set_move_temperature(strength_limit.move_temperature);
+        }
+    }
+
+    /// Returns the number of principal variations.
+    pub fn multi_pv(&self) -> usize
+    { self.multi_pv.load(Ordering::SeqCst) }
+
+    /// Sets the number of principal variations.
+    ///
+    /// The number of principal variations is the number of best lines that are printed for each
+    /// depth of iterative search.
+    pub fn set_multi_pv(&self, multi_pv: usize)
+    { self.multi_pv.store(multi_pv.clamp(Self::MIN_MULTI_PV, Self::MAX_MULTI_PV), Ordering::SeqCst); }
+
     /// Returns the interruption checker.
-    pub fn intr_checker(&self) -> &Arc<dyn IntrCheck + Send + Sync>
-    { self.searcher.intr_checker() }
+    pub fn intr_checker(&self) -> Arc<dyn IntrCheck + Send + Sync>
+    { self.searcher().intr_checker().clone() }
+
+    /// Returns the transposition table if the searcher uses it.
+    pub fn transposition_table(&self) -> Option<Arc<TranspositionTable>>
+    { self.searcher().transposition_table().cloned() }
 
     /// Prepares to iterative search.
     pub fn start(&self)
@@ -76,7 +432,7 @@ impl Thinker
             let mut is_stopped_g = self.is_stopped.lock().unwrap();
             *is_stopped_g = false;
         }
-        self.searcher.intr_checker().start();
+        self.searcher().intr_checker().start();
     }
 
     /// Waits for stopping of iterative search.
@@ -103,100 +459,625 @@ impl Thinker
         *is_stopped_g
     }
     
-    /// Iteratively searches a game tree.
+    /// Prepares to pondering.
     ///
-    /// The search moves are moves from which the search begins. The maximal depth, the maximal
-    /// nodes, and the timeout are the limitations of iterative search. This method searches for a
-    /// checkmate in the moves if these moves is specified. The flags infrom this method whether it
-    /// should make a best move, print a principal variation, and print the best move and an
-    /// outcome.
-    pub fn think(&self, move_chain: &Arc<Mutex<MoveChain>>, search_moves: &Option<Vec<Move>>, max_depth: Option<usize>, max_node_count: Option<u64>, move_count_to_checkmate: Option<usize>, now: Instant, timeout: Option<Duration>, can_make_best_move: bool, can_print_pv: bool, can_print_best_move_and_outcome: bool) -> Result<()>
+    /// The timeout is ignored during pondering. The pondering is finished by the
+    /// [`ponderhit`](Self::ponderhit) method or the [`stop_pondering`](Self::stop_pondering)
+    /// method.
+    pub fn start_pondering(&self)
     {
-        {
-            let mut move_chain_g = move_chain.lock().unwrap();
-            let mut depth = self.searcher.min_depth();
+        let mut ponder_data_g = self.ponder_data.lock().unwrap();
+        ponder_data_g.is_pondering = true;
+        ponder_data_g.pondered_move = None;
+        self.set_soft_timeout(None);
+        self.searcher().intr_checker().unset_timeout();
+        self.searcher().intr_checker().set_ponder(true);
+    }
+
+    fn start_pondering_with_move(&self, board: &Board, mv: Move)
+    {
+        let mut ponder_data_g = self.ponder_data.lock().unwrap();
+        ponder_data_g.is_pondering = true;
+        ponder_data_g.pondered_move = Some((board.clone(), mv));
+        self.set_soft_timeout(None);
+        self.searcher().intr_checker().unset_timeout();
+        self.searcher().intr_checker().set_ponder(true);
+        self.searcher().intr_checker().start();
+    }
+
+    /// Switches pondering to a search with the soft timeout and the timeout.
+    ///
+    /// The work done by pondering is preserved. If the thinker ponders a move, this move is
+    /// accepted.
+    pub fn ponderhit(&self, now: Instant, soft_timeout: Option<Duration>, timeout: Option<Duration>)
+    {
+        let mut ponder_data_g = self.ponder_data.lock().unwrap();
+        if ponder_data_g.is_pondering {
+            self.set_soft_timeout(soft_timeout.map(|soft_timeout| (now, soft_timeout)));
             match timeout {
                 Some(timeout) => {
-                    self.searcher.intr_checker().set_timeout(now, timeout);
+                    self.searcher().intr_checker().set_timeout(now, timeout);
                 },
                 None => {
-                    self.searcher.intr_checker().unset_timeout();
+                    self.searcher().intr_checker().unset_timeout();
                 },
             }
-            let mut best_move: Option<Move> = None;
-            {
-                let mut syzygy_g = self.syzygy.lock().unwrap();
-                match &mut *syzygy_g {
-                    Some(syzygy) => best_move = syzygy.probe(move_chain_g.last()),
-                    None => (),
+            self.searcher().intr_checker().set_ponder(false);
+            ponder_data_g.is_pondering = false;
+            self.ponder_condvar.notify_one();
+        }
+    }
+
+    /// Stops pondering.
+    ///
+    /// If the thinker ponders a move, this move is rejected and the thinker abandons the search
+    /// after this move.
+    pub fn stop_pondering(&self)
+    {
+        let mut ponder_data_g = self.ponder_data.lock().unwrap();
+        self.searcher().intr_checker().set_ponder(false);
+        ponder_data_g.is_pondering = false;
+        ponder_data_g.pondered_move = None;
+        self.ponder_condvar.notify_one();
+    }
+
+    /// Prepares to an infinite search.
+    ///
+    /// The best move of infinite search isn't printed until the
+    /// [`stop_infinite`](Self::stop_infinite) method is called, even if the infinite search is
+    /// finished earlier.
+    pub fn start_infinite(&self)
+    {
+        let mut ponder_data_g = self.ponder_data.lock().unwrap();
+        ponder_data_g.is_infinite = true;
+    }
+
+    /// Stops an infinite search.
+    pub fn stop_infinite(&self)
+    {
+        let mut ponder_data_g = self.ponder_data.lock().unwrap();
+        ponder_data_g.is_infinite = false;
+        self.ponder_condvar.notify_one();
+    }
+
+    /// Returns `true` if the thinker ponders, otherwise `false`.
+    pub fn is_pondering(&self) -> bool
+    {
+        let ponder_data_g = self.ponder_data.lock().unwrap();
+        ponder_data_g.is_pondering
+    }
+
+    /// Returns the pondered move with the board before this move.
+    ///
+    /// The pondered move is an expected move of opponent that the thinker made after a best move
+    /// so that the thinker ponders.
+    pub fn pondered_move(&self) -> Option<(Board, Move)>
+    {
+        let ponder_data_g = self.ponder_data.lock().unwrap();
+        if ponder_data_g.is_pondering {
+            ponder_data_g.pondered_move.clone()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the resign value.
+    pub fn resign_value(&self) -> i32
+    {
+        let adjudicator_g = self.adjudicator.lock().unwrap();
+        adjudicator_g.resign_value
+    }
+
+    /// Sets the resign value.
+    pub fn set_resign_value(&self, resign_value: i32)
+    {
+        let mut adjudicator_g = self.adjudicator.lock().unwrap();
+        adjudicator_g.resign_value = resign_value.clamp(Adjudicator::MIN_RESIGN_VALUE, Adjudicator::MAX_RESIGN_VALUE);
+    }
+
+    /// Returns the number of consecutive moves for resignation.
+    pub fn resign_move_count(&self) -> usize
+    {
+        let adjudicator_g = self.adjudicator.lock().unwrap();
+        adjudicator_g.resign_move_count
+    }
+
+    /// Sets the number of consecutive moves for resignation.
+    ///
+    /// The number of consecutive moves that is equal to zero disables resignation.
+    pub fn set_resign_move_count(&self, move_count: usize)
+    {
+        let mut adjudicator_g = self.adjudicator.lock().unwrap();
+        adjudicator_g.resign_move_count = move_count.min(Adjudicator::MAX_MOVE_COUNT);
+    }
+
+    /// Returns the draw value.
+    pub fn draw_value(&self) -> i32
+    {
+        let adjudicator_g = self.adjudicator.lock().unwrap();
+        adjudicator_g.draw_value
+    }
+
+    /// Sets the draw value.
+    pub fn set_draw_value(&self, draw_value: i32)
+    {
+        let mut adjudicator_g = self.adjudicator.lock().unwrap();
+        adjudicator_g.draw_value = draw_value.clamp(Adjudicator::MIN_DRAW_VALUE, Adjudicator::MAX_DRAW_VALUE);
+    }
+
+    /// Returns the number of consecutive moves for a draw offer.
+    pub fn draw_move_count(&self) -> usize
+    {
+        let adjudicator_g = self.adjudicator.lock().unwrap();
+        adjudicator_g.draw_move_count
+    }
+
+    /// Sets the number of consecutive moves for a draw offer.
+    ///
+    /// The number of consecutive moves that is equal to zero disables draw offers.
+    pub fn set_draw_move_count(&self, move_count: usize)
+    {
+        let mut adjudicator_g = self.adjudicator.lock().unwrap();
+        adjudicator_g.draw_move_count = move_count.min(Adjudicator::MAX_MOVE_COUNT);
+    }
+
+    /// Returns the minimal move number from which a draw can be offered.
+    pub fn min_draw_offer_move_number(&self) -> usize
+    {
+        let adjudicator_g = self.adjudicator.lock().unwrap();
+        adjudicator_g.min_draw_offer_move_number
+    }
+
+    /// Sets the minimal move number from which a draw can be offered.
+    pub fn set_min_draw_offer_move_number(&self, move_number: usize)
+    {
+        let mut adjudicator_g = self.adjudicator.lock().unwrap();
+        adjudicator_g.min_draw_offer_move_number = move_number.min(Adjudicator::MAX_MIN_DRAW_OFFER_MOVE_NUMBER);
+    }
+
+    /// Returns `true` if the thinker accepts a draw offer from the opponent, otherwise `false`.
+    pub fn can_accept_draw(&self) -> bool
+    {
+        let adjudicator_g = self.adjudicator.lock().unwrap();
+        adjudicator_g.can_accept_draw()
+    }
+
+    /// Removes search values of previous moves for resignation and draw offers.
+    ///
+    /// This method should be called for a new game.
+    pub fn clear_game_values(&self)
+    {
+        let mut adjudicator_g = self.adjudicator.lock().unwrap();
+        adjudicator_g.clear_values();
+    }
+
+    /// Returns the hint move for the board.
+    ///
+    /// The hint move is the pondered move or the second move of the last principal variation if
+    /// the board is the board after the first move of this principal variation.
+    pub fn hint_move(&self, board: &Board) -> Option<Move>
+    {
+        match self.pondered_move() {
+            Some((ponder_board, ponder_move)) if ponder_board.zobrist_hash() == board.zobrist_hash() => return Some(ponder_move),
+            _ => (),
+        }
+        let hint_move_g = self.hint_move.lock().unwrap();
+        match &*hint_move_g {
+            Some((hint_board, hint_move)) if hint_board.zobrist_hash() == board.zobrist_hash() => Some(*hint_move),
+            _ => None,
+        }
+    }
+
+    fn set_hint_move(&self, board: &Board, best_move: Option<Move>, ponder_move: Option<Move>)
+    {
+        let mut hint_move_g = self.hint_move.lock().unwrap();
+        *hint_move_g = None;
+        if let (Some(mv), Some(ponder_mv)) = (best_move, ponder_move) {
+            if let Ok(new_board) = board.make_move(mv) {
+                if new_board.make_move(ponder_mv).is_ok() {
+                    *hint_move_g = Some((new_board, ponder_mv));
                 }
             }
-            if best_move.is_none() {
-                let mut is_first = true;
-                let mut node_count = 0u64; 
-                loop {
-                    self.searcher.intr_checker().set_first(is_first);
-                    match self.searcher.search(&mut *move_chain_g, depth, search_moves) {
-                        Ok((value, _, search_node_count, pv)) => {
-                            best_move = pv.first().map(|mv| *mv);
-                            node_count += search_node_count;
-                            if can_print_pv {
-                                let mut writer_g = self.writer.lock().unwrap();
-                                self.printer.print_pv(&mut *writer_g, move_chain_g.last(), depth, value, now.elapsed(), node_count, pv.as_slice())?;
-                                writer_g.flush()?;
-                            }
-                            if value <= MIN_EVAL_MIDDLE_MATE_VALUE || value >= MAX_EVAL_MIDDLE_MATE_VALUE {
+        }
+    }
+
+    fn soft_timeout(&self) -> Option<(Instant, Duration)>
+    {
+        let soft_timeout_g = self.soft_timeout.lock().unwrap();
+        *soft_timeout_g
+    }
+
+    fn set_soft_timeout(&self, soft_timeout: Option<(Instant, Duration)>)
+    {
+        let mut soft_timeout_g = self.soft_timeout.lock().unwrap();
+        *soft_timeout_g = soft_timeout;
+    }
+
+    fn is_soft_timeout(&self, lines: &[(i32, Vec<Move>)], best_move_change_count: f64, dominant_iteration_count: usize) -> bool
+    {
+        if self.is_pondering() {
+            return false;
+        }
+        match self.soft_timeout() {
+            Some((now, soft_timeout)) => {
+                let elapsed = now.elapsed();
+                if lines.len() <= 1 {
+                    return true;
+                }
+                if elapsed >= soft_timeout.mul_f64((1.0 + best_move_change_count * BEST_MOVE_CHANGE_EXTENSION).min(MAX_SOFT_TIMEOUT_RATIO)) {
+                    return true;
+                }
+                dominant_iteration_count >= DOMINANT_ITERATION_COUNT && elapsed >= soft_timeout / DOMINANT_SOFT_TIMEOUT_DIVISOR
+            },
+            None => false,
+        }
+    }
+
+    fn has_pondered_move(&self) -> bool
+    {
+        let ponder_data_g = self.ponder_data.lock().unwrap();
+        ponder_data_g.pondered_move.is_some()
+    }
+
+    fn wait_for_ponderhit(&self) -> bool
+    {
+        let mut ponder_data_g = self.ponder_data.lock().unwrap();
+        while ponder_data_g.is_pondering || ponder_data_g.is_infinite {
+            ponder_data_g = self.ponder_condvar.wait(ponder_data_g).unwrap();
+        }
+        ponder_data_g.pondered_move.take().is_some()
+    }
+
+    fn half_move_count_to_checkmate(&self, value: i32, depth: usize) -> Option<i32>
+    {
+        if value >= MAX_EVAL_MATE_VALUE {
+            self.searcher().move_count_to_checkmate(value, depth).map(|n| n as i32)
+        } else if value <= MIN_EVAL_MATE_VALUE {
+            self.searcher().move_count_to_checkmate(-value, depth).map(|n| -(n as i32))
+        } else {
+            None
+        }
+    }
+
+    fn search_lines(&self, move_chain: &mut MoveChain, depth: usize, search_moves: &Option<Vec<Move>>, line_count: usize) -> Option<(SearchLines, u64)>
+    { self.searcher().search_multi_pv(move_chain, depth, search_moves, line_count).ok().map(|(lines, _, search_node_count)| (lines, search_node_count)) }
+
+    fn search_lines_with_progress(&self, move_chain: &mut MoveChain, depth: usize, search_moves: &Option<Vec<Move>>, line_count: usize, now: Instant, node_count: u64) -> Result<Option<(SearchLines, u64)>>
+    {
+        let board = move_chain.last().clone();
+        let is_searched = Mutex::new(false);
+        let condvar = Condvar::new();
+        scope(|s| {
+                let handle = s.spawn(|| -> Result<()> {
+                        let mut is_searched_g = is_searched.lock().unwrap();
+                        loop {
+                            let (tmp_is_searched_g, _) = condvar.wait_timeout_while(is_searched_g, PROGRESS_INTERVAL, |is_searched| !*is_searched).unwrap();
+                            is_searched_g = tmp_is_searched_g;
+                            if *is_searched_g {
                                 break;
                             }
-                            match max_depth {
-                                Some(max_depth) if depth + 1 > max_depth =>  break,
-                                _ => (),
-                            }
-                            match max_node_count {
-                                Some(max_node_count) if node_count >= max_node_count =>  break,
-                                _ => (),
-                            }
-                            match move_count_to_checkmate {
-                                Some(move_count_to_checkmate) if self.searcher.move_count_to_checkmate(value, depth).map(|n| n <= move_count_to_checkmate * 2).unwrap_or(false) =>  break,
-                                _ => (),
-                            }
-                        },
-                        Err(_) => break,
+                            let hashfull = self.searcher().transposition_table().map(|transposition_table| transposition_table.hashfull());
+                            let mut writer_g = self.writer.lock().unwrap();
+                            let progress = SearchProgress {
+                                depth,
+                                time: now.elapsed(),
+                                node_count: node_count + self.searcher().searched_node_count(),
+                                hashfull,
+                                curr_move: self.searcher().curr_move(),
+                            };
+                            self.printer.print_progress(&mut *writer_g, &board, &progress)?;
+                            writer_g.flush()?;
+                        }
+                        Ok(())
+                });
+                let res = self.search_lines(move_chain, depth, search_moves, line_count);
+                {
+                    let mut is_searched_g = is_searched.lock().unwrap();
+                    *is_searched_g = true;
+                    condvar.notify_one();
+                }
+                handle.join().unwrap()?;
+                Ok(res)
+        })
+    }
+
+    fn search_best_pv(&self, move_chain: &mut MoveChain, search_moves: &Option<Vec<Move>>, limits: SearchLimits, now: Instant, can_print_pv: bool) -> Result<(Option<i32>, Vec<Move>)>
+    {
+        let SearchLimits { max_depth, max_node_count, move_count_to_checkmate } = limits;
+        let mut best_value: Option<i32> = None;
+        let mut best_pv: Vec<Move> = Vec::new();
+        if move_chain.len() < self.book_max_depth() {
+            let book_g = self.book.lock().unwrap();
+            if let Some(book) = &*book_g {
+                let book_move = if self.has_book_best_move() {
+                    book.best_move(move_chain.last())
+                } else {
+                    book.random_move(move_chain.last())
+                };
+                match (book_move, search_moves) {
+                    (Some(mv), Some(search_moves)) if search_moves.contains(&mv) => return Ok((None, vec![mv])),
+                    (Some(mv), None) => return Ok((None, vec![mv])),
+                    (_, _) => (),
+                }
+            }
+        }
+        {
+            let mut syzygy_g = self.syzygy.lock().unwrap();
+            if let Some(syzygy) = &mut *syzygy_g {
+                if let Some((wdl, _, pv)) = syzygy.probe_pv(move_chain.last()) {
+                    let value = match wdl {
+                        fathom::Wdl::Win => MAX_EVAL_TB_VALUE,
+                        fathom::Wdl::Loss => MIN_EVAL_TB_VALUE,
+                        _ => 0,
+                    };
+                    if can_print_pv {
+                        let wdl_stats = if self.has_wdl() {
+                            Some(WdlStats::from_tb_wdl(wdl))
+                        } else {
+                            None
+                        };
+                        let half_move_count_to_checkmate = tb_half_move_count_to_checkmate(move_chain.last(), wdl, pv.as_slice());
+                        let mut writer_g = self.writer.lock().unwrap();
+                        // The root is only probed, so the depth and the number of nodes are one.
+                        let line = PvLine {
+                            depth: 1,
+                            value,
+                            half_move_count_to_checkmate,
+                            wdl: wdl_stats,
+                            time: now.elapsed(),
+                            node_count: 1,
+                            tb_hit_count: 1,
+                            pv: pv.as_slice(),
+                            multi_pv: 1,
+                        };
+                        self.printer.print_pv(&mut *writer_g, move_chain.last(), &line)?;
+                        writer_g.flush()?;
                     }
-                    depth += 1;
-                    is_first = false;
+                    return Ok((Some(value), pv));
                 }
             }
-            if can_print_best_move_and_outcome {
-                match best_move {
-                    Some(mv) => {
+        }
+        self.searcher().clear_tb_hit_count();
+        let mut depth = self.searcher().min_depth();
+        let mut is_first = true;
+        let mut node_count = 0u64; 
+        let multi_pv = self.multi_pv();
+        let mut best_move_change_count = 0.0f64;
+        let mut dominant_iteration_count = 0usize;
+        loop {
+            self.searcher().intr_checker().set_first(is_first);
+            // The second line is searched for checking whether the best move dominates only if
+            // the soft timeout is set or can be set by ponderhit.
+            let line_count = if self.soft_timeout().is_some() || self.is_pondering() {
+                multi_pv.max(2)
+            } else {
+                multi_pv
+            };
+            let lines_and_node_count = if can_print_pv {
+                self.search_lines_with_progress(move_chain, depth, search_moves, line_count, now, node_count)?
+            } else {
+                self.search_lines(move_chain, depth, search_moves, line_count)
+            };
+            match lines_and_node_count {
+                Some((lines, search_node_count)) => {
+                    let value = lines.first().map(|(value, _)| *value).unwrap_or(MIN_EVAL_VALUE);
+                    let pv = lines.first().map(|(_, pv)| pv.clone()).unwrap_or(Vec::new());
+                    let is_best_move_change = !is_first && pv.first() != best_pv.first();
+                    best_value = Some(value);
+                    best_pv = pv;
+                    node_count += search_node_count;
+                    if can_print_pv {
+                        let wdl_model = if self.has_wdl() {
+                            Some(self.wdl_model())
+                        } else {
+                            None
+                        };
                         let mut writer_g = self.writer.lock().unwrap();
-                        self.printer.print_best_move(&mut *writer_g, move_chain_g.last(), mv)?;
+                        for (i, (line_value, pv)) in lines.iter().take(multi_pv).enumerate() {
+                            let line = PvLine {
+                                depth,
+                                value: *line_value,
+                                half_move_count_to_checkmate: self.half_move_count_to_checkmate(*line_value, depth),
+                                wdl: wdl_model.map(|wdl_model| wdl_model.wdl(*line_value)),
+                                time: now.elapsed(),
+                                node_count,
+                                tb_hit_count: self.searcher().tb_hit_count(),
+                                pv: pv.as_slice(),
+                                multi_pv: i + 1,
+                            };
+                            self.printer.print_pv(&mut *writer_g, move_chain.last(), &line)?;
+                        }
                         writer_g.flush()?;
+                    }
+                    if value <= MIN_EVAL_MIDDLE_MATE_VALUE || value >= MAX_EVAL_MIDDLE_MATE_VALUE {
+                        break;
+                    }
+                    if self.searcher().is_full() {
+                        break;
+                    }
+                    match max_depth {
+                        Some(max_depth) if depth + 1 > max_depth =>  break,
+                        _ => (),
+                    }
+                    match max_node_count {
+                        Some(max_node_count) if node_count >= max_node_count =>  break,
+                        _ => (),
+                    }
+                    match move_count_to_checkmate {
+                        Some(move_count_to_checkmate) if value >= MAX_EVAL_MATE_VALUE && self.searcher().move_count_to_checkmate(value, depth).map(|n| n <= move_count_to_checkmate * 2).unwrap_or(false) =>  break,
+                        _ => (),
+                    }
+                    best_move_change_count /= 2.0;
+                    if is_best_move_change {
+                        best_move_change_count += 1.0;
+                    }
+                    match lines.get(1) {
+                        Some((second_value, _)) if !is_best_move_change && value - second_value >= DOMINANT_VALUE_MARGIN => dominant_iteration_count += 1,
+                        _ => dominant_iteration_count = 0,
+                    }
+                    if self.is_soft_timeout(lines.as_slice(), best_move_change_count, dominant_iteration_count) {
+                        break;
+                    }
+                },
+                None => break,
+            }
+            depth += 1;
+            is_first = false;
+        }
+        Ok((best_value, best_pv))
+    }
+
+    /// Iteratively searches a game tree.
+    ///
+    /// The search moves, the maximal depth, the maximal nodes, the soft timeout, and the timeout
+    /// of the thinking parameters are the limitations of iterative search. This method searches
+    /// for a checkmate in the moves if these moves is specified.
+    ///
+    /// The ponder flag informs this method that the thinker was prepared to pondering by the
+    /// [`start_pondering`](Self::start_pondering) method; in this case, the timeouts are set by the
+    /// [`ponderhit`](Self::ponderhit) method and this method waits for pondering end before
+    /// printing the best move. This method also waits for the end of infinite search if the
+    /// thinker was prepared to the infinite search by the [`start_infinite`](Self::start_infinite)
+    /// method.
+    ///
+    /// Other flags infrom this method whether it should make a best move, ponder an expected move
+    /// of opponent after the best move, print a principal variation, and print the best move and
+    /// an outcome. A search progress is printed periodically with the principal variation.
+    ///
+    /// If the board is in the Syzygy endgame tablebases, the principal variation is probed in the
+    /// tablebases instead of a search and is printed with a checkmate score if it ends with
+    /// checkmate.
+    ///
+    /// If this method makes and prints the best move, this method prints resignation instead of
+    /// the best move or a draw offer before the best move when the search values of consecutive
+    /// moves exceed thresholds.
+    pub fn think(&self, move_chain: &Arc<Mutex<MoveChain>>, params: ThinkingParams) -> Result<()>
+    {
+        let ThinkingParams {
+            search_moves,
+            depth: max_depth,
+            node_count: max_node_count,
+            move_count_to_checkmate,
+            now,
+            soft_timeout,
+            timeout,
+            is_ponder,
+            can_make_best_move,
+            can_ponder,
+            can_print_pv,
+            can_print_best_move_and_outcome,
+        } = params;
+        {
+            let mut move_chain_g = move_chain.lock().unwrap();
+            if let Some(transposition_table) = self.searcher().transposition_table() {
+                transposition_table.increase_generation();
+            }
+            if !is_ponder {
+                self.set_soft_timeout(soft_timeout.map(|soft_timeout| (now, soft_timeout)));
+                match timeout {
+                    Some(timeout) => {
+                        self.searcher().intr_checker().set_timeout(now, timeout);
+                    },
+                    None => {
+                        self.searcher().intr_checker().unset_timeout();
                     },
-                    None => (),
                 }
             }
-            if can_make_best_move {
-                match best_move {
-                    Some(mv) => {
+            let max_node_count = match (max_node_count, self.strength_limit()) {
+                (Some(max_node_count), Some(strength_limit)) => Some(max_node_count.min(strength_limit.max_node_count)),
+                (None, Some(strength_limit)) => Some(strength_limit.max_node_count),
+                (max_node_count, None) => max_node_count,
+            };
+            let limits = SearchLimits { max_depth, max_node_count, move_count_to_checkmate, };
+            let mut tmp_search_moves = search_moves;
+            let mut tmp_now = now;
+            let mut has_pondered_move = false;
+            loop {
+                let (best_value, best_pv) = self.search_best_pv(&mut move_chain_g, &tmp_search_moves, limits, tmp_now, can_print_pv)?;
+                let is_ponderhit = self.wait_for_ponderhit();
+                if has_pondered_move && !is_ponderhit {
+                    move_chain_g.pop();
+                    break;
+                }
+                let best_move = best_pv.first().copied();
+                let ponder_move = best_pv.get(1).copied();
+                self.set_hint_move(move_chain_g.last(), best_move, ponder_move);
+                let mut can_offer_draw = false;
+                if can_make_best_move && can_print_best_move_and_outcome && best_move.is_some() {
+                    let mut adjudicator_g = self.adjudicator.lock().unwrap();
+                    match best_value {
+                        Some(value) => adjudicator_g.push_value(value),
+                        None => adjudicator_g.clear_values(),
+                    }
+                    if adjudicator_g.can_resign() {
+                        let mut writer_g = self.writer.lock().unwrap();
+                        self.printer.print_resign(&mut *writer_g)?;
+                        writer_g.flush()?;
+                        break;
+                    }
+                    can_offer_draw = adjudicator_g.can_offer_draw(move_chain_g.last().raw().move_number as usize);
+                }
+                let mut can_ponder_move = false;
+                if can_make_best_move && can_ponder {
+                    if let (Some(mv), Some(ponder_mv)) = (best_move, ponder_move) {
+                        if let Ok(new_board) = move_chain_g.last().make_move(mv) {
+                            if new_board.make_move(ponder_mv).is_ok() {
+                                self.start_pondering_with_move(&new_board, ponder_mv);
+                                can_ponder_move = true;
+                            }
+                        }
+                    }
+                }
+                if can_print_best_move_and_outcome {
+                    if let Some(mv) = best_move {
+                        let mut writer_g = self.writer.lock().unwrap();
+                        if can_offer_draw {
+                            self.printer.print_draw_offer(&mut *writer_g)?;
+                        }
+                        self.printer.print_best_move(&mut *writer_g, move_chain_g.last(), mv, ponder_move)?;
+                        writer_g.flush()?;
+                    }
+                }
+                let mut is_outcome = false;
+                if can_make_best_move {
+                    if let Some(mv) = best_move {
                         move_chain_g.push(mv).unwrap();
                         let outcome = move_chain_g.set_auto_outcome(OutcomeFilter::Relaxed);
                         move_chain_g.clear_outcome();
+                        is_outcome = outcome.is_some();
                         if can_print_best_move_and_outcome {
-                            match outcome {
-                                Some(outcome) => {
-                                    let mut writer_g = self.writer.lock().unwrap();
-                                    self.printer.print_outcome(&mut *writer_g, outcome)?;
-                                    writer_g.flush()?;
-                                },
-                                None => (),
+                            if let Some(outcome) = outcome {
+                                let mut writer_g = self.writer.lock().unwrap();
+                                self.printer.print_outcome(&mut *writer_g, outcome)?;
+                                writer_g.flush()?;
                             }
                         }
+                    }
+                }
+                if !can_ponder_move {
+                    break;
+                }
+                match ponder_move {
+                    Some(ponder_mv) if !is_outcome && self.has_pondered_move() => {
+                        move_chain_g.push(ponder_mv).unwrap();
+                        let outcome = move_chain_g.set_auto_outcome(OutcomeFilter::Relaxed);
+                        move_chain_g.clear_outcome();
+                        if outcome.is_some() {
+                            move_chain_g.pop();
+                            break;
+                        }
                     },
-                    None => (),
+                    _ => break,
                 }
+                tmp_search_moves = None;
+                tmp_now = Instant::now();
+                has_pondered_move = true;
             }
+            let mut ponder_data_g = self.ponder_data.lock().unwrap();
+            self.searcher().intr_checker().set_ponder(false);
+            ponder_data_g.is_pondering = false;
+            ponder_data_g.is_infinite = false;
+            ponder_data_g.pondered_move = None;
         }
         self.stop();
         Ok(())
@@ -0,0 +1,280 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use crate::chess::Move;
+
+/// An enumeration of bound.
+///
+/// The bound informs how a value of entry of transposition table is related to an exact value.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Bound
+{
+    /// The value is exact.
+    Exact,
+    /// The value is a lower bound.
+    Lower,
+    /// The value is an upper bound.
+    Upper,
+}
+
+/// A structure of entry of transposition table.
+#[derive(Copy, Clone, Debug)]
+pub struct TranspositionEntry
+{
+    /// A Zobrist hash of board.
+    pub zobrist_hash: u64,
+    /// A best move.
+    pub best_move: Option<Move>,
+    /// A bound of value.
+    pub bound: Bound,
+    /// A value.
+    pub value: i32,
+    /// A depth that is a sum of a depth of middle search and a depth of neural search.
+    pub depth: usize,
+    /// A depth of middle search.
+    pub middle_depth: usize,
+    /// A generation of search.
+    pub generation: u8,
+    /// If this flag is set, the value is fixed by the user and doesn't depend on a depth of
+    /// middle search.
+    pub is_fixed: bool,
+    /// If this flag is set, the entry is stored for the root of search and its best move is only
+    /// used for move ordering.
+    pub is_root: bool,
+}
+
+struct TranspositionShard
+{
+    entries: Vec<Option<TranspositionEntry>>,
+    fixed_values: HashMap<u64, (i32, usize)>,
+}
+
+/// A structure of transposition table.
+///
+/// The transposition table has a fixed size and is indexed by Zobrist hashes of boards. An entry
+/// is replaced by a new entry if the new entry has a depth that is greater than or equal to the
+/// depth of the entry or the entry is from an older search. The transposition table can be shared
+/// between iterations of iterative search and between threads. The entries are distributed
+/// between shards that are locked separately so that threads rarely wait for each other.
+pub struct TranspositionTable
+{
+    shards: Vec<Mutex<TranspositionShard>>,
+    shard_entry_count: AtomicUsize,
+    generation: AtomicU8,
+}
+
+impl TranspositionTable
+{
+    /// A default size of transposition table in megabytes.
+    pub const DEFAULT_SIZE: usize = 16;
+
+    /// A minimal size of transposition table in megabytes.
+    pub const MIN_SIZE: usize = 1;
+
+    /// A maximal size of transposition table in megabytes.
+    pub const MAX_SIZE: usize = 65536;
+
+    /// A number of shards.
+    pub const SHARD_COUNT: usize = 64;
+
+    fn shard_entry_count_for_size(size: usize) -> usize
+    { ((size * 1024 * 1024) / size_of::<Option<TranspositionEntry>>() / Self::SHARD_COUNT).max(1) }
+
+    /// Creates a transposition table with the size in megabytes.
+    pub fn new(size: usize) -> Self
+    {
+        let shard_entry_count = Self::shard_entry_count_for_size(size);
+        let mut shards: Vec<Mutex<TranspositionShard>> = Vec::with_capacity(Self::SHARD_COUNT);
+        for _ in 0..Self::SHARD_COUNT {
+            let shard = TranspositionShard {
+                entries: vec![None; shard_entry_count],
+                fixed_values: HashMap::new(),
+            };
+            shards.push(Mutex::new(shard));
+        }
+        TranspositionTable {
+            shards,
+            shard_entry_count: AtomicUsize::new(shard_entry_count),
+            generation: AtomicU8::new(0),
+        }
+    }
+
+    /// Returns the number of entries that can be stored in the transposition table.
+    pub fn entry_count(&self) -> usize
+    { self.shard_entry_count.load(Ordering::SeqCst) * Self::SHARD_COUNT }
+
+    fn shard_and_index(&self, zobrist_hash: u64) -> (&Mutex<TranspositionShard>, usize)
+    {
+        let idx = (zobrist_hash % (self.entry_count() as u64)) as usize;
+        (&self.shards[idx % Self::SHARD_COUNT], idx / Self::SHARD_COUNT)
+    }
+
+    /// Returns the occupancy of the transposition table in permille.
+    ///
+    /// The occupancy is estimated from the first thousand entries that are from the current
+    /// search.
+    pub fn hashfull(&self) -> usize
+    {
+        let generation = self.generation.load(Ordering::SeqCst);
+        let sample_count = self.entry_count().min(1000);
+        let mut used_count = 0usize;
+        for (i, shard) in self.shards.iter().enumerate() {
+            let shard_g = shard.lock().unwrap();
+            let shard_sample_count = (sample_count + Self::SHARD_COUNT - 1 - i) / Self::SHARD_COUNT;
+            for entry in &shard_g.entries[0..shard_sample_count.min(shard_g.entries.len())] {
+                match entry {
+                    Some(entry) if entry.generation == generation => used_count += 1,
+                    _ => (),
+                }
+            }
+        }
+        (used_count * 1000) / sample_count
+    }
+
+    /// Resizes the transposition table to the size in megabytes.
+    ///
+    /// All entries of the transposition table are removed.
+    pub fn resize(&self, size: usize)
+    {
+        let shard_entry_count = Self::shard_entry_count_for_size(size);
+        let mut shard_gs: Vec<MutexGuard<'_, TranspositionShard>> = self.shards.iter().map(|shard| shard.lock().unwrap()).collect();
+        for shard_g in &mut shard_gs {
+            shard_g.entries = vec![None; shard_entry_count];
+        }
+        self.shard_entry_count.store(shard_entry_count, Ordering::SeqCst);
+    }
+
+    /// Removes all entries of the transposition table.
+    pub fn clear(&self)
+    {
+        for shard in &self.shards {
+            let mut shard_g = shard.lock().unwrap();
+            for entry in &mut shard_g.entries {
+                *entry = None;
+            }
+            shard_g.fixed_values.clear();
+        }
+        self.generation.store(0, Ordering::SeqCst);
+    }
+
+    /// Increases the generation of search.
+    ///
+    /// This method should be called before each new iterative search so that entries from older
+    /// searches are replaced.
+    pub fn increase_generation(&self)
+    { self.generation.fetch_add(1, Ordering::SeqCst); }
+
+    /// Returns the entry for the Zobrist hash if the entry is in the transposition table.
+    pub fn get(&self, zobrist_hash: u64) -> Option<TranspositionEntry>
+    {
+        let (shard, idx) = self.shard_and_index(zobrist_hash);
+        let shard_g = shard.lock().unwrap();
+        let entry = match shard_g.entries.get(idx) {
+            Some(Some(entry)) if entry.zobrist_hash == zobrist_hash => Some(*entry),
+            _ => None,
+        };
+        match shard_g.fixed_values.get(&zobrist_hash) {
+            Some((value, depth)) => {
+                Some(TranspositionEntry {
+                        zobrist_hash,
+                        best_move: entry.and_then(|entry| entry.best_move),
+                        bound: Bound::Exact,
+                        value: *value,
+                        depth: *depth,
+                        middle_depth: 0,
+                        generation: self.generation.load(Ordering::SeqCst),
+                        is_fixed: true,
+                        is_root: false,
+                })
+            },
+            None => entry,
+        }
+    }
+
+    /// Fixes the value for the Zobrist hash to the depth.
+    ///
+    /// The fixed value is returned by the [`get`](Self::get) method instead of a searched value
+    /// and isn't replaced by the [`set`](Self::set) method. Fixed values are removed by the
+    /// [`clear`](Self::clear) method.
+    pub fn set_fixed_value(&self, zobrist_hash: u64, value: i32, depth: usize)
+    {
+        let (shard, _) = self.shard_and_index(zobrist_hash);
+        let mut shard_g = shard.lock().unwrap();
+        shard_g.fixed_values.insert(zobrist_hash, (value, depth));
+    }
+
+    fn set_entry(&self, mut entry: TranspositionEntry)
+    {
+        let (shard, idx) = self.shard_and_index(entry.zobrist_hash);
+        let mut shard_g = shard.lock().unwrap();
+        let generation = self.generation.load(Ordering::SeqCst);
+        let old_entry = match shard_g.entries.get(idx) {
+            Some(old_entry) => *old_entry,
+            None => return,
+        };
+        let can_replace = match old_entry {
+            Some(old_entry) => old_entry.generation != generation || entry.depth >= old_entry.depth,
+            None => true,
+        };
+        if can_replace {
+            let old_best_move = match old_entry {
+                Some(old_entry) if old_entry.zobrist_hash == entry.zobrist_hash => old_entry.best_move,
+                _ => None,
+            };
+            entry.best_move = entry.best_move.or(old_best_move);
+            entry.generation = generation;
+            shard_g.entries[idx] = Some(entry);
+        }
+    }
+
+    /// Stores the entry in the transposition table.
+    ///
+    /// The generation of entry is set to the current generation of search.
+    pub fn set(&self, zobrist_hash: u64, best_move: Option<Move>, bound: Bound, value: i32, depth: usize, middle_depth: usize)
+    {
+        self.set_entry(TranspositionEntry {
+                zobrist_hash,
+                best_move,
+                bound,
+                value,
+                depth,
+                middle_depth,
+                generation: 0,
+                is_fixed: false,
+                is_root: false,
+        });
+    }
+
+    /// Stores the entry for the root of search in the transposition table.
+    ///
+    /// The value of root depends on the history of game, so the entry isn't used to cut off a
+    /// search and its best move is only used for move ordering.
+    pub fn set_root(&self, zobrist_hash: u64, best_move: Option<Move>, value: i32, depth: usize, middle_depth: usize)
+    {
+        self.set_entry(TranspositionEntry {
+                zobrist_hash,
+                best_move,
+                bound: Bound::Exact,
+                value,
+                depth,
+                middle_depth,
+                generation: 0,
+                is_fixed: false,
+                is_root: true,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,170 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::thread::scope;
+use crate::chess::Board;
+use super::*;
+
+#[test]
+fn test_transposition_table_set_and_get_sets_and_gets_entries()
+{
+    let tt = TranspositionTable::new(1);
+    let board = Board::initial();
+    let mv = Move::from_uci("e2e4", &board).unwrap();
+    tt.set(board.zobrist_hash(), Some(mv), Bound::Exact, 12, 5, 2);
+    match tt.get(board.zobrist_hash()) {
+        Some(entry) => {
+            assert_eq!(board.zobrist_hash(), entry.zobrist_hash);
+            assert_eq!(Some(mv), entry.best_move);
+            assert_eq!(Bound::Exact, entry.bound);
+            assert_eq!(12, entry.value);
+            assert_eq!(5, entry.depth);
+            assert_eq!(2, entry.middle_depth);
+        },
+        None => assert!(false),
+    }
+    assert!(tt.get(board.zobrist_hash() ^ 1).is_none());
+}
+
+#[test]
+fn test_transposition_table_set_replaces_entries_by_depth()
+{
+    let tt = TranspositionTable::new(1);
+    let board = Board::initial();
+    let mv = Move::from_uci("e2e4", &board).unwrap();
+    let mv2 = Move::from_uci("d2d4", &board).unwrap();
+    tt.set(board.zobrist_hash(), Some(mv), Bound::Exact, 12, 5, 2);
+    tt.set(board.zobrist_hash(), Some(mv2), Bound::Lower, 20, 4, 2);
+    match tt.get(board.zobrist_hash()) {
+        Some(entry) => {
+            assert_eq!(Some(mv), entry.best_move);
+            assert_eq!(12, entry.value);
+            assert_eq!(5, entry.depth);
+        },
+        None => assert!(false),
+    }
+    tt.set(board.zobrist_hash(), None, Bound::Upper, 7, 6, 2);
+    match tt.get(board.zobrist_hash()) {
+        Some(entry) => {
+            assert_eq!(Some(mv), entry.best_move);
+            assert_eq!(Bound::Upper, entry.bound);
+            assert_eq!(7, entry.value);
+            assert_eq!(6, entry.depth);
+        },
+        None => assert!(false),
+    }
+    tt.increase_generation();
+    tt.set(board.zobrist_hash(), Some(mv2), Bound::Lower, 20, 4, 2);
+    match tt.get(board.zobrist_hash()) {
+        Some(entry) => {
+            assert_eq!(Some(mv2), entry.best_move);
+            assert_eq!(4, entry.depth);
+        },
+        None => assert!(false),
+    }
+}
+
+#[test]
+fn test_transposition_table_clear_removes_entries()
+{
+    let tt = TranspositionTable::new(1);
+    let board = Board::initial();
+    tt.set(board.zobrist_hash(), None, Bound::Exact, 12, 5, 2);
+    tt.clear();
+    assert!(tt.get(board.zobrist_hash()).is_none());
+    tt.set(board.zobrist_hash(), None, Bound::Exact, 12, 5, 2);
+    tt.resize(2);
+    assert!(tt.get(board.zobrist_hash()).is_none());
+    assert!(tt.entry_count() > 0);
+}
+
+#[test]
+fn test_transposition_table_hashfull_returns_occupancy()
+{
+    let tt = TranspositionTable::new(1);
+    assert_eq!(0, tt.hashfull());
+    for i in 0..500u64 {
+        tt.set(i, None, Bound::Exact, 0, 1, 1);
+    }
+    assert_eq!(500, tt.hashfull());
+    tt.increase_generation();
+    assert_eq!(0, tt.hashfull());
+}
+
+#[test]
+fn test_transposition_table_set_fixed_value_fixes_values()
+{
+    let tt = TranspositionTable::new(1);
+    let board = Board::initial();
+    let mv = Move::from_uci("e2e4", &board).unwrap();
+    tt.set(board.zobrist_hash(), Some(mv), Bound::Lower, 12, 5, 2);
+    tt.set_fixed_value(board.zobrist_hash(), 34, 7);
+    tt.increase_generation();
+    tt.set(board.zobrist_hash(), None, Bound::Upper, 20, 8, 2);
+    match tt.get(board.zobrist_hash()) {
+        Some(entry) => {
+            assert_eq!(Some(mv), entry.best_move);
+            assert_eq!(Bound::Exact, entry.bound);
+            assert_eq!(34, entry.value);
+            assert_eq!(7, entry.depth);
+            assert!(entry.is_fixed);
+        },
+        None => assert!(false),
+    }
+    tt.clear();
+    assert!(tt.get(board.zobrist_hash()).is_none());
+}
+
+#[test]
+fn test_transposition_table_set_root_sets_root_entries()
+{
+    let tt = TranspositionTable::new(1);
+    let board = Board::initial();
+    let mv = Move::from_uci("e2e4", &board).unwrap();
+    tt.set_root(board.zobrist_hash(), Some(mv), 12, 5, 2);
+    match tt.get(board.zobrist_hash()) {
+        Some(entry) => {
+            assert_eq!(Some(mv), entry.best_move);
+            assert_eq!(Bound::Exact, entry.bound);
+            assert_eq!(12, entry.value);
+            assert_eq!(5, entry.depth);
+            assert_eq!(2, entry.middle_depth);
+            assert!(entry.is_root);
+        },
+        None => assert!(false),
+    }
+    tt.set(board.zobrist_hash(), None, Bound::Lower, 20, 5, 2);
+    match tt.get(board.zobrist_hash()) {
+        Some(entry) => {
+            assert_eq!(Some(mv), entry.best_move);
+            assert!(!entry.is_root);
+        },
+        None => assert!(false),
+    }
+}
+
+#[test]
+fn test_transposition_table_set_and_get_sets_and_gets_entries_in_threads()
+{
+    let tt = TranspositionTable::new(1);
+    scope(|s| {
+            for i in 0..4u64 {
+                let tt = &tt;
+                s.spawn(move || {
+                        for j in 0..1000u64 {
+                            tt.set(j * 4 + i, None, Bound::Exact, (j * 4 + i) as i32, 1, 1);
+                        }
+                });
+            }
+    });
+    for i in 0..4000u64 {
+        match tt.get(i) {
+            Some(entry) => assert_eq!(i as i32, entry.value),
+            None => assert!(false),
+        }
+    }
+}
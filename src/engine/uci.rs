@@ -10,7 +10,6 @@ use std::io::Error;
 use std::io::Result;
 use std::io::Write;
 use std::io::stdin;
-use std::mem::swap;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::time::Duration;
@@ -22,9 +21,10 @@ use crate::chess::MoveChain;
 use crate::chess::Outcome;
 use crate::engine::engine::*;
 use crate::engine::engine_id::*;
+use crate::engine::engine_option::*;
 use crate::engine::io::*;
 use crate::engine::print::*;
-use crate::engine::syzygy::*;
+use crate::engine::thinker::*;
 use crate::engine::utils::*;
 use crate::engine::LoopError;
 use crate::engine::LoopResult;
@@ -45,26 +45,61 @@ impl UciPrinter
 
 impl Print for UciPrinter
 {
-    fn print_pv(&self, w: &mut dyn Write, _board: &Board, depth: usize, value: i32, time: Duration, node_count: u64, pv: &[Move]) -> Result<()>
+    fn print_pv(&self, w: &mut dyn Write, _board: &Board, line: &PvLine) -> Result<()>
     {
-        let nps_millis = if time.as_millis() > 0 { time.as_millis() } else { 1 };
-        let nps = ((node_count as u128) * 1000) / nps_millis;
-        write!(w, "info depth {} multipv 1 score cp {} time {} nodes {} nps {} pv", depth, value, time.as_millis(), node_count, nps)?;
-        for mv in pv {
+        let nps_millis = if line.time.as_millis() > 0 { line.time.as_millis() } else { 1 };
+        let nps = ((line.node_count as u128) * 1000) / nps_millis;
+        write!(w, "info depth {} multipv {}", line.depth, line.multi_pv)?;
+        match line.half_move_count_to_checkmate {
+            Some(n) if n > 0 => write!(w, " score mate {}", (n + 1) / 2)?,
+            Some(n) => write!(w, " score mate {}", n / 2)?,
+            None => write!(w, " score cp {}", line.value)?,
+        }
+        if let Some(wdl) = line.wdl {
+            write!(w, " wdl {} {} {}", wdl.win, wdl.draw, wdl.loss)?;
+        }
+        write!(w, " time {} nodes {} nps {} tbhits {} pv", line.time.as_millis(), line.node_count, nps, line.tb_hit_count)?;
+        for mv in line.pv {
             write!(w, " {}", mv.uci())?;
         }
-        writeln!(w, "")?;
+        writeln!(w)?;
+        Ok(())
+    }
+
+    fn print_progress(&self, w: &mut dyn Write, _board: &Board, progress: &SearchProgress) -> Result<()>
+    {
+        let nps_millis = if progress.time.as_millis() > 0 { progress.time.as_millis() } else { 1 };
+        let nps = ((progress.node_count as u128) * 1000) / nps_millis;
+        write!(w, "info depth {} time {} nodes {} nps {}", progress.depth, progress.time.as_millis(), progress.node_count, nps)?;
+        if let Some(hashfull) = progress.hashfull {
+            write!(w, " hashfull {}", hashfull)?;
+        }
+        if let Some((mv, move_number)) = progress.curr_move {
+            write!(w, " currmove {} currmovenumber {}", mv.uci(), move_number)?;
+        }
+        writeln!(w)?;
         Ok(())
     }
     
-    fn print_best_move(&self, w: &mut dyn Write, _board: &Board, mv: Move) -> Result<()>
-    { writeln!(w, "bestmove {}", mv.uci()) }
+    fn print_best_move(&self, w: &mut dyn Write, _board: &Board, mv: Move, ponder_move: Option<Move>) -> Result<()>
+    {
+        match ponder_move {
+            Some(ponder_move) => writeln!(w, "bestmove {} ponder {}", mv.uci(), ponder_move.uci()),
+            None => writeln!(w, "bestmove {}", mv.uci()),
+        }
+    }
     
     fn print_outcome(&self, _w: &mut dyn Write, _outcome: Outcome) -> Result<()>
     { Ok(()) }
+
+    fn print_resign(&self, _w: &mut dyn Write) -> Result<()>
+    { Ok(()) }
+
+    fn print_draw_offer(&self, _w: &mut dyn Write) -> Result<()>
+    { Ok(()) }
 }
 
-fn uci_uciok(stdout_log: &Arc<Mutex<StdoutLog>>, engine_id: EngineId) -> Result<()>
+fn uci_uciok(stdout_log: &Arc<Mutex<StdoutLog>>, engine_id: EngineId, engine: &Engine) -> Result<()>
 {
     let mut stdout_log_g = stdout_log.lock().unwrap();
     writeln!(&mut *stdout_log_g, "id name {}", engine_id.name)?;
@@ -85,7 +120,22 @@ fn uci_uciok(stdout_log: &Arc<Mutex<StdoutLog>>, engine_id: EngineId) -> Result<
         None => (),
     }
     writeln!(&mut *stdout_log_g, "id author {}", author)?;
-    writeln!(&mut *stdout_log_g, "option name SyzygyPath type string default ")?;
+    for option in &engine_options(engine) {
+        if let Some(name) = option.uci_name {
+            match &option.option_type {
+                OptionType::Check(default) => writeln!(&mut *stdout_log_g, "option name {} type check default {}", name, default)?,
+                OptionType::Spin(default, min, max) => writeln!(&mut *stdout_log_g, "option name {} type spin default {} min {} max {}", name, default, min, max)?,
+                OptionType::String(default) | OptionType::File(default) | OptionType::Path(default) => writeln!(&mut *stdout_log_g, "option name {} type string default {}", name, default)?,
+                OptionType::Combo(default, values) => {
+                    write!(&mut *stdout_log_g, "option name {} type combo default {}", name, default)?;
+                    for value in values.iter() {
+                        write!(&mut *stdout_log_g, " var {}", value)?;
+                    }
+                    writeln!(&mut *stdout_log_g)?;
+                },
+            }
+        }
+    }
     writeln!(&mut *stdout_log_g, "uciok")?;
     stdout_log_g.flush()?;
     Ok(())
@@ -99,6 +149,22 @@ fn uci_readyok(stdout_log: &Arc<Mutex<StdoutLog>>) -> Result<()>
     Ok(())
 }
 
+fn uci_info_string(stdout_log: &Arc<Mutex<StdoutLog>>, s: &str) -> Result<()>
+{
+    let mut stdout_log_g = stdout_log.lock().unwrap();
+    writeln!(&mut *stdout_log_g, "info string {}", s)?;
+    stdout_log_g.flush()?;
+    Ok(())
+}
+
+fn uci_load_network(stdout_log: &Arc<Mutex<StdoutLog>>, engine: &mut Engine) -> Result<()>
+{
+    match engine.load_network() {
+        Ok(()) => Ok(()),
+        Err(err) => uci_info_string(stdout_log, format!("can't load network: {}", err).as_str()),
+    }
+}
+
 fn uci_unknown_command(stdout_log: &Arc<Mutex<StdoutLog>>, cmd: &str) -> Result<()>
 {
     let mut stdout_log_g = stdout_log.lock().unwrap();
@@ -114,15 +180,12 @@ fn initialize_commands(cmds: &mut HashMap<String, fn(&Arc<Mutex<StdoutLog>>, &mu
     cmds.insert(String::from("position"), uci_position);
     cmds.insert(String::from("go"), uci_go);
     cmds.insert(String::from("stop"), uci_stop);
-    cmds.insert(String::from("ponderhit"), uci_ignore);
+    cmds.insert(String::from("ponderhit"), uci_ponderhit);
     cmds.insert(String::from("quit"), uci_quit);
     cmds.insert(String::from("display"), uci_display);
 }
 
-fn uci_ignore(_stdout_log: &Arc<Mutex<StdoutLog>>, _engine: &mut Engine, _args: &[&str]) -> Result<bool>
-{ Ok(false) }
-
-fn uci_setoption(_stdout_log: &Arc<Mutex<StdoutLog>>, engine: &mut Engine, args: &[&str]) -> Result<bool>
+fn uci_setoption(stdout_log: &Arc<Mutex<StdoutLog>>, engine: &mut Engine, args: &[&str]) -> Result<bool>
 {
     let mut is_first = true;
     let mut i = 0usize;
@@ -168,38 +231,28 @@ fn uci_setoption(_stdout_log: &Arc<Mutex<StdoutLog>>, engine: &mut Engine, args:
         },
         _ => (),
     }
-    if name == String::from("SyzygyPath") {
-        let mut syzygy_g = engine.thinker().syzygy().lock().unwrap();
-        if !value.is_empty() {
-            let mut syzygy: Option<Syzygy> = None;
-            swap(&mut *syzygy_g, &mut syzygy);
-            match syzygy {
-                Some(syzygy) => {
-                    match syzygy.reload(value) {
-                        Ok(tmp_syzygy) => *syzygy_g = Some(tmp_syzygy),
-                        Err(_) => (),
-                    }
-                },
-                None => {
-                    match Syzygy::new(value) {
-                        Ok(tmp_syzygy) => *syzygy_g = Some(tmp_syzygy),
-                        Err(_) => (),
-                    }
-                },
+    match engine_options(engine).iter().find(|option| option.uci_name == Some(name.as_str())) {
+        Some(option) => {
+            match option.parse_value(value.as_str(), false).and_then(|option_value| (option.set)(engine, &option_value)) {
+                Ok(()) => (),
+                Err(err) => uci_info_string(stdout_log, format!("{}: {}", name, err).as_str())?,
             }
-        } else {
-            *syzygy_g = None;
-        }
+        },
+        None => uci_info_string(stdout_log, format!("{}: unknown option", name).as_str())?,
     }
     Ok(false)
 }
 
-fn uci_ucinewgame(_stdout_log: &Arc<Mutex<StdoutLog>>, engine: &mut Engine, _args: &[&str]) -> Result<bool>
+fn uci_ucinewgame(stdout_log: &Arc<Mutex<StdoutLog>>, engine: &mut Engine, _args: &[&str]) -> Result<bool>
 {
     engine.stop();
+    uci_load_network(stdout_log, engine)?;
     engine.do_move_chain(|move_chain| {
             *move_chain = MoveChain::new_initial();
     });
+    if let Some(transposition_table) = engine.thinker().transposition_table() {
+        transposition_table.clear();
+    }
     Ok(false)
 }
 
@@ -253,9 +306,10 @@ fn uci_position(_stdout_log: &Arc<Mutex<StdoutLog>>, engine: &mut Engine, args:
     Ok(false)
 }
 
-fn uci_go(_stdout_log: &Arc<Mutex<StdoutLog>>, engine: &mut Engine, args: &[&str]) -> Result<bool>
+fn uci_go(stdout_log: &Arc<Mutex<StdoutLog>>, engine: &mut Engine, args: &[&str]) -> Result<bool>
 {
     engine.stop();
+    uci_load_network(stdout_log, engine)?;
     let mut i = 0usize;
     let mut search_moves: Option<Vec<Move>> = None;
     let mut white_time: Option<Duration> = None;
@@ -267,6 +321,8 @@ fn uci_go(_stdout_log: &Arc<Mutex<StdoutLog>>, engine: &mut Engine, args: &[&str
     let mut node_count: Option<u64> = None;
     let mut move_count_to_checkmate: Option<usize> = None;
     let mut move_time: Option<Duration> = None;
+    let mut is_ponder = false;
+    let mut is_infinite = false;
     loop {
         match args.get(i) {
             Some(arg) if *arg == "searchmoves" => {
@@ -353,12 +409,21 @@ fn uci_go(_stdout_log: &Arc<Mutex<StdoutLog>>, engine: &mut Engine, args: &[&str
                 }
                 i += 2;
             },
+            Some(arg) if *arg == "ponder" => {
+                is_ponder = true;
+                i += 1;
+            },
+            Some(arg) if *arg == "infinite" => {
+                is_infinite = true;
+                i += 1;
+            },
             Some(_) => i += 1,
             None => break,
         }
     }
     let mut is_timeout = false;
     match move_time {
+        _ if is_infinite => (),
         Some(move_time) => {
             engine.set_time_control(TimeControl::Fixed(move_time));
             is_timeout = true;
@@ -386,7 +451,17 @@ fn uci_go(_stdout_log: &Arc<Mutex<StdoutLog>>, engine: &mut Engine, args: &[&str
         },
     }
     engine.set_move_count_to_go(move_count_to_go);
-    engine.go(search_moves, depth, node_count, move_count_to_checkmate, is_timeout, false, true, true);
+    let params = ThinkingParams {
+        search_moves,
+        depth,
+        node_count,
+        move_count_to_checkmate,
+        is_ponder,
+        can_print_pv: true,
+        can_print_best_move_and_outcome: true,
+        ..ThinkingParams::new()
+    };
+    engine.go_with_ponder(params, is_timeout, is_infinite);
     Ok(false)
 }
 
@@ -396,6 +471,12 @@ fn uci_stop(_stdout_log: &Arc<Mutex<StdoutLog>>, engine: &mut Engine, _args: &[&
     Ok(false)
 }
 
+fn uci_ponderhit(_stdout_log: &Arc<Mutex<StdoutLog>>, engine: &mut Engine, _args: &[&str]) -> Result<bool>
+{
+    engine.ponderhit();
+    Ok(false)
+}
+
 fn uci_quit(_stdout_log: &Arc<Mutex<StdoutLog>>, _engine: &mut Engine, _args: &[&str]) -> Result<bool>
 { Ok(true) }
 
@@ -420,9 +501,11 @@ pub fn uci_loop_with_engine_id<F>(stdout_log: Arc<Mutex<StdoutLog>>, engine_id:
 {
     let mut cmds: HashMap<String, fn(&Arc<Mutex<StdoutLog>>, &mut Engine, &[&str]) -> Result<bool>> = HashMap::new();
     let mut err: Option<LoopError> = None;
-    let mut engine: Option<Engine> = None;
     initialize_commands(&mut cmds);
-    match uci_uciok(&stdout_log, engine_id) {
+    // The engine is created before the options are sent, so the default values of options are
+    // the current values of the engine.
+    let mut engine = f(stdout_log.clone(), Arc::new(UciPrinter::new()))?;
+    match uci_uciok(&stdout_log, engine_id, &engine) {
         Ok(()) => (),
         Err(err2) => err = Some(LoopError::Io(err2)),
     }
@@ -465,42 +548,25 @@ pub fn uci_loop_with_engine_id<F>(stdout_log: Arc<Mutex<StdoutLog>>, engine_id:
                         }
                         continue;
                     }
-                    if engine.is_none() {
-                        match f(stdout_log.clone(), Arc::new(UciPrinter::new())) {
-                            Ok(new_engine) => engine = Some(new_engine),
-                            Err(err2) => {
-                                err = Some(err2);
-                                break;
-                            },
-                        }
-                    }
-                    match &mut engine {
-                        Some(engine) => {
-                            match cmds.get(&String::from(cmd_name)) {
-                                Some(cmd_fun) => {
-                                    match cmd_fun(&stdout_log, engine, args.as_slice()) {
-                                        Ok(is_exit) if is_exit => break,
-                                        Ok(_) => (),
-                                        Err(err2) => {
-                                            err = Some(LoopError::Io(err2));
-                                            break;
-                                        },
-                                    }
-                                },
-                                None => {
-                                    match uci_unknown_command(&stdout_log, cmd) {
-                                        Ok(()) => (),
-                                        Err(err2) => {
-                                            err = Some(LoopError::Io(err2));
-                                            break;
-                                        },
-                                    }
+                    match cmds.get(&String::from(cmd_name)) {
+                        Some(cmd_fun) => {
+                            match cmd_fun(&stdout_log, &mut engine, args.as_slice()) {
+                                Ok(is_exit) if is_exit => break,
+                                Ok(_) => (),
+                                Err(err2) => {
+                                    err = Some(LoopError::Io(err2));
+                                    break;
                                 },
                             }
                         },
                         None => {
-                            err = Some(LoopError::UninitializedLoopContext);
-                            break;
+                            match uci_unknown_command(&stdout_log, cmd) {
+                                Ok(()) => (),
+                                Err(err2) => {
+                                    err = Some(LoopError::Io(err2));
+                                    break;
+                                },
+                            }
                         },
                     }
                 },
@@ -508,13 +574,8 @@ pub fn uci_loop_with_engine_id<F>(stdout_log: Arc<Mutex<StdoutLog>>, engine_id:
             }
         }
     }
-    match engine {
-        Some(engine) => {
-            engine.quit();
-            engine.join_thread();
-        },
-        None => (),
-    }
+    engine.quit();
+    engine.join_thread();
     match err {
         Some(err) => Err(err),
         None => Ok(()),
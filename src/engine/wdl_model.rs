@@ -0,0 +1,92 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::engine::eval::*;
+use crate::engine::fathom;
+
+/// A structure of WDL statistics.
+///
+/// The WDL statistics contain expected numbers of wins, draws, and losses for the side to move in
+/// permille. A sum of these numbers is always equal to 1000.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct WdlStats
+{
+    /// An expected number of wins in permille.
+    pub win: u32,
+    /// An expected number of draws in permille.
+    pub draw: u32,
+    /// An expected number of losses in permille.
+    pub loss: u32,
+}
+
+impl WdlStats
+{
+    /// Creates WDL statistics.
+    pub fn new(win: u32, draw: u32, loss: u32) -> Self
+    { WdlStats { win, draw, loss, } }
+
+    /// Creates exact WDL statistics from a result of the Syzygy endgame tablebases.
+    ///
+    /// A cursed win and a blessed loss are draws by the fifty-move rule.
+    pub fn from_tb_wdl(wdl: fathom::Wdl) -> Self
+    {
+        match wdl {
+            fathom::Wdl::Win => WdlStats::new(1000, 0, 0),
+            fathom::Wdl::Loss => WdlStats::new(0, 0, 1000),
+            _ => WdlStats::new(0, 1000, 0),
+        }
+    }
+}
+
+/// A structure of WDL model.
+///
+/// The WDL model converts a search value in centipawns to WDL statistics. A probability of win is
+/// the logistic function `1 / (1 + exp((a - value) / b))` and a probability of loss is the logistic
+/// function `1 / (1 + exp((a + value) / b))`, where the parameters `a` and `b` should be fitted
+/// from results of games. The parameter `a` is a value for that the win is as likely as the rest
+/// and the parameter `b` is a spread of the logistic function. Checkmate values and values of
+/// tablebase wins or losses are converted to certain wins or losses.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct WdlModel
+{
+    /// A value in centipawns for that the win is as likely as the rest.
+    pub a: f64,
+    /// A spread of the logistic function in centipawns.
+    pub b: f64,
+}
+
+impl WdlModel
+{
+    /// A default parameter `a` of WDL model.
+    pub const DEFAULT_A: f64 = 200.0;
+
+    /// A default parameter `b` of WDL model.
+    pub const DEFAULT_B: f64 = 100.0;
+
+    /// Creates a WDL model.
+    pub fn new(a: f64, b: f64) -> Self
+    { WdlModel { a, b, } }
+
+    /// Converts the value to WDL statistics.
+    pub fn wdl(&self, value: i32) -> WdlStats
+    {
+        if value >= MAX_EVAL_TB_VALUE - 128 {
+            return WdlStats::new(1000, 0, 0);
+        }
+        if value <= MIN_EVAL_TB_VALUE + 128 {
+            return WdlStats::new(0, 0, 1000);
+        }
+        let value = value as f64;
+        let win = (1000.0 / (1.0 + ((self.a - value) / self.b).exp())).round().clamp(0.0, 1000.0) as u32;
+        let loss = (1000.0 / (1.0 + ((self.a + value) / self.b).exp())).round().clamp(0.0, 1000.0) as u32;
+        let loss = loss.min(1000 - win);
+        WdlStats::new(win, 1000 - win - loss, loss)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,36 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use super::*;
+
+#[test]
+fn test_wdl_model_wdl_converts_values_to_wdl_stats()
+{
+    let wdl_model = WdlModel::new(WdlModel::DEFAULT_A, WdlModel::DEFAULT_B);
+    let wdl = wdl_model.wdl(0);
+    assert_eq!(1000, wdl.win + wdl.draw + wdl.loss);
+    assert_eq!(wdl.win, wdl.loss);
+    assert_eq!(WdlStats::new(119, 762, 119), wdl);
+    assert_eq!(WdlStats::new(500, 482, 18), wdl_model.wdl(200));
+    assert_eq!(WdlStats::new(18, 482, 500), wdl_model.wdl(-200));
+    let wdl = wdl_model.wdl(5000);
+    assert_eq!(1000, wdl.win + wdl.draw + wdl.loss);
+    assert_eq!(WdlStats::new(1000, 0, 0), wdl_model.wdl(MAX_EVAL_TB_VALUE));
+    assert_eq!(WdlStats::new(1000, 0, 0), wdl_model.wdl(MAX_EVAL_MATE_VALUE + 3));
+    assert_eq!(WdlStats::new(0, 0, 1000), wdl_model.wdl(MIN_EVAL_TB_VALUE + 2));
+    assert_eq!(WdlStats::new(0, 0, 1000), wdl_model.wdl(MIN_EVAL_ROOT_MATE_VALUE));
+}
+
+#[test]
+fn test_wdl_stats_from_tb_wdl_creates_exact_wdl_stats()
+{
+    assert_eq!(WdlStats::new(1000, 0, 0), WdlStats::from_tb_wdl(fathom::Wdl::Win));
+    assert_eq!(WdlStats::new(0, 1000, 0), WdlStats::from_tb_wdl(fathom::Wdl::CursedWin));
+    assert_eq!(WdlStats::new(0, 1000, 0), WdlStats::from_tb_wdl(fathom::Wdl::Draw));
+    assert_eq!(WdlStats::new(0, 1000, 0), WdlStats::from_tb_wdl(fathom::Wdl::BlessedLoss));
+    assert_eq!(WdlStats::new(0, 0, 1000), WdlStats::from_tb_wdl(fathom::Wdl::Loss));
+}
@@ -16,6 +16,7 @@ use std::time::Duration;
 use crate::chess::types::OutcomeFilter;
 use crate::chess::board::PrettyStyle;
 use crate::chess::moves::Style;
+use crate::chess::movegen::legal;
 use crate::chess::Board;
 use crate::chess::Color;
 use crate::chess::DrawReason;
@@ -25,12 +26,18 @@ use crate::chess::Outcome;
 use crate::chess::WinReason;
 use crate::engine::engine::*;
 use crate::engine::engine_id::*;
+use crate::engine::engine_option::*;
 use crate::engine::io::*;
 use crate::engine::print::*;
+use crate::engine::thinker::*;
+use crate::engine::transposition_table::*;
 use crate::engine::utils::*;
 use crate::engine::LoopError;
 use crate::engine::LoopResult;
 
+/// A number of moves of neural network that are listed by the `bk` command.
+const BK_MOVE_COUNT: usize = 5;
+
 fn color_to_str(color: Color) -> &'static str
 {
     match color {
@@ -68,8 +75,8 @@ fn write_outcome(w: &mut dyn Write, outcome: Outcome) -> Result<()>
 
 /// A structure of Xboard printer.
 ///
-/// The Xboard printer prints a line of principal variation, a best move, and a game outcome for
-/// the Xboard protocol.
+/// The Xboard printer prints a line of principal variation, a best move, a game outcome,
+/// resignation, and a draw offer for the Xboard protocol.
 #[derive(Copy, Clone, Debug)]
 pub struct XboardPrinter;
 
@@ -82,11 +89,16 @@ impl XboardPrinter
 
 impl Print for XboardPrinter
 {
-    fn print_pv(&self, w: &mut dyn Write, board: &Board, depth: usize, value: i32, time: Duration, node_count: u64, pv: &[Move]) -> Result<()>
+    fn print_pv(&self, w: &mut dyn Write, board: &Board, line: &PvLine) -> Result<()>
     {
-        write!(w, "{} {} {} {}", depth, value, time.as_millis() / 10, node_count)?;
+        let score = match line.half_move_count_to_checkmate {
+            Some(n) if n > 0 => 100000 + (n + 1) / 2,
+            Some(n) => -100000 + n / 2,
+            None => line.value,
+        };
+        write!(w, "{} {} {} {}", line.depth, score, line.time.as_millis() / 10, line.node_count)?;
         let mut tmp_board = board.clone();
-        for mv in pv {
+        for mv in line.pv {
             match mv.styled(&tmp_board, Style::San) {
                 Ok(style_move) => {
                     write!(w, " {}", style_move)?;
@@ -101,12 +113,21 @@ impl Print for XboardPrinter
         writeln!(w, "")?;
         Ok(())
     }
+
+    fn print_progress(&self, _w: &mut dyn Write, _board: &Board, _progress: &SearchProgress) -> Result<()>
+    { Ok(()) }
     
-    fn print_best_move(&self, w: &mut dyn Write, _board: &Board, mv: Move) -> Result<()>
+    fn print_best_move(&self, w: &mut dyn Write, _board: &Board, mv: Move, _ponder_move: Option<Move>) -> Result<()>
     { writeln!(w, "move {}", mv.uci()) }
     
     fn print_outcome(&self, w: &mut dyn Write, outcome: Outcome) -> Result<()>
     { write_outcome(w, outcome) }
+
+    fn print_resign(&self, w: &mut dyn Write) -> Result<()>
+    { writeln!(w, "resign") }
+
+    fn print_draw_offer(&self, w: &mut dyn Write) -> Result<()>
+    { writeln!(w, "offer draw") }
 }
 
 fn xboard_protover_for_pre_init(stdout_log: &Arc<Mutex<StdoutLog>>) -> Result<()>
@@ -117,14 +138,14 @@ fn xboard_protover_for_pre_init(stdout_log: &Arc<Mutex<StdoutLog>>) -> Result<()
     Ok(())
 }
 
-fn xboard_protover_for_post_init(stdout_log: &Arc<Mutex<StdoutLog>>, engine_id: EngineId) -> Result<()>
+fn xboard_protover_for_post_init(stdout_log: &Arc<Mutex<StdoutLog>>, engine_id: EngineId, engine: &Engine) -> Result<()>
 {
     let mut stdout_log_g = stdout_log.lock().unwrap();
     writeln!(&mut *stdout_log_g, "feature ping=1")?;
     writeln!(&mut *stdout_log_g, "feature setboard=1")?;
     writeln!(&mut *stdout_log_g, "feature playother=1")?;
     writeln!(&mut *stdout_log_g, "feature time=1")?;
-    writeln!(&mut *stdout_log_g, "feature draw=0")?;
+    writeln!(&mut *stdout_log_g, "feature draw=1")?;
     writeln!(&mut *stdout_log_g, "feature sigint=0")?;
     writeln!(&mut *stdout_log_g, "feature sigterm=0")?;
     writeln!(&mut *stdout_log_g, "feature reuse=1")?;
@@ -133,6 +154,36 @@ fn xboard_protover_for_post_init(stdout_log: &Arc<Mutex<StdoutLog>>, engine_id:
     writeln!(&mut *stdout_log_g, "feature variants=\"normal\"")?;
     writeln!(&mut *stdout_log_g, "feature colors=0")?;
     writeln!(&mut *stdout_log_g, "feature name=0")?;
+    writeln!(&mut *stdout_log_g, "feature memory=1")?;
+    writeln!(&mut *stdout_log_g, "feature smp=1")?;
+    writeln!(&mut *stdout_log_g, "feature egt=\"syzygy\"")?;
+    writeln!(&mut *stdout_log_g, "feature exclude=1")?;
+    writeln!(&mut *stdout_log_g, "feature setscore=1")?;
+    for option in &engine_options(engine) {
+        if let Some(name) = option.xboard_name {
+            match &option.option_type {
+                OptionType::Check(default) => writeln!(&mut *stdout_log_g, "feature option=\"{} -check {}\"", name, if *default { 1 } else { 0 })?,
+                OptionType::Spin(default, min, max) => writeln!(&mut *stdout_log_g, "feature option=\"{} -spin {} {} {}\"", name, default, min, max)?,
+                OptionType::String(default) => writeln!(&mut *stdout_log_g, "feature option=\"{} -string {}\"", name, default)?,
+                OptionType::File(default) => writeln!(&mut *stdout_log_g, "feature option=\"{} -file {}\"", name, default)?,
+                OptionType::Path(default) => writeln!(&mut *stdout_log_g, "feature option=\"{} -path {}\"", name, default)?,
+                OptionType::Combo(default, values) => {
+                    write!(&mut *stdout_log_g, "feature option=\"{} -combo", name)?;
+                    for (i, value) in values.iter().enumerate() {
+                        if i > 0 {
+                            write!(&mut *stdout_log_g, " ///")?;
+                        }
+                        if value == default {
+                            write!(&mut *stdout_log_g, " *{}", value)?;
+                        } else {
+                            write!(&mut *stdout_log_g, " {}", value)?;
+                        }
+                    }
+                    writeln!(&mut *stdout_log_g, "\"")?;
+                },
+            }
+        }
+    }
     writeln!(&mut *stdout_log_g, "feature done=1")?;
     stdout_log_g.flush()?;
     Ok(())
@@ -144,7 +195,9 @@ struct Context
     depth: Option<usize>,
     has_force: bool,
     has_analysis: bool,
+    can_ponder: bool,
     can_print_pv: bool,
+    excluded_moves: Vec<Move>,
     analysis_commands: HashMap<String, (fn(&Arc<Mutex<StdoutLog>>, &mut Context, &[&str], &str) -> Result<bool>, Option<usize>, Option<usize>)>,
 }
 
@@ -157,7 +210,9 @@ impl Context
             depth: None,
             has_force: false,
             has_analysis: false,
+            can_ponder: false,
             can_print_pv: false,
+            excluded_moves: Vec::new(),
             analysis_commands,
         }
     }
@@ -179,6 +234,14 @@ fn xboard_error(stdout_log: &Arc<Mutex<StdoutLog>>, err_type: &str, cmd: &str) -
     Ok(())
 }
 
+fn xboard_load_network(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, cmd: &str) -> Result<()>
+{
+    match context.engine.load_network() {
+        Ok(()) => Ok(()),
+        Err(err) => xboard_error(stdout_log, format!("can't load network: {}", err).as_str(), cmd),
+    }
+}
+
 fn xboard_outcome(stdout_log: &Arc<Mutex<StdoutLog>>, outcome: Outcome) -> Result<()>
 {
     let mut stdout_log_g = stdout_log.lock().unwrap();
@@ -204,18 +267,26 @@ fn initialize_commands(cmds: &mut HashMap<String, (fn(&Arc<Mutex<StdoutLog>>, &m
     cmds.insert(String::from("otim"), (xboard_ignore, Some(1), Some(1)));
     cmds.insert(String::from("?"), (xboard_question, Some(0), Some(0)));
     cmds.insert(String::from("ping"), (xboard_ping, Some(1), Some(1)));
-    cmds.insert(String::from("result"), (xboard_ignore, Some(2), None));
+    cmds.insert(String::from("result"), (xboard_result, Some(1), None));
     cmds.insert(String::from("setboard"), (xboard_setboard, Some(4), Some(6)));
-    cmds.insert(String::from("hint"), (xboard_ignore, Some(0), Some(0)));
+    cmds.insert(String::from("hint"), (xboard_hint, Some(0), Some(0)));
     cmds.insert(String::from("bk"), (xboard_bk, Some(0), Some(0)));
     cmds.insert(String::from("undo"), (xboard_undo, Some(0), Some(0)));
     cmds.insert(String::from("remove"), (xboard_remove, Some(0), Some(0)));
-    cmds.insert(String::from("hard"), (xboard_ignore, Some(0), Some(0)));
-    cmds.insert(String::from("easy"), (xboard_ignore, Some(0), Some(0)));
+    cmds.insert(String::from("hard"), (xboard_hard, Some(0), Some(0)));
+    cmds.insert(String::from("easy"), (xboard_easy, Some(0), Some(0)));
     cmds.insert(String::from("post"), (xboard_post, Some(0), Some(0)));
     cmds.insert(String::from("nopost"), (xboard_nopost, Some(0), Some(0)));
     cmds.insert(String::from("analyze"), (xboard_analyze, Some(0), Some(0)));
     cmds.insert(String::from("display"), (xboard_display, Some(0), Some(0)));
+    cmds.insert(String::from("option"), (xboard_option, Some(1), None));
+    cmds.insert(String::from("egtpath"), (xboard_egtpath, Some(2), None));
+    cmds.insert(String::from("memory"), (xboard_memory, Some(1), Some(1)));
+    cmds.insert(String::from("cores"), (xboard_cores, Some(1), Some(1)));
+    cmds.insert(String::from("exclude"), (xboard_exclude, Some(1), Some(1)));
+    cmds.insert(String::from("include"), (xboard_include, Some(1), Some(1)));
+    cmds.insert(String::from("setscore"), (xboard_setscore, Some(2), Some(2)));
+    cmds.insert(String::from("draw"), (xboard_draw, Some(0), Some(0)));
 }
 
 fn initialize_analysis_commands(cmds: &mut HashMap<String, (fn(&Arc<Mutex<StdoutLog>>, &mut Context, &[&str], &str) -> Result<bool>, Option<usize>, Option<usize>)>)
@@ -225,16 +296,53 @@ fn initialize_analysis_commands(cmds: &mut HashMap<String, (fn(&Arc<Mutex<Stdout
     cmds.insert(String::from("setboard"), (xboard_new, Some(6), Some(6)));
     cmds.insert(String::from("exit"), (xboard_exit, Some(0), Some(0)));
     cmds.insert(String::from("."), (xboard_dot, Some(0), Some(0)));
-    cmds.insert(String::from("hint"), (xboard_ignore, Some(0), Some(0)));
+    cmds.insert(String::from("hint"), (xboard_hint, Some(0), Some(0)));
     cmds.insert(String::from("bk"), (xboard_bk, Some(0), Some(0)));
     cmds.insert(String::from("quit"), (xboard_quit, Some(0), Some(0)));
     cmds.insert(String::from("ping"), (xboard_ping, Some(1), Some(1)));
+    cmds.insert(String::from("option"), (xboard_option, Some(1), None));
+    cmds.insert(String::from("egtpath"), (xboard_egtpath, Some(2), None));
+    cmds.insert(String::from("memory"), (xboard_memory, Some(1), Some(1)));
+    cmds.insert(String::from("cores"), (xboard_cores, Some(1), Some(1)));
+    cmds.insert(String::from("exclude"), (xboard_exclude, Some(1), Some(1)));
+    cmds.insert(String::from("include"), (xboard_include, Some(1), Some(1)));
+    cmds.insert(String::from("setscore"), (xboard_setscore, Some(2), Some(2)));
 }
 
 fn xboard_go_for_engine(context: &mut Context)
 {
     let depth = if !context.has_analysis { context.depth } else { None };
-    context.engine.go(None, depth, None, None, !context.has_analysis, !context.has_analysis, context.can_print_pv || context.has_analysis, !context.has_analysis);
+    let search_moves = if context.has_analysis && !context.excluded_moves.is_empty() {
+        context.engine.stop();
+        let excluded_moves = &context.excluded_moves;
+        let moves: Vec<Move> = context.engine.do_move_chain(|move_chain| {
+                legal::gen_all(move_chain.last()).iter().filter(|mv| !excluded_moves.contains(mv)).copied().collect()
+        });
+        if moves.is_empty() {
+            return;
+        }
+        Some(moves)
+    } else {
+        None
+    };
+    let params = ThinkingParams {
+        search_moves,
+        depth,
+        can_make_best_move: !context.has_analysis,
+        can_ponder: context.can_ponder && !context.has_analysis,
+        can_print_pv: context.can_print_pv || context.has_analysis,
+        can_print_best_move_and_outcome: !context.has_analysis,
+        ..ThinkingParams::new()
+    };
+    context.engine.go_with_ponder(params, !context.has_analysis, false);
+}
+
+fn xboard_stop_pondering(context: &mut Context)
+{
+    if context.engine.thinker().is_pondering() {
+        context.engine.stop();
+        context.engine.thinker().wait();
+    }
 }
 
 fn xboard_ignore(_stdout_log: &Arc<Mutex<StdoutLog>>, _context: &mut Context, _args: &[&str], _cmd: &str) -> Result<bool>
@@ -242,6 +350,7 @@ fn xboard_ignore(_stdout_log: &Arc<Mutex<StdoutLog>>, _context: &mut Context, _a
 
 fn xboard_new(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _args: &[&str], cmd: &str) -> Result<bool>
 {
+    xboard_stop_pondering(context);
     if context.has_analysis {
         context.engine.stop();
     } else {
@@ -251,9 +360,15 @@ fn xboard_new(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _args:
         }
         context.has_force = false;
     }
+    xboard_load_network(stdout_log, context, cmd)?;
     context.engine.do_move_chain(|move_chain| {
             *move_chain = MoveChain::new_initial();
     });
+    context.excluded_moves.clear();
+    context.engine.thinker().clear_game_values();
+    if let Some(transposition_table) = context.engine.thinker().transposition_table() {
+        transposition_table.clear();
+    }
     if context.has_analysis {
         xboard_go_for_engine(context);
     }
@@ -265,6 +380,7 @@ fn xboard_quit(_stdout_log: &Arc<Mutex<StdoutLog>>, _context: &mut Context, _arg
 
 fn xboard_force(_stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _args: &[&str], _cmd: &str) -> Result<bool>
 {
+    xboard_stop_pondering(context);
     context.has_force = true;
     Ok(false)
 }
@@ -278,6 +394,7 @@ fn xboard_go(_stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _args:
 
 fn xboard_playother(_stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _args: &[&str], _cmd: &str) -> Result<bool>
 {
+    xboard_stop_pondering(context);
     context.has_force = false;
     Ok(false)
 }
@@ -345,6 +462,7 @@ fn xboard_ping(stdout_log: &Arc<Mutex<StdoutLog>>, _context: &mut Context, args:
 
 fn xboard_setboard(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, args: &[&str], cmd: &str) -> Result<bool>
 {
+    xboard_stop_pondering(context);
     if context.has_analysis {
         context.engine.stop();
     } else {
@@ -371,6 +489,8 @@ fn xboard_setboard(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, ar
             Ok::<bool, Error>(true)
     })?;
     if is_set_board {
+        context.excluded_moves.clear();
+        context.engine.thinker().clear_game_values();
         if context.has_analysis {
             xboard_go_for_engine(context);
         }
@@ -378,10 +498,61 @@ fn xboard_setboard(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, ar
     Ok(false)
 }
 
-fn xboard_bk(stdout_log: &Arc<Mutex<StdoutLog>>, _context: &mut Context, _args: &[&str], _cmd: &str) -> Result<bool>
+fn xboard_board(context: &Context) -> Option<Board>
+{
+    match context.engine.thinker().pondered_move() {
+        Some((board, _)) => Some(board),
+        None if context.engine.is_stopped() => Some(context.engine.do_move_chain(|move_chain| move_chain.last().clone())),
+        None => None,
+    }
+}
+
+fn xboard_hint(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _args: &[&str], _cmd: &str) -> Result<bool>
+{
+    if let Some(mv) = xboard_board(context).and_then(|board| context.engine.thinker().hint_move(&board)) {
+        let mut stdout_log_g = stdout_log.lock().unwrap();
+        writeln!(&mut *stdout_log_g, "Hint: {}", mv.uci())?;
+        stdout_log_g.flush()?;
+    }
+    Ok(false)
+}
+
+fn xboard_bk(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _args: &[&str], _cmd: &str) -> Result<bool>
 {
+    let mut lines: Vec<String> = Vec::new();
+    if let Some(board) = xboard_board(context) {
+        let book_moves = {
+            let book_g = context.engine.thinker().book().lock().unwrap();
+            match &*book_g {
+                Some(book) => book.moves(&board),
+                None => Vec::new(),
+            }
+        };
+        if !book_moves.is_empty() {
+            let weight_sum: u64 = book_moves.iter().map(|(_, weight)| *weight as u64).sum();
+            for (mv, weight) in &book_moves {
+                let percent = if weight_sum > 0 { (*weight as f64) * 100.0 / (weight_sum as f64) } else { 0.0 };
+                if let Ok(style_move) = mv.styled(&board, Style::San) {
+                    lines.push(format!(" {} {} ({:.1}%)", style_move, weight, percent));
+                }
+            }
+        } else if context.engine.is_stopped() {
+            let mut move_probs = context.engine.thinker().searcher().move_probs(&board);
+            move_probs.sort_by(|(_, prob1), (_, prob2)| prob2.total_cmp(prob1));
+            for (mv, prob) in move_probs.iter().take(BK_MOVE_COUNT) {
+                if let Ok(style_move) = mv.styled(&board, Style::San) {
+                    lines.push(format!(" {} {:.1}%", style_move, prob * 100.0));
+                }
+            }
+        }
+    }
     let mut stdout_log_g = stdout_log.lock().unwrap();
-    writeln!(&mut *stdout_log_g, " ")?;
+    if lines.is_empty() {
+        writeln!(&mut *stdout_log_g, " ")?;
+    }
+    for line in &lines {
+        writeln!(&mut *stdout_log_g, "{}", line)?;
+    }
     writeln!(&mut *stdout_log_g, "")?;
     stdout_log_g.flush()?;
     Ok(false)
@@ -389,6 +560,7 @@ fn xboard_bk(stdout_log: &Arc<Mutex<StdoutLog>>, _context: &mut Context, _args:
 
 fn xboard_undo(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _args: &[&str], cmd: &str) -> Result<bool>
 {
+    xboard_stop_pondering(context);
     if context.has_analysis {
         context.engine.stop();
     } else {
@@ -400,6 +572,7 @@ fn xboard_undo(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _args:
     context.engine.do_move_chain(|move_chain| {
             move_chain.pop();
     });
+    context.excluded_moves.clear();
     if context.has_analysis {
         xboard_go_for_engine(context);
     }
@@ -408,6 +581,7 @@ fn xboard_undo(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _args:
 
 fn xboard_remove(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _args: &[&str], cmd: &str) -> Result<bool>
 {
+    xboard_stop_pondering(context);
     if !context.engine.is_stopped() {
         xboard_error(stdout_log, "locked move chain", cmd)?;
         return Ok(false);
@@ -416,6 +590,36 @@ fn xboard_remove(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _arg
             move_chain.pop();
             move_chain.pop();
     });
+    context.excluded_moves.clear();
+    Ok(false)
+}
+
+fn xboard_result(_stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _args: &[&str], _cmd: &str) -> Result<bool>
+{
+    xboard_stop_pondering(context);
+    Ok(false)
+}
+
+fn xboard_draw(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _args: &[&str], _cmd: &str) -> Result<bool>
+{
+    if context.engine.thinker().can_accept_draw() {
+        let mut stdout_log_g = stdout_log.lock().unwrap();
+        writeln!(&mut *stdout_log_g, "offer draw")?;
+        stdout_log_g.flush()?;
+    }
+    Ok(false)
+}
+
+fn xboard_hard(_stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _args: &[&str], _cmd: &str) -> Result<bool>
+{
+    context.can_ponder = true;
+    Ok(false)
+}
+
+fn xboard_easy(_stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _args: &[&str], _cmd: &str) -> Result<bool>
+{
+    context.can_ponder = false;
+    xboard_stop_pondering(context);
     Ok(false)
 }
 
@@ -431,8 +635,168 @@ fn xboard_nopost(_stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _ar
     Ok(false)
 }
 
-fn xboard_analyze(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _args: &[&str], _cmd: &str) -> Result<bool>
+fn xboard_option(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, args: &[&str], cmd: &str) -> Result<bool>
 {
+    let arg = args.join(" ");
+    let (name, value) = match arg.split_once('=') {
+        Some((name, value)) => (name, value),
+        None => (arg.as_str(), ""),
+    };
+    match engine_options(&context.engine).iter().find(|option| option.xboard_name == Some(name)) {
+        Some(option) => {
+            match option.parse_value(value, true).and_then(|option_value| (option.set)(&mut context.engine, &option_value)) {
+                Ok(()) => {
+                    if option.can_restart_analysis && context.has_analysis {
+                        xboard_go_for_engine(context);
+                    }
+                },
+                Err(err) => xboard_error(stdout_log, format!("{}", err).as_str(), cmd)?,
+            }
+        },
+        None => xboard_error(stdout_log, "unknown option", cmd)?,
+    }
+    Ok(false)
+}
+
+fn xboard_egtpath(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, args: &[&str], cmd: &str) -> Result<bool>
+{
+    if args[0] == "syzygy" {
+        match load_syzygy(context.engine.thinker(), args[1..].join(" ").as_str()) {
+            Ok(()) => {
+                if context.has_analysis {
+                    xboard_go_for_engine(context);
+                }
+            },
+            Err(err) => xboard_error(stdout_log, format!("{}", err).as_str(), cmd)?,
+        }
+    } else {
+        xboard_error(stdout_log, "unsupported tablebases", cmd)?;
+    }
+    Ok(false)
+}
+
+fn xboard_memory(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, args: &[&str], cmd: &str) -> Result<bool>
+{
+    match args[0].parse::<usize>() {
+        Ok(size) => context.engine.thinker().searcher().resize(size.clamp(TranspositionTable::MIN_SIZE, TranspositionTable::MAX_SIZE)),
+        Err(_) => xboard_error(stdout_log, "invalid number", cmd)?,
+    }
+    Ok(false)
+}
+
+fn xboard_cores(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, args: &[&str], cmd: &str) -> Result<bool>
+{
+    match args[0].parse::<usize>() {
+        Ok(thread_count) => {
+            match context.engine.thinker().searcher().config() {
+                Some(config) => config.set_thread_count(thread_count),
+                None => xboard_error(stdout_log, "unsupported option", cmd)?,
+            }
+        },
+        Err(_) => xboard_error(stdout_log, "invalid number", cmd)?,
+    }
+    Ok(false)
+}
+
+fn xboard_exclude_or_include(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, arg: &str, cmd: &str, is_exclusion: bool) -> Result<()>
+{
+    xboard_stop_pondering(context);
+    if context.has_analysis {
+        context.engine.stop();
+    } else {
+        if !context.engine.is_stopped() {
+            xboard_error(stdout_log, "locked move chain", cmd)?;
+            return Ok(());
+        }
+    }
+    let excluded_moves = &mut context.excluded_moves;
+    context.engine.do_move_chain(|move_chain| {
+            if arg == "all" {
+                if is_exclusion {
+                    *excluded_moves = legal::gen_all(move_chain.last()).iter().copied().collect();
+                } else {
+                    excluded_moves.clear();
+                }
+            } else {
+                let mv = match Move::from_uci_legal(arg, move_chain.last()) {
+                    Ok(tmp_mv) => tmp_mv,
+                    Err(_) => {
+                        match Move::from_san(arg, move_chain.last()) {
+                            Ok(tmp_mv) => tmp_mv,
+                            Err(_) => {
+                                xboard_error(stdout_log, "illegal move", cmd)?;
+                                return Ok::<(), Error>(());
+                            },
+                        }
+                    },
+                };
+                if is_exclusion {
+                    if !excluded_moves.contains(&mv) {
+                        excluded_moves.push(mv);
+                    }
+                } else {
+                    excluded_moves.retain(|mv2| *mv2 != mv);
+                }
+            }
+            Ok::<(), Error>(())
+    })?;
+    if context.has_analysis {
+        xboard_go_for_engine(context);
+    }
+    Ok(())
+}
+
+fn xboard_exclude(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, args: &[&str], cmd: &str) -> Result<bool>
+{
+    xboard_exclude_or_include(stdout_log, context, args[0], cmd, true)?;
+    Ok(false)
+}
+
+fn xboard_include(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, args: &[&str], cmd: &str) -> Result<bool>
+{
+    xboard_exclude_or_include(stdout_log, context, args[0], cmd, false)?;
+    Ok(false)
+}
+
+fn xboard_setscore(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, args: &[&str], cmd: &str) -> Result<bool>
+{
+    let value = match args[0].parse::<i32>() {
+        Ok(tmp_value) => tmp_value,
+        Err(_) => {
+            xboard_error(stdout_log, "invalid number", cmd)?;
+            return Ok(false);
+        },
+    };
+    let depth = match args[1].parse::<usize>() {
+        Ok(tmp_depth) => tmp_depth,
+        Err(_) => {
+            xboard_error(stdout_log, "invalid number", cmd)?;
+            return Ok(false);
+        },
+    };
+    xboard_stop_pondering(context);
+    if context.has_analysis {
+        context.engine.stop();
+    } else {
+        if !context.engine.is_stopped() {
+            xboard_error(stdout_log, "locked move chain", cmd)?;
+            return Ok(false);
+        }
+    }
+    let zobrist_hash = context.engine.do_move_chain(|move_chain| move_chain.last().zobrist_hash());
+    if let Some(transposition_table) = context.engine.thinker().transposition_table() {
+        transposition_table.set_fixed_value(zobrist_hash, value, depth);
+    }
+    if context.has_analysis {
+        xboard_go_for_engine(context);
+    }
+    Ok(false)
+}
+
+fn xboard_analyze(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _args: &[&str], cmd: &str) -> Result<bool>
+{
+    xboard_stop_pondering(context);
+    xboard_load_network(stdout_log, context, cmd)?;
     context.has_analysis = true;
     xboard_go_for_engine(context);
     loop {
@@ -516,6 +880,17 @@ fn xboard_display(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, _ar
 
 fn xboard_make_move(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, s: &str) -> Result<()>
 {
+    if let Some((board, ponder_move)) = context.engine.thinker().pondered_move() {
+        let mv = match Move::from_uci_legal(s, &board) {
+            Ok(tmp_mv) => Some(tmp_mv),
+            Err(_) => Move::from_san(s, &board).ok(),
+        };
+        if mv == Some(ponder_move) {
+            context.engine.ponderhit();
+            return Ok(());
+        }
+        xboard_stop_pondering(context);
+    }
     if context.has_analysis {
         context.engine.stop();
     } else {
@@ -553,6 +928,7 @@ fn xboard_make_move(stdout_log: &Arc<Mutex<StdoutLog>>, context: &mut Context, s
             Ok(true)
     })?;
     if is_made_move {
+        context.excluded_moves.clear();
         if !context.has_force || context.has_analysis {
             xboard_go_for_engine(context);
         }
@@ -615,10 +991,18 @@ pub fn xboard_loop_with_engine_id<F>(stdout_log: Arc<Mutex<StdoutLog>>, engine_i
                             },
                         }
                     }
-                    match xboard_protover_for_post_init(&stdout_log, engine_id) {
-                        Ok(_) => (),
-                        Err(err2) => {
-                            err = Some(LoopError::Io(err2));
+                    match &context {
+                        Some(context) => {
+                            match xboard_protover_for_post_init(&stdout_log, engine_id, &context.engine) {
+                                Ok(_) => (),
+                                Err(err2) => {
+                                    err = Some(LoopError::Io(err2));
+                                    break;
+                                },
+                            }
+                        },
+                        None => {
+                            err = Some(LoopError::UninitializedLoopContext);
                             break;
                         },
                     }
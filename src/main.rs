@@ -10,6 +10,7 @@ use std::io::Write;
 use std::process::exit;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
 use clap::Parser;
 use clap::ValueEnum;
 use neurina::matrix::Matrix;
@@ -25,7 +26,24 @@ enum NetworkVersion
     V3,
 }
 
-#[derive(Parser, Debug)]
+#[derive(ValueEnum, Copy, Clone, Debug)]
+#[clap(rename_all = "lower")]
+enum Backend
+{
+    Cpu,
+    OpenCl,
+    Cuda,
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug)]
+#[clap(rename_all = "lower")]
+enum Searcher
+{
+    One,
+    Mcts,
+}
+
+#[derive(Parser, Clone, Debug)]
 #[command(version)]
 struct Args
 {
@@ -44,202 +62,413 @@ struct Args
     /// Load Syzygy endgame tablebases
     #[arg(short, long, value_name = "SYZYGY_PATH")]
     syzygy: Option<String>,
+    /// Load opening book
+    #[arg(short, long, value_name = "BOOK_FILE")]
+    book: Option<String>,
+    /// Maximal depth of opening book in half moves
+    #[arg(long, value_name = "DEPTH")]
+    book_max_depth: Option<usize>,
+    /// Select book moves with greatest weights
+    #[arg(long)]
+    book_best_move: bool,
     /// Network version
     #[arg(short = 'v', long, value_name = "VERSION", value_enum, default_value_t = NetworkVersion::V3)]
     network_version: NetworkVersion,
+    /// Size of transposition table or tree of Monte-Carlo tree search in megabytes
+    #[arg(long, value_name = "SIZE", default_value_t = TranspositionTable::DEFAULT_SIZE)]
+    hash: usize,
+    /// Backend kind
+    #[arg(long, value_enum)]
+    backend: Option<Backend>,
+    /// Depth of middle search
+    #[arg(long, value_name = "DEPTH")]
+    middle_depth: Option<usize>,
+    /// Range of random component of evaluation function
+    #[arg(long, value_name = "RANGE")]
+    eval_random_range: Option<i32>,
+    /// Move epsilon for neural search
+    #[arg(long, value_name = "EPSILON")]
+    move_eps: Option<f32>,
+    /// Beam width for neural search
+    #[arg(long, value_name = "WIDTH")]
+    beam_width: Option<usize>,
+    /// Depth of beam search for neural search
+    #[arg(long, value_name = "DEPTH")]
+    beam_depth: Option<usize>,
+    /// Searcher
+    #[arg(long, value_enum, default_value_t = Searcher::One)]
+    searcher: Searcher,
+    /// Number of search threads
+    #[arg(long, value_name = "NUMBER", default_value_t = 1)]
+    threads: usize,
+    /// Move overhead in milliseconds
+    #[arg(long, value_name = "MILLISECONDS")]
+    move_overhead: Option<u64>,
 }
 
-const MIDDLE_DEPTH: usize = 2;
-
-const RANDOM_EVAL_FUN_RANGE: i32 = 5;
-
-fn initialize_engine_v1(args: &Args, config: &Option<Config>, writer: Arc<Mutex<dyn Write + Send + Sync>>, printer: Arc<dyn Print + Send + Sync>) -> LoopResult<Engine>
+fn engine_params(args: &Args, config: &Option<Config>) -> (usize, i32, f32)
 {
-    match initialize_backend(config) {
-        Ok(()) => (),
-        Err(err) => return Err(LoopError::Matrix(err)),
-    }
-    let converter = Converter::new(IndexConverter::new());
-    let network = match args.random_network {
-        Some(count) => {
-            let mut iw_elems = vec![0.0f32; count * Converter::BOARD_ROW_COUNT];
-            xavier_init(iw_elems.as_mut_slice(), Converter::BOARD_ROW_COUNT, count);
-            let iw = Matrix::new_with_elems(count, Converter::BOARD_ROW_COUNT, iw_elems.as_slice());
-            let mut ib_elems = vec![0.0f32; count];
-            xavier_init(ib_elems.as_mut_slice(), Converter::BOARD_ROW_COUNT, count);
-            let ib = Matrix::new_with_elems(count, 1, ib_elems.as_slice());
-            let mut sw_elems = vec![0.0f32; count * count];
-            xavier_init(sw_elems.as_mut_slice(), count, count);
-            let sw = Matrix::new_with_elems(count, count, sw_elems.as_slice());
-            let mut sb_elems = vec![0.0f32; count];
-            xavier_init(sb_elems.as_mut_slice(), count, count);
-            let sb = Matrix::new_with_elems(count, 1, sb_elems.as_slice());
-            let mut pw_elems = vec![0.0f32; count * count];
-            xavier_init(pw_elems.as_mut_slice(), count, count);
-            let pw = Matrix::new_with_elems(count, count, pw_elems.as_slice());
-            let mut pb_elems = vec![0.0f32; count];
-            xavier_init(pb_elems.as_mut_slice(), count, count);
-            let pb = Matrix::new_with_elems(count, 1, pb_elems.as_slice());
-            let mut ow_elems = vec![0.0f32; converter.move_row_count() * count];
-            xavier_init(ow_elems.as_mut_slice(), count, converter.move_row_count());
-            let ow = Matrix::new_with_elems(converter.move_row_count(), count, ow_elems.as_slice());
-            let mut ob_elems = vec![0.0f32; converter.move_row_count()];
-            xavier_init(ob_elems.as_mut_slice(), count, converter.move_row_count());
-            let ob = Matrix::new_with_elems(converter.move_row_count(), 1, ob_elems.as_slice());
-            Network::new(iw, ib, sw, sb, pw, pb, ow, ob)
-        },
-        None => {
-            match load_network(args.network.as_str()) {
-                Ok(tmp_network) => {
-                    if !tmp_network.check(Converter::BOARD_ROW_COUNT, converter.move_row_count()) {
-                        return Err(LoopError::InvalidNetwork);
-                    }
-                    tmp_network
+    let mut middle_depth = OneSearcher::DEFAULT_MIDDLE_DEPTH;
+    let mut eval_random_range = RandomEvalFun::DEFAULT_RANGE;
+    let mut move_eps = DEFAULT_MOVE_EPS;
+    match config {
+        Some(config) => {
+            match &config.engine {
+                Some(engine_config) => {
+                    middle_depth = engine_config.middle_depth.unwrap_or(middle_depth);
+                    eval_random_range = engine_config.eval_random_range.unwrap_or(eval_random_range);
+                    move_eps = engine_config.move_eps.unwrap_or(move_eps);
                 },
-                Err(err) => return Err(LoopError::Io(err)),
+                None => (),
             }
         },
-    };
-    let mut config_syzygy_path: Option<String> = None;
+        None => (),
+    }
+    middle_depth = args.middle_depth.unwrap_or(middle_depth);
+    eval_random_range = args.eval_random_range.unwrap_or(eval_random_range);
+    move_eps = args.move_eps.unwrap_or(move_eps);
+    (middle_depth, eval_random_range, move_eps)
+}
+
+fn beam_params(args: &Args, config: &Option<Config>) -> (usize, usize)
+{
+    let mut beam_width = NeuralSearcher::<Network>::DEFAULT_BEAM_WIDTH;
+    let mut beam_depth = NeuralSearcher::<Network>::DEFAULT_BEAM_DEPTH;
     match config {
         Some(config) => {
-            match &config.syzygy {
-                Some(syzygy) => config_syzygy_path = syzygy.path.clone(),
+            match &config.engine {
+                Some(engine_config) => {
+                    beam_width = engine_config.beam_width.unwrap_or(beam_width);
+                    beam_depth = engine_config.beam_depth.unwrap_or(beam_depth);
+                },
                 None => (),
             }
         },
         None => (),
     }
-    let syzygy = match args.syzygy.as_ref().or(config_syzygy_path.as_ref()) {
-        Some(syzygy_path) => {
-            match Syzygy::new(syzygy_path) {
-                Ok(tmp_syzygy) => Arc::new(Mutex::new(Some(tmp_syzygy))),
-                Err(err) => return Err(LoopError::Fathom(err)),
+    beam_width = args.beam_width.unwrap_or(beam_width);
+    beam_depth = args.beam_depth.unwrap_or(beam_depth);
+    (beam_width, beam_depth)
+}
+
+fn move_overhead(args: &Args, config: &Option<Config>) -> Duration
+{
+    let mut move_overhead = Engine::DEFAULT_MOVE_OVERHEAD.as_millis() as u64;
+    match config {
+        Some(config) => {
+            match &config.engine {
+                Some(engine_config) => move_overhead = engine_config.move_overhead.unwrap_or(move_overhead),
+                None => (),
             }
         },
-        None => Arc::new(Mutex::new(None)),
-    };
-    let intr_checker = Arc::new(IntrChecker::new());
-    let simple_eval_fun = Arc::new(SimpleEvalFun::new());
-    let eval_fun = Arc::new(RandomEvalFun::new(simple_eval_fun, RANDOM_EVAL_FUN_RANGE));
-    let neural_searcher = Arc::new(NeuralSearcher::new(intr_checker, converter, network));
-    let middle_searcher = MiddleSearcher::new(eval_fun, neural_searcher);
-    let one_searcher = Arc::new(OneSearcher::new(middle_searcher, MIDDLE_DEPTH));
-    let thinker = Arc::new(Thinker::new(one_searcher, writer, printer, syzygy));
-    Ok(Engine::new(thinker))
+        None => (),
+    }
+    move_overhead = args.move_overhead.unwrap_or(move_overhead);
+    Duration::from_millis(move_overhead)
 }
 
-fn initialize_engine_v2(args: &Args, config: &Option<Config>, writer: Arc<Mutex<dyn Write + Send + Sync>>, printer: Arc<dyn Print + Send + Sync>) -> LoopResult<Engine>
+fn book_params(args: &Args, config: &Option<Config>) -> (usize, bool)
 {
-    match initialize_backend(config) {
-        Ok(()) => (),
-        Err(err) => return Err(LoopError::Matrix(err)),
-    }
-    let converter = Converter::new(IndexConverter::new());
-    let network = match args.random_network {
-        Some(count) => {
-            let mut iw_elems = vec![0.0f32; count * Converter::BOARD_ROW_COUNT];
-            xavier_init(iw_elems.as_mut_slice(), Converter::BOARD_ROW_COUNT, count);
-            let iw = Matrix::new_with_elems(count, Converter::BOARD_ROW_COUNT, iw_elems.as_slice());
-            let mut ib_elems = vec![0.0f32; count];
-            xavier_init(ib_elems.as_mut_slice(), Converter::BOARD_ROW_COUNT, count);
-            let ib = Matrix::new_with_elems(count, 1, ib_elems.as_slice());
-            let mut ow_elems = vec![0.0f32; converter.move_row_count() * count];
-            xavier_sqrt_init(ow_elems.as_mut_slice(), count, converter.move_row_count());
-            let ow = Matrix::new_with_elems(converter.move_row_count(), count, ow_elems.as_slice());
-            let mut ob_elems = vec![0.0f32; converter.move_row_count()];
-            xavier_sqrt_init(ob_elems.as_mut_slice(), count, converter.move_row_count());
-            let ob = Matrix::new_with_elems(converter.move_row_count(), 1, ob_elems.as_slice());
-            NetworkV2::new(iw, ib, ow, ob)
+    let mut book_max_depth = Book::DEFAULT_MAX_DEPTH;
+    let mut has_book_best_move = false;
+    match config {
+        Some(config) => {
+            match &config.book {
+                Some(book_config) => {
+                    book_max_depth = book_config.max_depth.unwrap_or(book_max_depth);
+                    has_book_best_move = book_config.best_move.unwrap_or(has_book_best_move);
+                },
+                None => (),
+            }
         },
-        None => {
-            match load_network_v2(args.network.as_str()) {
-                Ok(tmp_network) => {
-                    if !tmp_network.check(Converter::BOARD_ROW_COUNT, converter.move_row_count()) {
-                        return Err(LoopError::InvalidNetwork);
-                    }
-                    tmp_network
+        None => (),
+    }
+    book_max_depth = args.book_max_depth.unwrap_or(book_max_depth);
+    has_book_best_move = args.book_best_move || has_book_best_move;
+    (book_max_depth, has_book_best_move)
+}
+
+fn wdl_model(config: &Option<Config>) -> WdlModel
+{
+    let mut a = WdlModel::DEFAULT_A;
+    let mut b = WdlModel::DEFAULT_B;
+    match config {
+        Some(config) => {
+            match &config.wdl {
+                Some(wdl_config) => {
+                    a = wdl_config.a.unwrap_or(a);
+                    b = wdl_config.b.unwrap_or(b);
                 },
-                Err(err) => return Err(LoopError::Io(err)),
+                None => (),
             }
         },
-    };
-    let mut config_syzygy_path: Option<String> = None;
+        None => (),
+    }
+    WdlModel::new(a, b)
+}
+
+fn load_book(args: &Args, config: &Option<Config>) -> LoopResult<Arc<Mutex<Option<Book>>>>
+{
+    let mut config_book_path: Option<String> = None;
     match config {
         Some(config) => {
-            match &config.syzygy {
-                Some(syzygy) => config_syzygy_path = syzygy.path.clone(),
+            match &config.book {
+                Some(book_config) => config_book_path = book_config.path.clone(),
                 None => (),
             }
         },
         None => (),
     }
-    let syzygy = match args.syzygy.as_ref().or(config_syzygy_path.as_ref()) {
-        Some(syzygy_path) => {
-            match Syzygy::new(syzygy_path) {
-                Ok(tmp_syzygy) => Arc::new(Mutex::new(Some(tmp_syzygy))),
-                Err(err) => return Err(LoopError::Fathom(err)),
+    match args.book.as_ref().or(config_book_path.as_ref()) {
+        Some(book_path) => {
+            match Book::load(book_path) {
+                Ok(tmp_book) => Ok(Arc::new(Mutex::new(Some(tmp_book)))),
+                Err(err) => Err(LoopError::Io(err)),
             }
         },
-        None => Arc::new(Mutex::new(None)),
-    };
-    let intr_checker = Arc::new(IntrChecker::new());
+        None => Ok(Arc::new(Mutex::new(None))),
+    }
+}
+
+fn backend_kind(backend: Option<Backend>) -> Option<BackendKind>
+{
+    match backend {
+        Some(Backend::Cpu) => Some(BackendKind::Cpu),
+        Some(Backend::OpenCl) => Some(BackendKind::OpenCl),
+        Some(Backend::Cuda) => Some(BackendKind::Cuda),
+        None => None,
+    }
+}
+
+fn is_cpu_backend(args: &Args, config: &Option<Config>) -> bool
+{ selected_backend_kind(config, backend_kind(args.backend)) == Some(BackendKind::Cpu) }
+
+fn network_version_kind(network_version: NetworkVersion) -> NetworkVersionKind
+{
+    match network_version {
+        NetworkVersion::V1 => NetworkVersionKind::V1,
+        NetworkVersion::V2 => NetworkVersionKind::V2,
+        NetworkVersion::V3 => NetworkVersionKind::V3,
+    }
+}
+
+fn random_network_v1(converter: &Converter, count: usize) -> Network
+{
+    let mut iw_elems = vec![0.0f32; count * Converter::BOARD_ROW_COUNT];
+    xavier_init(iw_elems.as_mut_slice(), Converter::BOARD_ROW_COUNT, count);
+    let iw = Matrix::new_with_elems(count, Converter::BOARD_ROW_COUNT, iw_elems.as_slice());
+    let mut ib_elems = vec![0.0f32; count];
+    xavier_init(ib_elems.as_mut_slice(), Converter::BOARD_ROW_COUNT, count);
+    let ib = Matrix::new_with_elems(count, 1, ib_elems.as_slice());
+    let mut sw_elems = vec![0.0f32; count * count];
+    xavier_init(sw_elems.as_mut_slice(), count, count);
+    let sw = Matrix::new_with_elems(count, count, sw_elems.as_slice());
+    let mut sb_elems = vec![0.0f32; count];
+    xavier_init(sb_elems.as_mut_slice(), count, count);
+    let sb = Matrix::new_with_elems(count, 1, sb_elems.as_slice());
+    let mut pw_elems = vec![0.0f32; count * count];
+    xavier_init(pw_elems.as_mut_slice(), count, count);
+    let pw = Matrix::new_with_elems(count, count, pw_elems.as_slice());
+    let mut pb_elems = vec![0.0f32; count];
+    xavier_init(pb_elems.as_mut_slice(), count, count);
+    let pb = Matrix::new_with_elems(count, 1, pb_elems.as_slice());
+    let mut ow_elems = vec![0.0f32; converter.move_row_count() * count];
+    xavier_init(ow_elems.as_mut_slice(), count, converter.move_row_count());
+    let ow = Matrix::new_with_elems(converter.move_row_count(), count, ow_elems.as_slice());
+    let mut ob_elems = vec![0.0f32; converter.move_row_count()];
+    xavier_init(ob_elems.as_mut_slice(), count, converter.move_row_count());
+    let ob = Matrix::new_with_elems(converter.move_row_count(), 1, ob_elems.as_slice());
+    Network::new(iw, ib, sw, sb, pw, pb, ow, ob)
+}
+
+fn random_network_v2(converter: &Converter, count: usize) -> NetworkV2
+{
+    let mut iw_elems = vec![0.0f32; count * Converter::BOARD_ROW_COUNT];
+    xavier_init(iw_elems.as_mut_slice(), Converter::BOARD_ROW_COUNT, count);
+    let iw = Matrix::new_with_elems(count, Converter::BOARD_ROW_COUNT, iw_elems.as_slice());
+    let mut ib_elems = vec![0.0f32; count];
+    xavier_init(ib_elems.as_mut_slice(), Converter::BOARD_ROW_COUNT, count);
+    let ib = Matrix::new_with_elems(count, 1, ib_elems.as_slice());
+    let mut ow_elems = vec![0.0f32; converter.move_row_count() * count];
+    xavier_sqrt_init(ow_elems.as_mut_slice(), count, converter.move_row_count());
+    let ow = Matrix::new_with_elems(converter.move_row_count(), count, ow_elems.as_slice());
+    let mut ob_elems = vec![0.0f32; converter.move_row_count()];
+    xavier_sqrt_init(ob_elems.as_mut_slice(), count, converter.move_row_count());
+    let ob = Matrix::new_with_elems(converter.move_row_count(), 1, ob_elems.as_slice());
+    NetworkV2::new(iw, ib, ow, ob)
+}
+
+fn random_network_v3(converter: &Converter, count: usize) -> NetworkV3
+{
+    let mut iw_elems = vec![0.0f32; count * Converter::BOARD_ROW_COUNT];
+    xavier_init(iw_elems.as_mut_slice(), Converter::BOARD_ROW_COUNT, count);
+    let iw = Matrix::new_with_elems(count, Converter::BOARD_ROW_COUNT, iw_elems.as_slice());
+    let mut ib_elems = vec![0.0f32; count];
+    xavier_init(ib_elems.as_mut_slice(), Converter::BOARD_ROW_COUNT, count);
+    let ib = Matrix::new_with_elems(count, 1, ib_elems.as_slice());
+    let mut sw_elems = vec![0.0f32; count * count];
+    xavier_init(sw_elems.as_mut_slice(), count, count);
+    let sw = Matrix::new_with_elems(count, count, sw_elems.as_slice());
+    let mut sb_elems = vec![0.0f32; count];
+    xavier_init(sb_elems.as_mut_slice(), count, count);
+    let sb = Matrix::new_with_elems(count, 1, sb_elems.as_slice());
+    let mut pw_elems = vec![0.0f32; count * count];
+    xavier_init(pw_elems.as_mut_slice(), count, count);
+    let pw = Matrix::new_with_elems(count, count, pw_elems.as_slice());
+    let mut pb_elems = vec![0.0f32; count];
+    xavier_init(pb_elems.as_mut_slice(), count, count);
+    let pb = Matrix::new_with_elems(count, 1, pb_elems.as_slice());
+    let mut ow_elems = vec![0.0f32; converter.move_row_count() * count];
+    xavier_sqrt_init(ow_elems.as_mut_slice(), count, converter.move_row_count());
+    let ow = Matrix::new_with_elems(converter.move_row_count(), count, ow_elems.as_slice());
+    let mut ob_elems = vec![0.0f32; converter.move_row_count()];
+    xavier_sqrt_init(ob_elems.as_mut_slice(), count, converter.move_row_count());
+    let ob = Matrix::new_with_elems(converter.move_row_count(), 1, ob_elems.as_slice());
+    NetworkV3::new(iw, ib, sw, sb, pw, pb, ow, ob)
+}
+
+fn random_cpu_layer(input_count: usize, output_count: usize, is_sqrt: bool) -> CpuLayer
+{
+    let mut w_elems = vec![0.0f32; output_count * input_count];
+    let mut b_elems = vec![0.0f32; output_count];
+    if is_sqrt {
+        xavier_sqrt_init(w_elems.as_mut_slice(), input_count, output_count);
+        xavier_sqrt_init(b_elems.as_mut_slice(), input_count, output_count);
+    } else {
+        xavier_init(w_elems.as_mut_slice(), input_count, output_count);
+        xavier_init(b_elems.as_mut_slice(), input_count, output_count);
+    }
+    let w = CpuMatrix::new_with_elems(output_count, input_count, w_elems.as_slice());
+    let b = CpuMatrix::new_with_elems(output_count, 1, b_elems.as_slice());
+    CpuLayer::new(w, b)
+}
+
+fn random_cpu_network_v1(converter: &Converter, count: usize) -> CpuNetwork
+{
+    let input_layer = random_cpu_layer(Converter::BOARD_ROW_COUNT, count, false);
+    let search_layer = random_cpu_layer(count, count, false);
+    let pv_layer = random_cpu_layer(count, count, false);
+    let output_layer = random_cpu_layer(count, converter.move_row_count(), false);
+    CpuNetwork::new(input_layer, search_layer, pv_layer, output_layer)
+}
+
+fn random_cpu_network_v2(converter: &Converter, count: usize) -> CpuNetworkV2
+{
+    let input_layer = random_cpu_layer(Converter::BOARD_ROW_COUNT, count, false);
+    let output_layer = random_cpu_layer(count, converter.move_row_count(), true);
+    CpuNetworkV2::new(input_layer, output_layer)
+}
+
+fn random_cpu_network_v3(converter: &Converter, count: usize) -> CpuNetworkV3
+{
+    let input_layer = random_cpu_layer(Converter::BOARD_ROW_COUNT, count, false);
+    let search_layer = random_cpu_layer(count, count, false);
+    let pv_layer = random_cpu_layer(count, count, false);
+    let output_layer = random_cpu_layer(count, converter.move_row_count(), true);
+    CpuNetworkV3::new(input_layer, search_layer, pv_layer, output_layer)
+}
+
+fn checked_network<T: Net>(converter: &Converter, network: T) -> LoopResult<T>
+{
+    if !network.check(Converter::BOARD_ROW_COUNT, converter.move_row_count()) {
+        return Err(LoopError::InvalidNetwork);
+    }
+    Ok(network)
+}
+
+fn create_searcher<T: ForwardNet + Send + Sync + 'static>(args: &Args, config: &Option<Config>, converter: Converter, network: T, intr_checker: Arc<IntrChecker>, syzygy: Arc<Mutex<Option<Syzygy>>>, transposition_table: Arc<TranspositionTable>) -> Arc<dyn Search + Send + Sync>
+{
+    let (middle_depth, eval_random_range, move_eps) = engine_params(args, config);
+    let (beam_width, beam_depth) = beam_params(args, config);
     let simple_eval_fun = Arc::new(SimpleEvalFun::new());
-    let eval_fun = Arc::new(RandomEvalFun::new(simple_eval_fun, RANDOM_EVAL_FUN_RANGE));
-    let neural_searcher = Arc::new(OneNeuralSearcher::new(intr_checker, converter, network));
-    let middle_searcher = MiddleSearcher::new(eval_fun, neural_searcher);
-    let one_searcher = Arc::new(OneSearcher::new(middle_searcher, MIDDLE_DEPTH));
-    let thinker = Arc::new(Thinker::new(one_searcher, writer, printer, syzygy));
-    Ok(Engine::new(thinker))
+    let eval_fun = Arc::new(RandomEvalFun::new(simple_eval_fun, eval_random_range));
+    match args.searcher {
+        Searcher::One => {
+            let neural_searcher = Arc::new(NeuralSearcher::new_with_eval_fun(intr_checker, converter, network, eval_fun.clone()));
+            neural_searcher.set_move_eps(move_eps);
+            neural_searcher.set_beam_width(beam_width);
+            neural_searcher.set_beam_depth(beam_depth);
+            let middle_searcher = MiddleSearcher::new_with_transposition_table_and_syzygy(eval_fun, neural_searcher, transposition_table, syzygy);
+            Arc::new(OneSearcher::new_with_thread_count(middle_searcher, middle_depth, args.threads))
+        },
+        Searcher::Mcts => Arc::new(MctsSearcher::new_with_size(intr_checker, converter, network, eval_fun, args.hash)),
+    }
 }
 
-fn initialize_engine_v3(args: &Args, config: &Option<Config>, writer: Arc<Mutex<dyn Write + Send + Sync>>, printer: Arc<dyn Print + Send + Sync>) -> LoopResult<Engine>
+fn create_searcher_for_one_neural_searcher<T: ForwardNet + Send + Sync + 'static>(args: &Args, config: &Option<Config>, converter: Converter, network: T, intr_checker: Arc<IntrChecker>, syzygy: Arc<Mutex<Option<Syzygy>>>, transposition_table: Arc<TranspositionTable>) -> Arc<dyn Search + Send + Sync>
 {
-    match initialize_backend(config) {
-        Ok(()) => (),
-        Err(err) => return Err(LoopError::Matrix(err)),
+    let (middle_depth, eval_random_range, move_eps) = engine_params(args, config);
+    let simple_eval_fun = Arc::new(SimpleEvalFun::new());
+    let eval_fun = Arc::new(RandomEvalFun::new(simple_eval_fun, eval_random_range));
+    match args.searcher {
+        Searcher::One => {
+            let neural_searcher = Arc::new(OneNeuralSearcher::new(intr_checker, converter, network));
+            neural_searcher.set_move_eps(move_eps);
+            let middle_searcher = MiddleSearcher::new_with_transposition_table_and_syzygy(eval_fun, neural_searcher, transposition_table, syzygy);
+            Arc::new(OneSearcher::new_with_thread_count(middle_searcher, middle_depth, args.threads))
+        },
+        Searcher::Mcts => Arc::new(MctsSearcher::new_with_size(intr_checker, converter, network, eval_fun, args.hash)),
     }
+}
+
+fn load_searcher(args: &Args, config: &Option<Config>, network_path: &str, network_version: NetworkVersionKind, intr_checker: Arc<IntrChecker>, syzygy: Arc<Mutex<Option<Syzygy>>>, transposition_table: Arc<TranspositionTable>) -> LoopResult<Arc<dyn Search + Send + Sync>>
+{
     let converter = Converter::new(IndexConverter::new());
-    let network = match args.random_network {
-        Some(count) => {
-            let mut iw_elems = vec![0.0f32; count * Converter::BOARD_ROW_COUNT];
-            xavier_init(iw_elems.as_mut_slice(), Converter::BOARD_ROW_COUNT, count);
-            let iw = Matrix::new_with_elems(count, Converter::BOARD_ROW_COUNT, iw_elems.as_slice());
-            let mut ib_elems = vec![0.0f32; count];
-            xavier_init(ib_elems.as_mut_slice(), Converter::BOARD_ROW_COUNT, count);
-            let ib = Matrix::new_with_elems(count, 1, ib_elems.as_slice());
-            let mut sw_elems = vec![0.0f32; count * count];
-            xavier_init(sw_elems.as_mut_slice(), count, count);
-            let sw = Matrix::new_with_elems(count, count, sw_elems.as_slice());
-            let mut sb_elems = vec![0.0f32; count];
-            xavier_init(sb_elems.as_mut_slice(), count, count);
-            let sb = Matrix::new_with_elems(count, 1, sb_elems.as_slice());
-            let mut pw_elems = vec![0.0f32; count * count];
-            xavier_init(pw_elems.as_mut_slice(), count, count);
-            let pw = Matrix::new_with_elems(count, count, pw_elems.as_slice());
-            let mut pb_elems = vec![0.0f32; count];
-            xavier_init(pb_elems.as_mut_slice(), count, count);
-            let pb = Matrix::new_with_elems(count, 1, pb_elems.as_slice());
-            let mut ow_elems = vec![0.0f32; converter.move_row_count() * count];
-            xavier_sqrt_init(ow_elems.as_mut_slice(), count, converter.move_row_count());
-            let ow = Matrix::new_with_elems(converter.move_row_count(), count, ow_elems.as_slice());
-            let mut ob_elems = vec![0.0f32; converter.move_row_count()];
-            xavier_sqrt_init(ob_elems.as_mut_slice(), count, converter.move_row_count());
-            let ob = Matrix::new_with_elems(converter.move_row_count(), 1, ob_elems.as_slice());
-            NetworkV3::new(iw, ib, sw, sb, pw, pb, ow, ob)
+    match (network_version, is_cpu_backend(args, config)) {
+        (NetworkVersionKind::V1, true) => {
+            let network = match load_cpu_network(network_path) {
+                Ok(tmp_network) if tmp_network.check(Converter::BOARD_ROW_COUNT, converter.move_row_count()) => tmp_network,
+                Ok(_) => return Err(LoopError::InvalidNetwork),
+                Err(err) => return Err(LoopError::Io(err)),
+            };
+            Ok(create_searcher(args, config, converter, network, intr_checker, syzygy, transposition_table))
         },
-        None => {
-            match load_network_v3(args.network.as_str()) {
-                Ok(tmp_network) => {
-                    if !tmp_network.check(Converter::BOARD_ROW_COUNT, converter.move_row_count()) {
-                        return Err(LoopError::InvalidNetwork);
-                    }
-                    tmp_network
-                },
+        (NetworkVersionKind::V2, true) => {
+            let network = match load_cpu_network_v2(network_path) {
+                Ok(tmp_network) if tmp_network.check(Converter::BOARD_ROW_COUNT, converter.move_row_count()) => tmp_network,
+                Ok(_) => return Err(LoopError::InvalidNetwork),
                 Err(err) => return Err(LoopError::Io(err)),
-            }
+            };
+            Ok(create_searcher_for_one_neural_searcher(args, config, converter, network, intr_checker, syzygy, transposition_table))
         },
-    };
+        (NetworkVersionKind::V3, true) => {
+            let network = match load_cpu_network_v3(network_path) {
+                Ok(tmp_network) if tmp_network.check(Converter::BOARD_ROW_COUNT, converter.move_row_count()) => tmp_network,
+                Ok(_) => return Err(LoopError::InvalidNetwork),
+                Err(err) => return Err(LoopError::Io(err)),
+            };
+            Ok(create_searcher(args, config, converter, network, intr_checker, syzygy, transposition_table))
+        },
+        (NetworkVersionKind::V1, false) => {
+            let network = match load_network(network_path) {
+                Ok(tmp_network) => checked_network(&converter, tmp_network)?,
+                Err(err) => return Err(LoopError::Io(err)),
+            };
+            Ok(create_searcher(args, config, converter, network, intr_checker, syzygy, transposition_table))
+        },
+        (NetworkVersionKind::V2, false) => {
+            let network = match load_network_v2(network_path) {
+                Ok(tmp_network) => checked_network(&converter, tmp_network)?,
+                Err(err) => return Err(LoopError::Io(err)),
+            };
+            Ok(create_searcher_for_one_neural_searcher(args, config, converter, network, intr_checker, syzygy, transposition_table))
+        },
+        (NetworkVersionKind::V3, false) => {
+            let network = match load_network_v3(network_path) {
+                Ok(tmp_network) => checked_network(&converter, tmp_network)?,
+                Err(err) => return Err(LoopError::Io(err)),
+            };
+            Ok(create_searcher(args, config, converter, network, intr_checker, syzygy, transposition_table))
+        },
+    }
+}
+
+fn initialize_engine(args: &Args, config: &Option<Config>, writer: Arc<Mutex<dyn Write + Send + Sync>>, printer: Arc<dyn Print + Send + Sync>) -> LoopResult<Engine>
+{
+    match initialize_backend_with_kind(config, backend_kind(args.backend)) {
+        Ok(()) => (),
+        Err(err) => return Err(LoopError::Matrix(err)),
+    }
     let mut config_syzygy_path: Option<String> = None;
     match config {
         Some(config) => {
@@ -260,22 +489,57 @@ fn initialize_engine_v3(args: &Args, config: &Option<Config>, writer: Arc<Mutex<
         None => Arc::new(Mutex::new(None)),
     };
     let intr_checker = Arc::new(IntrChecker::new());
-    let simple_eval_fun = Arc::new(SimpleEvalFun::new());
-    let eval_fun = Arc::new(RandomEvalFun::new(simple_eval_fun, RANDOM_EVAL_FUN_RANGE));
-    let neural_searcher = Arc::new(NeuralSearcher::new(intr_checker, converter, network));
-    let middle_searcher = MiddleSearcher::new(eval_fun, neural_searcher);
-    let one_searcher = Arc::new(OneSearcher::new(middle_searcher, MIDDLE_DEPTH));
-    let thinker = Arc::new(Thinker::new(one_searcher, writer, printer, syzygy));
-    Ok(Engine::new(thinker))
-}
-
-fn initialize_engine(args: &Args, config: &Option<Config>, writer: Arc<Mutex<dyn Write + Send + Sync>>, printer: Arc<dyn Print + Send + Sync>) -> LoopResult<Engine>
-{
-    match args.network_version {
-        NetworkVersion::V1 => initialize_engine_v1(args, config, writer, printer),
-        NetworkVersion::V2 => initialize_engine_v2(args, config, writer, printer),
-        NetworkVersion::V3 => initialize_engine_v3(args, config, writer, printer),
-    }
+    let transposition_table = Arc::new(TranspositionTable::new(args.hash));
+    let network_version = network_version_kind(args.network_version);
+    let searcher = match args.random_network {
+        Some(count) => {
+            let converter = Converter::new(IndexConverter::new());
+            match (network_version, is_cpu_backend(args, config)) {
+                (NetworkVersionKind::V1, true) => {
+                    let network = random_cpu_network_v1(&converter, count);
+                    create_searcher(args, config, converter, network, intr_checker.clone(), syzygy.clone(), transposition_table.clone())
+                },
+                (NetworkVersionKind::V2, true) => {
+                    let network = random_cpu_network_v2(&converter, count);
+                    create_searcher_for_one_neural_searcher(args, config, converter, network, intr_checker.clone(), syzygy.clone(), transposition_table.clone())
+                },
+                (NetworkVersionKind::V3, true) => {
+                    let network = random_cpu_network_v3(&converter, count);
+                    create_searcher(args, config, converter, network, intr_checker.clone(), syzygy.clone(), transposition_table.clone())
+                },
+                (NetworkVersionKind::V1, false) => {
+                    let network = random_network_v1(&converter, count);
+                    create_searcher(args, config, converter, network, intr_checker.clone(), syzygy.clone(), transposition_table.clone())
+                },
+                (NetworkVersionKind::V2, false) => {
+                    let network = random_network_v2(&converter, count);
+                    create_searcher_for_one_neural_searcher(args, config, converter, network, intr_checker.clone(), syzygy.clone(), transposition_table.clone())
+                },
+                (NetworkVersionKind::V3, false) => {
+                    let network = random_network_v3(&converter, count);
+                    create_searcher(args, config, converter, network, intr_checker.clone(), syzygy.clone(), transposition_table.clone())
+                },
+            }
+        },
+        None => load_searcher(args, config, args.network.as_str(), network_version, intr_checker.clone(), syzygy.clone(), transposition_table.clone())?,
+    };
+    let book = load_book(args, config)?;
+    let (book_max_depth, has_book_best_move) = book_params(args, config);
+    let thinker = Arc::new(Thinker::new_with_book(searcher, writer, printer, syzygy.clone(), book));
+    thinker.set_book_max_depth(book_max_depth);
+    thinker.set_book_best_move(has_book_best_move);
+    thinker.set_wdl_model(wdl_model(config));
+    let mut engine = Engine::new(thinker);
+    engine.set_move_overhead(move_overhead(args, config));
+    let loader_args = args.clone();
+    let loader_config = config.clone();
+    let searcher_loader = move |network_path: &str, network_version: NetworkVersionKind, size: usize| {
+        let mut tmp_args = loader_args.clone();
+        tmp_args.hash = size;
+        load_searcher(&tmp_args, &loader_config, network_path, network_version, intr_checker.clone(), syzygy.clone(), transposition_table.clone())
+    };
+    engine.set_searcher_loader(Box::new(searcher_loader), args.network.clone(), network_version, args.hash);
+    Ok(engine)
 }
 
 fn main()
@@ -288,6 +552,14 @@ fn main()
         },
         _ => (),
     }
+    if !(TranspositionTable::MIN_SIZE..=TranspositionTable::MAX_SIZE).contains(&args.hash) {
+        eprintln!("size of transposition table is out of range");
+        exit(1);
+    }
+    if !(OneSearcher::MIN_THREAD_COUNT..=OneSearcher::MAX_THREAD_COUNT).contains(&args.threads) {
+        eprintln!("number of threads is out of range");
+        exit(1);
+    }
     let config = match load_config(args.config.as_str()) {
         Ok(tmp_config) => tmp_config,
         Err(err) => {
@@ -295,6 +567,41 @@ fn main()
             exit(1);
         },
     };
+    let (middle_depth, eval_random_range, move_eps) = engine_params(&args, &config);
+    if !(OneSearcher::MIN_MIDDLE_DEPTH..=OneSearcher::MAX_MIDDLE_DEPTH).contains(&middle_depth) {
+        eprintln!("depth of middle search is out of range");
+        exit(1);
+    }
+    if !(RandomEvalFun::MIN_RANGE..=RandomEvalFun::MAX_RANGE).contains(&eval_random_range) {
+        eprintln!("range of random component of evaluation function is out of range");
+        exit(1);
+    }
+    if !(MIN_MOVE_EPS..=MAX_MOVE_EPS).contains(&move_eps) {
+        eprintln!("move epsilon is out of range");
+        exit(1);
+    }
+    let (beam_width, beam_depth) = beam_params(&args, &config);
+    if !(NeuralSearcher::<Network>::MIN_BEAM_WIDTH..=NeuralSearcher::<Network>::MAX_BEAM_WIDTH).contains(&beam_width) {
+        eprintln!("beam width is out of range");
+        exit(1);
+    }
+    if !(NeuralSearcher::<Network>::MIN_BEAM_DEPTH..=NeuralSearcher::<Network>::MAX_BEAM_DEPTH).contains(&beam_depth) {
+        eprintln!("depth of beam search is out of range");
+        exit(1);
+    }
+    let (book_max_depth, _) = book_params(&args, &config);
+    if !(Book::MIN_MAX_DEPTH..=Book::MAX_MAX_DEPTH).contains(&book_max_depth) {
+        eprintln!("maximal depth of opening book is out of range");
+        exit(1);
+    }
+    if !(Engine::MIN_MOVE_OVERHEAD..=Engine::MAX_MOVE_OVERHEAD).contains(&move_overhead(&args, &config)) {
+        eprintln!("move overhead is out of range");
+        exit(1);
+    }
+    if wdl_model(&config).b <= 0.0 {
+        eprintln!("spread of WDL model isn't positive");
+        exit(1);
+    }
     let stdout_log = match &args.log {
         Some(log_path) => {
             match File::options().create(true).append(true).open(log_path.as_str()) {
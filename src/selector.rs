@@ -40,6 +40,7 @@ impl fmt::Display for SelectorError
             SelectorError::Interruption(Interruption::Timeout) => write!(f, "interrupted by timeout"),
             SelectorError::Interruption(Interruption::Stop) => write!(f, "interrupted by stop"),
             SelectorError::Interruption(Interruption::CtrlC) => write!(f, "interrupted by ctrl-c"),
+            SelectorError::Interruption(Interruption::BackendError) => write!(f, "interrupted by backend error"),
             SelectorError::Io(err) => write!(f, "{}", err),
         }
     }
@@ -8,6 +8,10 @@
 pub(crate) mod backend;
 pub(crate) mod config;
 pub(crate) mod converter;
+pub(crate) mod cpu_matrix;
+pub(crate) mod cpu_network;
+pub(crate) mod cpu_network_v2;
+pub(crate) mod cpu_network_v3;
 pub(crate) mod ctrl_c_intr_checker;
 pub(crate) mod index_converter;
 pub(crate) mod intr_check;
@@ -25,6 +29,10 @@ pub(crate) mod xavier_init;
 pub use backend::*;
 pub use config::*;
 pub use converter::*;
+pub use cpu_matrix::*;
+pub use cpu_network::*;
+pub use cpu_network_v2::*;
+pub use cpu_network_v3::*;
 pub use ctrl_c_intr_checker::*;
 pub use index_converter::*;
 pub use intr_check::*;
@@ -48,4 +56,6 @@ pub enum Interruption
     Stop,
     /// Interruption by pressed keys `Ctrl-C`.
     CtrlC,
+    /// Interruption by an error of backend for operations on matrices.
+    BackendError,
 }
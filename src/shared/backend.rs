@@ -18,7 +18,6 @@ use crate::matrix::opencl::Device;
 use crate::matrix::opencl::get_platforms;
 #[cfg(feature = "cuda")]
 use crate::matrix::cuda::CudaBackend;
-#[cfg(feature = "opencl")]
 use crate::matrix::Error;
 use crate::matrix::Result;
 use crate::matrix::set_default_backend;
@@ -55,10 +54,35 @@ fn initialize_opencl_backend(platform_idx: usize, device_idx: usize) -> Result<(
     set_default_backend(Arc::new(ClBackend::new_with_context(context)?))
 }
 
+/// Returns the selected kind of backend from the kind of backend or the configuration.
+///
+/// The kind of backend overrides the kind of backend from the configuration.
+pub fn selected_backend_kind(config: &Option<Config>, kind: Option<BackendKind>) -> Option<BackendKind>
+{
+    match config {
+        Some(config) => {
+            match &config.backend {
+                Some(backend_config) => kind.or(backend_config.kind),
+                None => kind,
+            }
+        },
+        None => kind,
+    }
+}
+
 /// Initializes a backend for operations on matrices.
-#[allow(unused_assignments)]
 pub fn initialize_backend(config: &Option<Config>) -> Result<()>
+{ initialize_backend_with_kind(config, None) }
+
+/// Initializes a backend of the kind for operations on matrices.
+///
+/// The kind of backend overrides the kind of backend from the configuration. If the kind of
+/// backend isn't specified in both places, there tries to initialize all available backends. The
+/// CPU backend doesn't need an initialization because the CPU neural networks don't use matrices.
+#[allow(unused_assignments)]
+pub fn initialize_backend_with_kind(config: &Option<Config>, kind: Option<BackendKind>) -> Result<()>
 {
+    let kind = selected_backend_kind(config, kind);
     let mut is_first_opencl = false;
     let mut ordinal = 0usize;
     let mut platform_idx = 0usize;
@@ -82,6 +106,22 @@ pub fn initialize_backend(config: &Option<Config>) -> Result<()>
         None => (),
     }
     let mut res: Option<Result<()>> = None;
+    match kind {
+        Some(BackendKind::Cpu) => return Ok(()),
+        Some(BackendKind::OpenCl) => {
+            #[cfg(feature = "opencl")]
+            return initialize_opencl_backend(platform_idx, device_idx);
+            #[cfg(not(feature = "opencl"))]
+            return Err(Error::DefaultBackendInitialization);
+        },
+        Some(BackendKind::Cuda) => {
+            #[cfg(feature = "cuda")]
+            return initialize_cuda_backend(ordinal, is_cublas, is_mma);
+            #[cfg(not(feature = "cuda"))]
+            return Err(Error::DefaultBackendInitialization);
+        },
+        None => (),
+    }
     #[cfg(feature = "opencl")]
     if is_first_opencl {
         match res {
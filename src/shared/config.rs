@@ -21,12 +21,36 @@ pub struct Config
     pub backend: Option<BackendConfig>,
     /// A configuration of Syzygy endgame tablebases.
     pub syzygy: Option<SyzygyConfig>,
+    /// A configuration of opening book.
+    pub book: Option<BookConfig>,
+    /// An engine configuration.
+    pub engine: Option<EngineConfig>,
+    /// A configuration of WDL model.
+    pub wdl: Option<WdlConfig>,
+}
+
+/// An enumeration of backend kind.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize)]
+pub enum BackendKind
+{
+    /// A CPU backend that only computes forward passes of neural networks.
+    #[serde(rename = "cpu")]
+    Cpu,
+    /// An OpenCL backend.
+    #[serde(rename = "opencl")]
+    OpenCl,
+    /// A CUDA backend.
+    #[serde(rename = "cuda")]
+    Cuda,
 }
 
 /// A structure of backend configuration.
 #[derive(Clone, Debug, Deserialize)]
 pub struct BackendConfig
 {
+    /// A kind of backend that is only initialized. If this field isn't specified, there tries to
+    /// initialize all available backends.
+    pub kind: Option<BackendKind>,
     /// If this field is `true`, there tries to initialize the OpenCL backend as first. Default
     /// value of this field is `true`.
     pub first_opencl: Option<bool>,
@@ -52,6 +76,51 @@ pub struct SyzygyConfig
     pub path: Option<String>,
 }
 
+/// A structure of configuration of opening book.
+#[derive(Clone, Debug, Deserialize)]
+pub struct BookConfig
+{
+    /// A path to the opening book file of the Polyglot format.
+    pub path: Option<String>,
+    /// A maximal depth of opening book in half moves. Default value of this field is 40.
+    pub max_depth: Option<usize>,
+    /// If this field is `true`, a book move with the greatest weight is selected, otherwise a book
+    /// move is randomly selected. Default value of this field is `false`.
+    pub best_move: Option<bool>,
+}
+
+/// A structure of configuration of WDL model.
+///
+/// The parameters of WDL model should be fitted from results of games.
+#[derive(Clone, Debug, Deserialize)]
+pub struct WdlConfig
+{
+    /// A value in centipawns for that a win is as likely as a draw or a loss. Default value of this
+    /// field is 200.
+    pub a: Option<f64>,
+    /// A spread of the logistic function of WDL model in centipawns. Default value of this field is
+    /// 100.
+    pub b: Option<f64>,
+}
+
+/// A structure of engine configuration.
+#[derive(Clone, Debug, Deserialize)]
+pub struct EngineConfig
+{
+    /// A depth of middle search. Default value of this field is two.
+    pub middle_depth: Option<usize>,
+    /// A range of random component of evaluation function. Default value of this field is five.
+    pub eval_random_range: Option<i32>,
+    /// A move epsilon for neural search. Default value of this field is 0.01.
+    pub move_eps: Option<f32>,
+    /// A beam width for neural search. Default value of this field is one.
+    pub beam_width: Option<usize>,
+    /// A depth of beam search for neural search. Default value of this field is two.
+    pub beam_depth: Option<usize>,
+    /// A move overhead in milliseconds. Default value of this field is 100.
+    pub move_overhead: Option<u64>,
+}
+
 /// Reads a configuration from the reader.
 pub fn read_config(r: &mut dyn Read) -> Result<Config>
 {
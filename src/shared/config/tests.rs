@@ -22,6 +22,7 @@ device = 2
         Ok(config) => {
             match &config.backend {
                 Some(backend_config) => {
+                    assert_eq!(None, backend_config.kind);
                     assert_eq!(None, backend_config.first_opencl);
                     assert_eq!(None, backend_config.ordinal);
                     assert_eq!(Some(1), backend_config.platform);
@@ -41,6 +42,7 @@ fn test_read_config_reads_configuration_file_for_all_backend_fields()
 {
     let s = "
 [backend]
+kind = \"cuda\"
 first_opencl = true
 ordinal = 1
 platform = 2
@@ -54,6 +56,7 @@ mma = true
         Ok(config) => {
             match &config.backend {
                 Some(backend_config) => {
+                    assert_eq!(Some(BackendKind::Cuda), backend_config.kind);
                     assert_eq!(Some(true), backend_config.first_opencl);
                     assert_eq!(Some(1), backend_config.ordinal);
                     assert_eq!(Some(2), backend_config.platform);
@@ -67,3 +70,123 @@ mma = true
         Err(_) => assert!(false),
     }
 }
+
+#[test]
+fn test_read_config_reads_configuration_file_for_cpu_backend_kind()
+{
+    let s = "
+[backend]
+kind = \"cpu\"
+";
+    let s2 = &s[1..];
+    let mut cursor = Cursor::new(s2);
+    match read_config(&mut cursor) {
+        Ok(config) => {
+            match &config.backend {
+                Some(backend_config) => assert_eq!(Some(BackendKind::Cpu), backend_config.kind),
+                None => assert!(false),
+            }
+        },
+        Err(_) => assert!(false),
+    }
+}
+
+#[test]
+fn test_read_config_complains_on_invalid_backend_kind()
+{
+    let s = "
+[backend]
+kind = \"xxx\"
+";
+    let s2 = &s[1..];
+    let mut cursor = Cursor::new(s2);
+    match read_config(&mut cursor) {
+        Ok(_) => assert!(false),
+        Err(err) => assert_eq!(ErrorKind::InvalidData, err.kind()),
+    }
+}
+
+#[test]
+fn test_read_config_reads_configuration_file_for_engine_fields()
+{
+    let s = "
+[engine]
+middle_depth = 3
+eval_random_range = 10
+move_eps = 0.05
+beam_width = 3
+beam_depth = 1
+move_overhead = 200
+";
+    let s2 = &s[1..];
+    let mut cursor = Cursor::new(s2);
+    match read_config(&mut cursor) {
+        Ok(config) => {
+            assert!(config.backend.is_none());
+            match &config.engine {
+                Some(engine_config) => {
+                    assert_eq!(Some(3), engine_config.middle_depth);
+                    assert_eq!(Some(10), engine_config.eval_random_range);
+                    assert_eq!(Some(0.05), engine_config.move_eps);
+                    assert_eq!(Some(3), engine_config.beam_width);
+                    assert_eq!(Some(1), engine_config.beam_depth);
+                    assert_eq!(Some(200), engine_config.move_overhead);
+                },
+                None => assert!(false),
+            } 
+        },
+        Err(_) => assert!(false),
+    }
+}
+
+#[test]
+fn test_read_config_reads_configuration_file_for_book_fields()
+{
+    let s = "
+[book]
+path = \"book.bin\"
+max_depth = 20
+best_move = true
+";
+    let s2 = &s[1..];
+    let mut cursor = Cursor::new(s2);
+    match read_config(&mut cursor) {
+        Ok(config) => {
+            assert!(config.backend.is_none());
+            match &config.book {
+                Some(book_config) => {
+                    assert_eq!(Some(String::from("book.bin")), book_config.path);
+                    assert_eq!(Some(20), book_config.max_depth);
+                    assert_eq!(Some(true), book_config.best_move);
+                },
+                None => assert!(false),
+            } 
+        },
+        Err(_) => assert!(false),
+    }
+}
+
+#[test]
+fn test_read_config_reads_configuration_file_for_wdl_fields()
+{
+    let s = "
+[wdl]
+a = 250.5
+b = 80.0
+";
+    let s2 = &s[1..];
+    let mut cursor = Cursor::new(s2);
+    match read_config(&mut cursor) {
+        Ok(config) => {
+            assert!(config.backend.is_none());
+            match &config.wdl {
+                Some(wdl_config) => {
+                    assert_eq!(Some(250.5), wdl_config.a);
+                    assert_eq!(Some(80.0), wdl_config.b);
+                },
+                None => assert!(false),
+            } 
+        },
+        Err(_) => assert!(false),
+    }
+}
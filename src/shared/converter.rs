@@ -5,6 +5,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 //
+use rand::random_range;
 use crate::chess::Board;
 use crate::chess::CastlingSide;
 use crate::chess::Color;
@@ -138,6 +139,86 @@ impl Converter
             None => None,
         }
     }
+
+    fn scored_moves(&self, moves: &MoveList, color: Color, elems: &[f32], col: usize, col_count: usize) -> Vec<(f32, Move)>
+    {
+        let mut scored_moves: Vec<(f32, Move)> = Vec::new();
+        for mv in moves {
+            if let Some(idx) = self.index_converter.move_to_index(*mv, color) {
+                scored_moves.push((elems[col_count * idx + col], *mv));
+            }
+        }
+        scored_moves
+    }
+
+    /// Converts the column of output matrix to the best moves.
+    ///
+    /// This method is similar to the [`matrix_col_to_move`](Self::matrix_col_to_move) method but
+    /// returns the moves that are sorted by their scores in descending order. If the move scores
+    /// are too close, no moves are returned.
+    pub fn matrix_col_to_moves(&self, moves: &MoveList, color: Color, elems: &[f32], col: usize, col_count: usize, eps: f32) -> Vec<Move>
+    {
+        let mut scored_moves = self.scored_moves(moves, color, elems, col, col_count);
+        scored_moves.sort_by(|(score1, _), (score2, _)| score2.total_cmp(score1));
+        match (scored_moves.first(), scored_moves.last()) {
+            (Some((best_move_score, _)), Some((worst_move_score, _))) => {
+                if scored_moves.len() <= 1 || (best_move_score - worst_move_score).abs() > best_move_score.abs() * eps {
+                    scored_moves.iter().map(|(_, mv)| *mv).collect()
+                } else {
+                    Vec::new()
+                }
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    /// Converts the column of output matrix to a random move.
+    ///
+    /// This method is similar to the [`matrix_col_to_move`](Self::matrix_col_to_move) method but
+    /// samples a move from probabilities that are computed by the softmax function from the move
+    /// scores divided by the temperature. A higher temperature makes weaker moves more likely. If
+    /// the temperature isn't positive, the best move is returned.
+    pub fn matrix_col_to_random_move(&self, moves: &MoveList, color: Color, elems: &[f32], col: usize, col_count: usize, temperature: f32) -> Option<Move>
+    {
+        let scored_moves = self.scored_moves(moves, color, elems, col, col_count);
+        if scored_moves.is_empty() {
+            return None;
+        }
+        let best_move_score = scored_moves.iter().fold(-f32::INFINITY, |score1, (score2, _)| score1.max(*score2));
+        if temperature <= 0.0 {
+            return scored_moves.iter().find(|(score, _)| *score == best_move_score).map(|(_, mv)| *mv);
+        }
+        let exps: Vec<f32> = scored_moves.iter().map(|(score, _)| ((score - best_move_score) / temperature).exp()).collect();
+        let sum: f32 = exps.iter().sum();
+        let mut r = random_range(0.0..sum);
+        for ((_, mv), exp) in scored_moves.iter().zip(exps.iter()) {
+            if r < *exp {
+                return Some(*mv);
+            }
+            r -= exp;
+        }
+        scored_moves.last().map(|(_, mv)| *mv)
+    }
+
+    /// Converts the column of output matrix to probabilities of moves.
+    ///
+    /// The moves should be legal moves for the current board. The color is a side of converted
+    /// board. The probabilities are computed by the softmax function from the move scores. A move
+    /// that can't be converted to an index has the worst move score.
+    pub fn matrix_col_to_move_probs(&self, moves: &MoveList, color: Color, elems: &[f32], col: usize, col_count: usize) -> Vec<(Move, f32)>
+    {
+        let scores: Vec<Option<f32>> = moves.iter().map(|mv| self.index_converter.move_to_index(*mv, color).map(|idx| elems[col_count * idx + col])).collect();
+        let best_move_score = scores.iter().flatten().fold(-f32::INFINITY, |score1, score2| score1.max(*score2));
+        let worst_move_score = scores.iter().flatten().fold(f32::INFINITY, |score1, score2| score1.min(*score2));
+        let (best_move_score, worst_move_score) = if best_move_score.is_finite() {
+            (best_move_score, worst_move_score)
+        } else {
+            (0.0, 0.0)
+        };
+        let exps: Vec<f32> = scores.iter().map(|score| (score.unwrap_or(worst_move_score) - best_move_score).exp()).collect();
+        let sum: f32 = exps.iter().sum();
+        moves.iter().zip(exps.iter()).map(|(mv, exp)| (*mv, exp / sum)).collect()
+    }
 }
 
 #[cfg(test)]
@@ -352,3 +352,89 @@ fn test_converter_matrix_col_to_move_only_checks_one_matrix_column()
         None => assert!(false),
     }
 }
+
+#[test]
+fn test_converter_matrix_col_to_moves_converts_matrix_column_to_best_moves()
+{
+    let converter = Converter::new(IndexConverter::new());
+    let board = Board::from_fen("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2").unwrap();
+    let mv = Move::from_uci_legal("b1c3", &board).unwrap();
+    let mv2 = Move::from_uci_legal("d2d4", &board).unwrap();
+    let mv3 = Move::from_uci_legal("g1f3", &board).unwrap();
+    let mut elems = vec![1.0f32; converter.move_row_count()];
+    let move_idx = converter.index_converter().move_to_index(mv, Color::White).unwrap();
+    let move_idx2 = converter.index_converter().move_to_index(mv2, Color::White).unwrap();
+    let move_idx3 = converter.index_converter().move_to_index(mv3, Color::White).unwrap();
+    elems[move_idx] = 8.0;
+    elems[move_idx2] = 10.0;
+    elems[move_idx3] = 9.0;
+    let moves = legal::gen_all(&board);
+    let best_moves = converter.matrix_col_to_moves(&moves, Color::White, elems.as_slice(), 0, 1, 0.1);
+    assert_eq!(moves.len(), best_moves.len());
+    assert_eq!(vec![mv2, mv3, mv], best_moves[0..3].to_vec());
+}
+
+#[test]
+fn test_converter_matrix_col_to_moves_does_not_convert_matrix_column_to_best_moves()
+{
+    let converter = Converter::new(IndexConverter::new());
+    let board = Board::from_fen("rnbqkbnr/pppp1ppp/8/4p3/4P3/2N5/PPPP1PPP/R1BQKBNR b KQkq - 0 2").unwrap();
+    let mv = Move::from_uci_legal("g8f6", &board).unwrap();
+    let mut elems = vec![9.5f32; converter.move_row_count()];
+    let move_idx = converter.index_converter().move_to_index(mv, Color::Black).unwrap();
+    elems[move_idx] = 10.0;
+    let moves = legal::gen_all(&board);
+    assert!(converter.matrix_col_to_moves(&moves, Color::Black, elems.as_slice(), 0, 1, 0.1).is_empty());
+}
+
+#[test]
+fn test_converter_matrix_col_to_random_move_converts_matrix_column_to_random_move()
+{
+    let converter = Converter::new(IndexConverter::new());
+    let board = Board::from_fen("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2").unwrap();
+    let mv = Move::from_uci_legal("b1c3", &board).unwrap();
+    let mv2 = Move::from_uci_legal("d2d4", &board).unwrap();
+    let mut elems = vec![-100.0f32; converter.move_row_count()];
+    let move_idx = converter.index_converter().move_to_index(mv, Color::White).unwrap();
+    let move_idx2 = converter.index_converter().move_to_index(mv2, Color::White).unwrap();
+    elems[move_idx] = 10.0;
+    elems[move_idx2] = 10.0;
+    let moves = legal::gen_all(&board);
+    let mut has_move = false;
+    let mut has_move2 = false;
+    for _ in 0..100 {
+        match converter.matrix_col_to_random_move(&moves, Color::White, elems.as_slice(), 0, 1, 1.0) {
+            Some(mv3) if mv3 == mv => has_move = true,
+            Some(mv3) if mv3 == mv2 => has_move2 = true,
+            _ => assert!(false),
+        }
+    }
+    assert!(has_move);
+    assert!(has_move2);
+    elems[move_idx2] = 9.0;
+    assert_eq!(Some(mv), converter.matrix_col_to_random_move(&moves, Color::White, elems.as_slice(), 0, 1, 0.0));
+}
+
+#[test]
+fn test_converter_matrix_col_to_move_probs_converts_matrix_column_to_move_probabilities()
+{
+    let converter = Converter::new(IndexConverter::new());
+    let board = Board::from_fen("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2").unwrap();
+    let mv = Move::from_uci_legal("b1c3", &board).unwrap();
+    let mv2 = Move::from_uci_legal("d2d4", &board).unwrap();
+    let mut elems = vec![1.0f32; converter.move_row_count()];
+    let move_idx = converter.index_converter().move_to_index(mv, Color::White).unwrap();
+    let move_idx2 = converter.index_converter().move_to_index(mv2, Color::White).unwrap();
+    elems[move_idx] = 3.0;
+    elems[move_idx2] = 2.0;
+    let moves = legal::gen_all(&board);
+    let probs = converter.matrix_col_to_move_probs(&moves, Color::White, elems.as_slice(), 0, 1);
+    assert_eq!(moves.len(), probs.len());
+    let sum: f32 = probs.iter().map(|(_, prob)| *prob).sum();
+    assert!((sum - 1.0).abs() < 0.0001);
+    let (best_move, _) = probs.iter().fold((None, 0.0f32), |(best_move, best_prob), (mv3, prob)| if *prob > best_prob { (Some(*mv3), *prob) } else { (best_move, best_prob) });
+    assert_eq!(Some(mv), best_move);
+    let prob = probs.iter().find(|(mv3, _)| *mv3 == mv).map(|(_, prob)| *prob).unwrap();
+    let prob2 = probs.iter().find(|(mv3, _)| *mv3 == mv2).map(|(_, prob)| *prob).unwrap();
+    assert!((prob / prob2 - 1.0f32.exp()).abs() < 0.001);
+}
@@ -0,0 +1,100 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+
+/// A structure of CPU matrix.
+///
+/// The CPU matrix is a matrix that is stored in the main memory and is computed on the CPU. The
+/// elements of the CPU matrix are in row-major order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CpuMatrix
+{
+    row_count: usize,
+    col_count: usize,
+    elems: Vec<f32>,
+}
+
+impl CpuMatrix
+{
+    /// Creates a CPU matrix with the elements.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the number of elements isn't equal to the number of rows multiplied
+    /// by the number of columns.
+    pub fn new_with_elems(row_count: usize, col_count: usize, elems: &[f32]) -> Self
+    {
+        assert_eq!(row_count * col_count, elems.len());
+        CpuMatrix { row_count, col_count, elems: elems.to_vec(), }
+    }
+
+    /// Returns the number of rows.
+    pub fn row_count(&self) -> usize
+    { self.row_count }
+
+    /// Returns the number of columns.
+    pub fn col_count(&self) -> usize
+    { self.col_count }
+
+    /// Returns the elements of the CPU matrix.
+    pub fn elems(&self) -> &[f32]
+    { self.elems.as_slice() }
+
+    /// Multiplies the CPU matrix by the `b` matrix and adds the `c` column to each column of the
+    /// product.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the CPU matrices have invalid sizes.
+    pub fn mul_add_col(&self, b: &CpuMatrix, c: &CpuMatrix) -> CpuMatrix
+    {
+        assert_eq!(self.col_count, b.row_count);
+        assert_eq!(self.row_count, c.row_count);
+        assert_eq!(1, c.col_count);
+        let mut elems = vec![0.0f32; self.row_count * b.col_count];
+        for i in 0..self.row_count {
+            let row = &self.elems[(i * self.col_count)..((i + 1) * self.col_count)];
+            let dst_row = &mut elems[(i * b.col_count)..((i + 1) * b.col_count)];
+            dst_row.fill(c.elems[i]);
+            for (k, a) in row.iter().enumerate() {
+                let b_row = &b.elems[(k * b.col_count)..((k + 1) * b.col_count)];
+                for (dst_elem, b_elem) in dst_row.iter_mut().zip(b_row.iter()) {
+                    *dst_elem += a * b_elem;
+                }
+            }
+        }
+        CpuMatrix { row_count: self.row_count, col_count: b.col_count, elems, }
+    }
+
+    /// Multiplies the elements of the CPU matrix by the elements of the `b` matrix.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the CPU matrices have different sizes.
+    pub fn mul_elems(&self, b: &CpuMatrix) -> CpuMatrix
+    {
+        assert_eq!(self.row_count, b.row_count);
+        assert_eq!(self.col_count, b.col_count);
+        let elems: Vec<f32> = self.elems.iter().zip(b.elems.iter()).map(|(a, b)| a * b).collect();
+        CpuMatrix { row_count: self.row_count, col_count: self.col_count, elems, }
+    }
+
+    /// Applies the function to each element of the CPU matrix.
+    pub fn map<F>(&self, f: F) -> CpuMatrix
+        where F: FnMut(&f32) -> f32
+    {
+        let elems: Vec<f32> = self.elems.iter().map(f).collect();
+        CpuMatrix { row_count: self.row_count, col_count: self.col_count, elems, }
+    }
+
+    /// Computes a hyperbolic tangent for each element of the CPU matrix.
+    pub fn tanh(&self) -> CpuMatrix
+    { self.map(|x| x.tanh()) }
+}
+
+#[cfg(test)]
+mod tests;
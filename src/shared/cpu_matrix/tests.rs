@@ -0,0 +1,38 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use super::*;
+
+#[test]
+fn test_cpu_matrix_mul_add_col_multiplies_and_adds_column()
+{
+    let a = CpuMatrix::new_with_elems(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let b = CpuMatrix::new_with_elems(3, 2, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let c = CpuMatrix::new_with_elems(2, 1, &[0.5, -1.0]);
+    let d = a.mul_add_col(&b, &c);
+    assert_eq!(2, d.row_count());
+    assert_eq!(2, d.col_count());
+    assert_eq!(&[22.5, 28.5, 48.0, 63.0], d.elems());
+}
+
+#[test]
+fn test_cpu_matrix_mul_elems_multiplies_elements()
+{
+    let a = CpuMatrix::new_with_elems(2, 2, &[1.0, 2.0, 3.0, 4.0]);
+    let b = CpuMatrix::new_with_elems(2, 2, &[2.0, -1.0, 0.5, 3.0]);
+    assert_eq!(&[2.0, -2.0, 1.5, 12.0], a.mul_elems(&b).elems());
+}
+
+#[test]
+fn test_cpu_matrix_tanh_computes_hyperbolic_tangent()
+{
+    let a = CpuMatrix::new_with_elems(1, 3, &[0.0, 1.0, -2.0]);
+    let b = a.tanh();
+    assert_eq!(1, b.row_count());
+    assert_eq!(3, b.col_count());
+    assert_eq!(&[0.0f32, 1.0f32.tanh(), (-2.0f32).tanh()], b.elems());
+}
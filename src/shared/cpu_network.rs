@@ -0,0 +1,128 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::shared::cpu_matrix::*;
+use crate::shared::net::*;
+use crate::shared::Interruption;
+
+/// A structure of CPU layer.
+///
+/// The CPU layer contains a matrix of weights and a matrix of biases of one layer of the CPU
+/// neural network.
+#[derive(Clone, Debug)]
+pub struct CpuLayer
+{
+    w: CpuMatrix,
+    b: CpuMatrix,
+}
+
+impl CpuLayer
+{
+    /// Creates a CPU layer.
+    pub fn new(w: CpuMatrix, b: CpuMatrix) -> Self
+    { CpuLayer { w, b, } }
+
+    /// Returns the matrix of weights.
+    pub fn w(&self) -> &CpuMatrix
+    { &self.w }
+
+    /// Returns the matrix of biases.
+    pub fn b(&self) -> &CpuMatrix
+    { &self.b }
+
+    /// Computes `w * x + b` for the CPU layer.
+    pub fn compute(&self, x: &CpuMatrix) -> CpuMatrix
+    { self.w.mul_add_col(x, &self.b) }
+
+    /// Checks the CPU layer.
+    pub fn check(&self, input_count: usize, output_count: usize) -> bool
+    {
+        if self.w.row_count() != output_count { return false; }
+        if self.w.col_count() != input_count { return false; }
+        if self.b.row_count() != output_count { return false; }
+        if self.b.col_count() != 1 { return false; }
+        true
+    }
+}
+
+pub(crate) fn copy_output_elems<OF>(o: &CpuMatrix, output_elems: &mut [f32], of: &mut OF) -> Result<(), Interruption>
+    where OF: FnMut(&[f32]) -> Result<(), Interruption>
+{
+    let elem_count = o.elems().len();
+    output_elems[0..elem_count].copy_from_slice(o.elems());
+    of(&output_elems[0..elem_count])
+}
+
+/// A structure of CPU neural network.
+///
+/// The CPU neural network is the neural network of the first version which is computed on the
+/// CPU. This neural network only computes the forward pass, so it can't be trained.
+#[derive(Clone, Debug)]
+pub struct CpuNetwork
+{
+    input_layer: CpuLayer,
+    search_layer: CpuLayer,
+    pv_layer: CpuLayer,
+    output_layer: CpuLayer,
+}
+
+impl CpuNetwork
+{
+    /// Creates a CPU neural network.
+    pub fn new(input_layer: CpuLayer, search_layer: CpuLayer, pv_layer: CpuLayer, output_layer: CpuLayer) -> Self
+    { CpuNetwork { input_layer, search_layer, pv_layer, output_layer, } }
+
+    /// Returns the input layer.
+    pub fn input_layer(&self) -> &CpuLayer
+    { &self.input_layer }
+
+    /// Returns the search layer.
+    pub fn search_layer(&self) -> &CpuLayer
+    { &self.search_layer }
+
+    /// Returns the PV layer.
+    pub fn pv_layer(&self) -> &CpuLayer
+    { &self.pv_layer }
+
+    /// Returns the output layer.
+    pub fn output_layer(&self) -> &CpuLayer
+    { &self.output_layer }
+
+    /// Checks the CPU neural network.
+    pub fn check(&self, input_count: usize, output_count: usize) -> bool
+    {
+        let middle_count = self.input_layer.w().row_count();
+        if middle_count == 0 { return false; }
+        if !self.input_layer.check(input_count, middle_count) { return false; }
+        if !self.search_layer.check(middle_count, middle_count) { return false; }
+        if !self.pv_layer.check(middle_count, middle_count) { return false; }
+        if !self.output_layer.check(middle_count, output_count) { return false; }
+        true
+    }
+}
+
+impl ForwardNet for CpuNetwork
+{
+    fn compute_elems<OF>(&self, input_elems: &[f32], col_count: usize, depth: usize, pv_count: usize, output_elems: &mut [f32], mut of: OF) -> Result<(), Interruption>
+        where OF: FnMut(&[f32]) -> Result<(), Interruption>
+    {
+        let i = CpuMatrix::new_with_elems(input_elems.len() / col_count, col_count, input_elems);
+        let mut h = self.input_layer.compute(&i).tanh();
+        for _ in 0..depth {
+            h = self.search_layer.compute(&h).tanh();
+        }
+        for _ in 0..pv_count {
+            h = self.pv_layer.compute(&h).tanh();
+            let o = self.output_layer.compute(&h);
+            copy_output_elems(&o, output_elems, &mut of)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,68 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::shared::cpu_matrix::*;
+use crate::shared::cpu_network::*;
+use crate::shared::net::*;
+use crate::shared::Interruption;
+
+#[derive(Clone, Debug)]
+pub struct CpuNetworkV2
+{
+    input_layer: CpuLayer,
+    output_layer: CpuLayer,
+    neg_sq_output_layer: CpuLayer,
+}
+
+impl CpuNetworkV2
+{
+    pub fn new(input_layer: CpuLayer, output_layer: CpuLayer) -> Self
+    {
+        let neg_sq_output_layer = neg_sq_layer(&output_layer);
+        CpuNetworkV2 { input_layer, output_layer, neg_sq_output_layer, }
+    }
+
+    pub fn input_layer(&self) -> &CpuLayer
+    { &self.input_layer }
+
+    pub fn output_layer(&self) -> &CpuLayer
+    { &self.output_layer }
+
+    pub fn check(&self, input_count: usize, output_count: usize) -> bool
+    {
+        let middle_count = self.input_layer.w().row_count();
+        if middle_count == 0 { return false; }
+        if !self.input_layer.check(input_count, middle_count) { return false; }
+        if !self.output_layer.check(middle_count, output_count) { return false; }
+        true
+    }
+}
+
+pub(crate) fn neg_sq_layer(layer: &CpuLayer) -> CpuLayer
+{
+    // -((w (*) w) * h + (b (*) b)) = (-(w (*) w)) * h + (-(b (*) b))
+    let w = layer.w().map(|x| -(x * x));
+    let b = layer.b().map(|x| -(x * x));
+    CpuLayer::new(w, b)
+}
+
+impl ForwardNet for CpuNetworkV2
+{
+    fn compute_elems<OF>(&self, input_elems: &[f32], col_count: usize, depth: usize, pv_count: usize, output_elems: &mut [f32], mut of: OF) -> Result<(), Interruption>
+        where OF: FnMut(&[f32]) -> Result<(), Interruption>
+    {
+        assert_eq!(1, depth);
+        assert_eq!(1, pv_count);
+        let i = CpuMatrix::new_with_elems(input_elems.len() / col_count, col_count, input_elems);
+        let h = self.input_layer.compute(&i).tanh();
+        let o = self.neg_sq_output_layer.compute(&h);
+        copy_output_elems(&o, output_elems, &mut of)
+    }
+}
+
+#[cfg(test)]
+mod tests;
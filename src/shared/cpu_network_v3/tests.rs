@@ -0,0 +1,103 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::matrix::Matrix;
+use crate::shared::network_v3::*;
+use crate::shared::xavier_init::*;
+use super::*;
+
+fn assert_elems_are_near(expected: &[f32], actual: &[f32])
+{
+    assert_eq!(expected.len(), actual.len());
+    for (expected_elem, actual_elem) in expected.iter().zip(actual.iter()) {
+        assert!((expected_elem - actual_elem).abs() < 0.0001, "{} != {}", expected_elem, actual_elem);
+    }
+}
+
+fn random_elems(row_count: usize, col_count: usize) -> Vec<f32>
+{
+    let mut elems = vec![0.0f32; row_count * col_count];
+    xavier_init(elems.as_mut_slice(), col_count, row_count);
+    elems
+}
+
+#[test]
+fn test_cpu_network_v3_compute_elems_computes_outputs()
+{
+    let input_layer = CpuLayer::new(CpuMatrix::new_with_elems(1, 2, &[1.0, 0.0]), CpuMatrix::new_with_elems(1, 1, &[0.0]));
+    let search_layer = CpuLayer::new(CpuMatrix::new_with_elems(1, 1, &[1.0]), CpuMatrix::new_with_elems(1, 1, &[0.0]));
+    let pv_layer = CpuLayer::new(CpuMatrix::new_with_elems(1, 1, &[1.0]), CpuMatrix::new_with_elems(1, 1, &[0.0]));
+    let output_layer = CpuLayer::new(CpuMatrix::new_with_elems(2, 1, &[1.0, 2.0]), CpuMatrix::new_with_elems(2, 1, &[0.5, -0.5]));
+    let network = CpuNetworkV3::new(input_layer, search_layer, pv_layer, output_layer);
+    assert!(network.check(2, 2));
+    let mut output_elems = vec![0.0f32; 4];
+    let mut os: Vec<Vec<f32>> = Vec::new();
+    network.compute_elems(&[0.5, 1.0, 0.0, 2.0], 2, 1, 2, output_elems.as_mut_slice(), |o| {
+            os.push(o.to_vec());
+            Ok(())
+    }).unwrap();
+    let h1 = [0.5f32.tanh().tanh().tanh(), 1.0f32.tanh().tanh().tanh()];
+    let h2 = [h1[0].tanh(), h1[1].tanh()];
+    assert_eq!(2, os.len());
+    assert_elems_are_near(&[-(h1[0] + 0.25), -(h1[1] + 0.25), -(4.0 * h1[0] + 0.25), -(4.0 * h1[1] + 0.25)], os[0].as_slice());
+    assert_elems_are_near(&[-(h2[0] + 0.25), -(h2[1] + 0.25), -(4.0 * h2[0] + 0.25), -(4.0 * h2[1] + 0.25)], os[1].as_slice());
+}
+
+#[test]
+fn test_cpu_network_v3_check_complains_on_invalid_sizes()
+{
+    let input_layer = CpuLayer::new(CpuMatrix::new_with_elems(1, 2, &[1.0, 0.0]), CpuMatrix::new_with_elems(1, 1, &[0.0]));
+    let search_layer = CpuLayer::new(CpuMatrix::new_with_elems(1, 1, &[1.0]), CpuMatrix::new_with_elems(1, 1, &[0.0]));
+    let pv_layer = CpuLayer::new(CpuMatrix::new_with_elems(1, 1, &[1.0]), CpuMatrix::new_with_elems(1, 1, &[0.0]));
+    let output_layer = CpuLayer::new(CpuMatrix::new_with_elems(2, 1, &[1.0, 2.0]), CpuMatrix::new_with_elems(2, 1, &[0.5, -0.5]));
+    let network = CpuNetworkV3::new(input_layer, search_layer, pv_layer, output_layer);
+    assert!(!network.check(3, 2));
+    assert!(!network.check(2, 3));
+}
+
+#[test]
+fn test_cpu_network_v3_compute_elems_computes_same_outputs_as_network_v3()
+{
+    let iw_elems = random_elems(20, 10);
+    let ib_elems = random_elems(20, 1);
+    let sw_elems = random_elems(20, 20);
+    let sb_elems = random_elems(20, 1);
+    let pw_elems = random_elems(20, 20);
+    let pb_elems = random_elems(20, 1);
+    let ow_elems = random_elems(15, 20);
+    let ob_elems = random_elems(15, 1);
+    let i_elems = random_elems(10, 5);
+    let input_layer = CpuLayer::new(CpuMatrix::new_with_elems(20, 10, iw_elems.as_slice()), CpuMatrix::new_with_elems(20, 1, ib_elems.as_slice()));
+    let search_layer = CpuLayer::new(CpuMatrix::new_with_elems(20, 20, sw_elems.as_slice()), CpuMatrix::new_with_elems(20, 1, sb_elems.as_slice()));
+    let pv_layer = CpuLayer::new(CpuMatrix::new_with_elems(20, 20, pw_elems.as_slice()), CpuMatrix::new_with_elems(20, 1, pb_elems.as_slice()));
+    let output_layer = CpuLayer::new(CpuMatrix::new_with_elems(15, 20, ow_elems.as_slice()), CpuMatrix::new_with_elems(15, 1, ob_elems.as_slice()));
+    let cpu_network = CpuNetworkV3::new(input_layer, search_layer, pv_layer, output_layer);
+    let iw = Matrix::new_with_elems(20, 10, iw_elems.as_slice());
+    let ib = Matrix::new_with_elems(20, 1, ib_elems.as_slice());
+    let sw = Matrix::new_with_elems(20, 20, sw_elems.as_slice());
+    let sb = Matrix::new_with_elems(20, 1, sb_elems.as_slice());
+    let pw = Matrix::new_with_elems(20, 20, pw_elems.as_slice());
+    let pb = Matrix::new_with_elems(20, 1, pb_elems.as_slice());
+    let ow = Matrix::new_with_elems(15, 20, ow_elems.as_slice());
+    let ob = Matrix::new_with_elems(15, 1, ob_elems.as_slice());
+    let network = NetworkV3::new(iw, ib, sw, sb, pw, pb, ow, ob);
+    let mut output_elems = vec![0.0f32; 15 * 5];
+    let mut cpu_os: Vec<Vec<f32>> = Vec::new();
+    cpu_network.compute_elems(i_elems.as_slice(), 5, 2, 2, output_elems.as_mut_slice(), |o| {
+            cpu_os.push(o.to_vec());
+            Ok(())
+    }).unwrap();
+    let mut os: Vec<Vec<f32>> = Vec::new();
+    network.compute_elems(i_elems.as_slice(), 5, 2, 2, output_elems.as_mut_slice(), |o| {
+            os.push(o.to_vec());
+            Ok(())
+    }).unwrap();
+    assert_eq!(2, cpu_os.len());
+    assert_eq!(2, os.len());
+    assert_elems_are_near(os[0].as_slice(), cpu_os[0].as_slice());
+    assert_elems_are_near(os[1].as_slice(), cpu_os[1].as_slice());
+}
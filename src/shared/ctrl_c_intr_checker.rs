@@ -75,4 +75,7 @@ impl IntrCheck for CtrlCIntrChecker
 
     fn set_first(&self, _is_first: bool) -> bool
     { false }
+
+    fn set_ponder(&self, _is_ponder: bool) -> bool
+    { false }
 }
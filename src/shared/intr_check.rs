@@ -48,6 +48,12 @@ pub trait IntrCheck
     /// If the first search flag is enabled, the timeout and the stop flag are ignored. This method
     /// returns `true` if this operation is successful, otherwise `false`.
     fn set_first(&self, is_first: bool) -> bool;
+
+    /// Sets the ponder flag.
+    ///
+    /// If the ponder flag is enabled, the timeout is ignored. This method returns `true` if this
+    /// operation is successful, otherwise `false`.
+    fn set_ponder(&self, is_ponder: bool) -> bool;
 }
 
 /// A structure of empty interruption checker.
@@ -82,4 +88,7 @@ impl IntrCheck for EmptyIntrChecker
 
     fn set_first(&self, _is_first: bool) -> bool
     { false }
+
+    fn set_ponder(&self, _is_ponder: bool) -> bool
+    { false }
 }
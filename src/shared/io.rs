@@ -15,6 +15,11 @@ use std::io::Result;
 use std::io::Write;
 use std::path::Path;
 use crate::matrix::Matrix;
+use crate::shared::cpu_matrix::CpuMatrix;
+use crate::shared::cpu_network::CpuLayer;
+use crate::shared::cpu_network::CpuNetwork;
+use crate::shared::cpu_network_v2::CpuNetworkV2;
+use crate::shared::cpu_network_v3::CpuNetworkV3;
 use crate::shared::network::Network;
 use crate::shared::network_v2::NetworkV2;
 use crate::shared::network_v3::NetworkV3;
@@ -87,8 +92,7 @@ impl Load<NetworkV3> for NetworkV3Loader
 }
 
 
-/// Reads a matrix from the reader.
-pub fn read_matrix(r: &mut dyn Read) -> Result<Matrix>
+fn read_matrix_elems(r: &mut dyn Read) -> Result<(usize, usize, Vec<f32>)>
 {
     let mut u64_buf: [u8; 8] = [0; 8];
     r.read_exact(&mut u64_buf)?;
@@ -113,9 +117,32 @@ pub fn read_matrix(r: &mut dyn Read) -> Result<Matrix>
         r.read_exact(&mut f32_buf)?;
         elems[i] = f32::from_le_bytes(f32_buf);
     }
+    Ok((row_count, col_count, elems))
+}
+
+/// Reads a matrix from the reader.
+pub fn read_matrix(r: &mut dyn Read) -> Result<Matrix>
+{
+    let (row_count, col_count, elems) = read_matrix_elems(r)?;
     Ok(Matrix::new_with_elems(row_count, col_count, elems.as_slice()))
 }
 
+/// Reads a CPU matrix from the reader.
+///
+/// The CPU matrix has the same format as the matrix.
+pub fn read_cpu_matrix(r: &mut dyn Read) -> Result<CpuMatrix>
+{
+    let (row_count, col_count, elems) = read_matrix_elems(r)?;
+    Ok(CpuMatrix::new_with_elems(row_count, col_count, elems.as_slice()))
+}
+
+fn read_cpu_layer(r: &mut dyn Read) -> Result<CpuLayer>
+{
+    let w = read_cpu_matrix(r)?;
+    let b = read_cpu_matrix(r)?;
+    Ok(CpuLayer::new(w, b))
+}
+
 /// Writes the matrix to the writer.
 pub fn write_matrix(w: &mut dyn Write, matrix: &Matrix) -> Result<()>
 {
@@ -265,5 +292,70 @@ pub fn save_network_v3<P: AsRef<Path>>(path: P, network: &NetworkV3) -> Result<(
     write_network_v3(&mut w, network)
 }
 
+/// Reads a CPU neural network from the reader.
+///
+/// The CPU neural network has the same format as the neural network.
+pub fn read_cpu_network(r: &mut dyn Read) -> Result<CpuNetwork>
+{
+    let mut magic_buf: [u8; 12] = [0; 12];
+    r.read_exact(&mut magic_buf)?;
+    if &magic_buf != b"neurina_v001" {
+        return Err(Error::new(ErrorKind::InvalidData, "invalid network format"));
+    }
+    let input_layer = read_cpu_layer(r)?;
+    let search_layer = read_cpu_layer(r)?;
+    let pv_layer = read_cpu_layer(r)?;
+    let output_layer = read_cpu_layer(r)?;
+    Ok(CpuNetwork::new(input_layer, search_layer, pv_layer, output_layer))
+}
+
+/// Loads a CPU neural network from the file.
+pub fn load_cpu_network<P: AsRef<Path>>(path: P) -> Result<CpuNetwork>
+{
+    let file = File::open(path)?;
+    let mut r = BufReader::new(file);
+    read_cpu_network(&mut r)
+}
+
+pub fn read_cpu_network_v2(r: &mut dyn Read) -> Result<CpuNetworkV2>
+{
+    let mut magic_buf: [u8; 12] = [0; 12];
+    r.read_exact(&mut magic_buf)?;
+    if &magic_buf != b"neurina_v002" {
+        return Err(Error::new(ErrorKind::InvalidData, "invalid network format"));
+    }
+    let input_layer = read_cpu_layer(r)?;
+    let output_layer = read_cpu_layer(r)?;
+    Ok(CpuNetworkV2::new(input_layer, output_layer))
+}
+
+pub fn load_cpu_network_v2<P: AsRef<Path>>(path: P) -> Result<CpuNetworkV2>
+{
+    let file = File::open(path)?;
+    let mut r = BufReader::new(file);
+    read_cpu_network_v2(&mut r)
+}
+
+pub fn read_cpu_network_v3(r: &mut dyn Read) -> Result<CpuNetworkV3>
+{
+    let mut magic_buf: [u8; 12] = [0; 12];
+    r.read_exact(&mut magic_buf)?;
+    if &magic_buf != b"neurina_v003" {
+        return Err(Error::new(ErrorKind::InvalidData, "invalid network format"));
+    }
+    let input_layer = read_cpu_layer(r)?;
+    let search_layer = read_cpu_layer(r)?;
+    let pv_layer = read_cpu_layer(r)?;
+    let output_layer = read_cpu_layer(r)?;
+    Ok(CpuNetworkV3::new(input_layer, search_layer, pv_layer, output_layer))
+}
+
+pub fn load_cpu_network_v3<P: AsRef<Path>>(path: P) -> Result<CpuNetworkV3>
+{
+    let file = File::open(path)?;
+    let mut r = BufReader::new(file);
+    read_cpu_network_v3(&mut r)
+}
+
 #[cfg(test)]
 mod tests;
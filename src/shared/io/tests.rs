@@ -206,3 +206,71 @@ fn test_write_network_v3_and_read_network_v3_writes_network_v3_and_reads_network
         Err(_) => assert!(false),
     }
 }
+
+fn push_matrix_bytes(bytes: &mut Vec<u8>, row_count: usize, col_count: usize, elems: &[f32])
+{
+    bytes.extend_from_slice(&(row_count as u64).to_le_bytes());
+    bytes.extend_from_slice(&(col_count as u64).to_le_bytes());
+    for elem in elems {
+        bytes.extend_from_slice(&elem.to_le_bytes());
+    }
+}
+
+#[test]
+fn test_read_cpu_matrix_reads_cpu_matrix()
+{
+    let mut bytes: Vec<u8> = Vec::new();
+    push_matrix_bytes(&mut bytes, 2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let mut cursor = Cursor::new(bytes);
+    match read_cpu_matrix(&mut cursor) {
+        Ok(a) => {
+            assert_eq!(2, a.row_count());
+            assert_eq!(3, a.col_count());
+            assert_eq!(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], a.elems());
+        },
+        Err(_) => assert!(false),
+    }
+}
+
+#[test]
+fn test_read_cpu_network_v3_reads_cpu_network_v3()
+{
+    let mut bytes: Vec<u8> = Vec::new();
+    bytes.extend_from_slice(b"neurina_v003");
+    push_matrix_bytes(&mut bytes, 2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    push_matrix_bytes(&mut bytes, 2, 1, &[7.0, 8.0]);
+    push_matrix_bytes(&mut bytes, 2, 2, &[1.0, 0.0, 0.0, 1.0]);
+    push_matrix_bytes(&mut bytes, 2, 1, &[0.5, 0.5]);
+    push_matrix_bytes(&mut bytes, 2, 2, &[0.0, 1.0, 1.0, 0.0]);
+    push_matrix_bytes(&mut bytes, 2, 1, &[-0.5, -0.5]);
+    push_matrix_bytes(&mut bytes, 1, 2, &[2.0, 3.0]);
+    push_matrix_bytes(&mut bytes, 1, 1, &[4.0]);
+    let mut cursor = Cursor::new(bytes);
+    match read_cpu_network_v3(&mut cursor) {
+        Ok(network) => {
+            assert!(network.check(3, 1));
+            assert_eq!(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], network.input_layer().w().elems());
+            assert_eq!(&[7.0, 8.0], network.input_layer().b().elems());
+            assert_eq!(&[1.0, 0.0, 0.0, 1.0], network.search_layer().w().elems());
+            assert_eq!(&[0.5, 0.5], network.search_layer().b().elems());
+            assert_eq!(&[0.0, 1.0, 1.0, 0.0], network.pv_layer().w().elems());
+            assert_eq!(&[-0.5, -0.5], network.pv_layer().b().elems());
+            assert_eq!(&[2.0, 3.0], network.output_layer().w().elems());
+            assert_eq!(&[4.0], network.output_layer().b().elems());
+        },
+        Err(_) => assert!(false),
+    }
+}
+
+#[test]
+fn test_read_cpu_network_v3_complains_on_invalid_format()
+{
+    let mut bytes: Vec<u8> = Vec::new();
+    bytes.extend_from_slice(b"neurina_v001");
+    push_matrix_bytes(&mut bytes, 1, 1, &[1.0]);
+    let mut cursor = Cursor::new(bytes);
+    match read_cpu_network_v3(&mut cursor) {
+        Ok(_) => assert!(false),
+        Err(err) => assert_eq!(ErrorKind::InvalidData, err.kind()),
+    }
+}
@@ -69,9 +69,31 @@ impl<T> MatrixBuffer<T>
     /// The first closure converts the elements and sets the elements of matrices in buffers. The
     /// second clusore processes matrices which are created from the elements of matrices with using
     /// the middle buffer.
-    pub fn do_elems<U, F, G>(&mut self, elems: &mut [U], output_count: usize, intr_checker: &dyn IntrCheck, mut f: F, mut g: G) -> Result<(), Interruption>
+    pub fn do_elems<U, F, G>(&mut self, elems: &mut [U], output_count: usize, intr_checker: &dyn IntrCheck, f: F, mut g: G) -> Result<(), Interruption>
         where F: FnMut(&U, &mut [f32], &mut [Vec<f32>], usize, usize),
             G: FnMut(Matrix, &[Matrix], &mut T, &mut [U]) -> Result<(), Interruption>
+    {
+        let input_row_count = self.input_row_count;
+        let output_row_count = self.output_row_count;
+        self.do_elem_bufs(elems, output_count, intr_checker, f, |input_elems, output_bufs, middle_buf, elems| {
+                let col_count = elems.len();
+                let input = Matrix::new_with_elems(input_row_count, col_count, input_elems);
+                let outputs: Vec<Matrix> = output_bufs.iter().map(|output_buf| {
+                        Matrix::new_with_elems(output_row_count, col_count, &output_buf[0..(output_row_count * col_count)])
+                }).collect();
+                g(input, outputs.as_slice(), middle_buf, elems)
+        })
+    }
+
+    /// Processes the elements with using the buffers of elements of matrices without creating
+    /// matrices.
+    ///
+    /// This method is like [`do_elems`](Self::do_elems) but the second closure takes the elements
+    /// of input matrix and the buffers of elements of output matrices instead of matrices, so this
+    /// method doesn't need a backend for operations on matrices.
+    pub fn do_elem_bufs<U, F, G>(&mut self, elems: &mut [U], output_count: usize, intr_checker: &dyn IntrCheck, mut f: F, mut g: G) -> Result<(), Interruption>
+        where F: FnMut(&U, &mut [f32], &mut [Vec<f32>], usize, usize),
+            G: FnMut(&[f32], &[Vec<f32>], &mut T, &mut [U]) -> Result<(), Interruption>
     {
         for i in (0..elems.len()).step_by(self.max_col_count) {
             intr_checker.check()?;
@@ -79,11 +101,7 @@ impl<T> MatrixBuffer<T>
             for j in 0..col_count {
                 f(&elems[i + j], self.input_buf.as_mut_slice(), &mut self.output_bufs[0..output_count], j, col_count);
             }
-            let input = Matrix::new_with_elems(self.input_row_count, col_count, &self.input_buf[0..(self.input_row_count * col_count)]);
-            let outputs: Vec<Matrix> = (0..output_count).map(|j| {
-                    Matrix::new_with_elems(self.output_row_count, col_count, &self.output_bufs[j][0..(self.output_row_count * col_count)])
-            }).collect();
-            g(input, outputs.as_slice(), &mut self.middle_buf, &mut elems[i..(i + col_count)])?;
+            g(&self.input_buf[0..(self.input_row_count * col_count)], &self.output_bufs[0..output_count], &mut self.middle_buf, &mut elems[i..(i + col_count)])?;
         }
         Ok(())
     }
@@ -105,3 +105,39 @@ fn test_matrix_buffer_do_elems_does_elements_with_outputs_after_resize_output_bu
     assert_eq!(vec![7.0f32, 6.0f32, 7.0f32, 6.0f32], outputs[1][0].elems());
     assert_eq!(vec![8.0f32, 7.0f32, 8.0f32, 7.0f32], outputs[1][1].elems());
 }
+
+#[test]
+fn test_matrix_buffer_do_elem_bufs_does_elements_with_outputs()
+{
+    let mut elems = vec![2.0f32, 1.0f32, 4.0f32, 3.0f32, 6.0f32, 5.0f32];
+    let mut inputs: Vec<Vec<f32>> = Vec::new();
+    let mut outputs: Vec<Vec<Vec<f32>>> = Vec::new();
+    let mut matrix_buf = MatrixBuffer::new(3, 2, 4, 2, ());
+    let intr_checker = EmptyIntrChecker::new();
+    matrix_buf.do_elem_bufs(elems.as_mut_slice(), 2, &intr_checker, |e, i, os, j, col_count| {
+            i[0 * col_count + j] = *e;
+            i[1 * col_count + j] = *e;
+            i[2 * col_count + j] = *e;
+            os[0][0 * col_count + j] = *e + 1.0;
+            os[0][1 * col_count + j] = *e + 1.0;
+            os[1][0 * col_count + j] = *e + 2.0;
+            os[1][1 * col_count + j] = *e + 2.0;
+    }, |i, os, _, es| {
+            let col_count = es.len();
+            inputs.push(i.to_vec());
+            outputs.push(os.iter().map(|o| o[0..(2 * col_count)].to_vec()).collect());
+            for e in es {
+                *e += 1.0;
+            }
+            Ok(())
+    }).unwrap();
+    assert_eq!(vec![3.0f32, 2.0f32, 5.0f32, 4.0f32, 7.0f32, 6.0f32], elems);
+    assert_eq!(2, inputs.len());
+    assert_eq!(vec![2.0f32, 1.0f32, 4.0f32, 3.0f32, 2.0f32, 1.0f32, 4.0f32, 3.0f32, 2.0f32, 1.0f32, 4.0f32, 3.0f32], inputs[0]);
+    assert_eq!(vec![6.0f32, 5.0f32, 6.0f32, 5.0f32, 6.0f32, 5.0f32], inputs[1]);
+    assert_eq!(2, outputs.len());
+    assert_eq!(vec![3.0f32, 2.0f32, 5.0f32, 4.0f32, 3.0f32, 2.0f32, 5.0f32, 4.0f32], outputs[0][0]);
+    assert_eq!(vec![4.0f32, 3.0f32, 6.0f32, 5.0f32, 4.0f32, 3.0f32, 6.0f32, 5.0f32], outputs[0][1]);
+    assert_eq!(vec![7.0f32, 6.0f32, 7.0f32, 6.0f32], outputs[1][0]);
+    assert_eq!(vec![8.0f32, 7.0f32, 8.0f32, 7.0f32], outputs[1][1]);
+}
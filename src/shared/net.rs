@@ -5,6 +5,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 //
+use crate::matrix::Frontend;
 use crate::matrix::Matrix;
 use crate::shared::Interruption;
 
@@ -43,3 +44,44 @@ pub trait Net
     /// Checks the neural network.
     fn check(&self, input_count: usize, output_count: usize) -> bool;
 }
+
+/// A trait of forward pass of neural network.
+///
+/// This trait provides a method which computes outputs of the neural network from elements of
+/// the input matrix. Unlike [`Net`], this trait doesn't require a backend for operations on
+/// matrices, so it can be also implemented by the neural networks which are computed on the CPU.
+pub trait ForwardNet
+{
+    /// Computes output matrices for the neural network from elements of the input matrix.
+    ///
+    /// The elements of each output matrix are written to the output elements and then the
+    /// closure is called with the written elements. The input matrix has the specified number of
+    /// columns and the elements of matrices are in row-major order.
+    fn compute_elems<OF>(&self, input_elems: &[f32], col_count: usize, depth: usize, pv_count: usize, output_elems: &mut [f32], of: OF) -> Result<(), Interruption>
+        where OF: FnMut(&[f32]) -> Result<(), Interruption>;
+}
+
+impl<T: Net> ForwardNet for T
+{
+    fn compute_elems<OF>(&self, input_elems: &[f32], col_count: usize, depth: usize, pv_count: usize, output_elems: &mut [f32], mut of: OF) -> Result<(), Interruption>
+        where OF: FnMut(&[f32]) -> Result<(), Interruption>
+    {
+        let frontend = match Frontend::new() {
+            Ok(tmp_frontend) => tmp_frontend,
+            Err(_) => return Err(Interruption::BackendError),
+        };
+        let i = match frontend.create_matrix_and_set_elems(input_elems.len() / col_count, col_count, input_elems) {
+            Ok(tmp_i) => tmp_i,
+            Err(_) => return Err(Interruption::BackendError),
+        };
+        self.compute(&i, depth, pv_count, |_| Ok(()), |o| {
+                let elem_count = o.row_count() * o.col_count();
+                let mut is_transposed = false;
+                match frontend.get_elems_and_transpose_flag(&o, &mut output_elems[0..elem_count], &mut is_transposed) {
+                    Ok(()) => (),
+                    Err(_) => return Err(Interruption::BackendError),
+                }
+                of(&output_elems[0..elem_count])
+        })
+    }
+}
@@ -80,6 +80,7 @@ impl fmt::Display for TrainerError
             TrainerError::Interruption(Interruption::Timeout) => write!(f, "interrupted by timeout"),
             TrainerError::Interruption(Interruption::Stop) => write!(f, "interrupted by stop"),
             TrainerError::Interruption(Interruption::CtrlC) => write!(f, "interrupted by ctrl-c"),
+            TrainerError::Interruption(Interruption::BackendError) => write!(f, "interrupted by backend error"),
             TrainerError::NoGradient => write!(f, "no gradient"),
             TrainerError::Io(err) => write!(f, "{}", err),
         }